@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use types::unidades::{Gramos, GramosPorSegundo, Milisegundos};
+
+use crate::dosificador::Dosificador;
+use crate::errors::CalibracionError;
+
+/// Separador de campos en el archivo de calibraciones.
+const SEPARADOR: char = ';';
+
+/// Una medición de calibración: el dosificador se abrió `duracion` y se pesaron
+/// `gramos` de alimento a la salida.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Muestra {
+    pub duracion: Milisegundos,
+    pub gramos: Gramos,
+}
+
+/// Curva de entrega medida de un dosificador para un tipo de alimento.
+///
+/// Es una recta `gramos = entrega * ms / 1000 + desfase`, donde `entrega` es el caudal
+/// medido en g/s y `desfase` los gramos que se pierden (negativo) o ganan (positivo) en
+/// cada apertura, por ejemplo mientras el tornillo alcanza su velocidad.
+///
+/// # Ejemplo:
+///! ```
+///! let curva = Curva::new(GramosPorSegundo(1800), -150);
+///! assert_eq!(curva.calcular_gramos(Milisegundos(5000)), Gramos(8850));
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Curva {
+    /// Caudal medido.
+    entrega: GramosPorSegundo,
+
+    /// Gramos que se suman a cada apertura. Es un entero con signo y no `Gramos`
+    /// porque puede ser negativo.
+    desfase: i32,
+}
+
+impl Curva {
+    pub fn new(entrega: GramosPorSegundo, desfase: i32) -> Self {
+        Self { entrega, desfase }
+    }
+
+    /// Obtiene el caudal medido.
+    pub fn get_entrega(&self) -> GramosPorSegundo {
+        self.entrega
+    }
+
+    /// Obtiene los gramos que se suman a cada apertura.
+    pub fn get_desfase(&self) -> i32 {
+        self.desfase
+    }
+
+    /// Calcula los gramos que entrega una apertura de `ms`. Nunca es negativo.
+    pub fn calcular_gramos(&self, ms: Milisegundos) -> Gramos {
+        let gramos = i64::from((self.entrega * ms).0) + i64::from(self.desfase);
+        Gramos(gramos.clamp(0, i64::from(u32::MAX)) as u32)
+    }
+}
+
+/// Registro de los pulsos de prueba de un dosificador con un tipo de alimento.
+///
+/// Se abren pulsos de prueba de distintas duraciones, se pesa lo entregado y se
+/// ajusta una `Curva` por mínimos cuadrados.
+///
+/// # Ejemplo:
+///! ```
+///! let mut calibracion = Calibracion::new();
+///! calibracion.registrar(Milisegundos(2000), Gramos(3450)).unwrap();
+///! calibracion.registrar(Milisegundos(5000), Gramos(8850)).unwrap();
+///! let curva = calibracion.ajustar().unwrap(); // 1800 g/s, -150 g
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(Debug, Clone)]
+pub struct Calibracion {
+    muestras: Vec<Muestra>,
+}
+
+impl Calibracion {
+    pub fn new() -> Self {
+        Self {
+            muestras: Vec::new(),
+        }
+    }
+
+    /// Registra un pulso de prueba de `duracion` en el que se pesaron `gramos`.
+    ///
+    /// # Errores:
+    /// - `CalibracionError::MuestraInvalida`: La duración es 0.
+    pub fn registrar(
+        &mut self,
+        duracion: Milisegundos,
+        gramos: Gramos,
+    ) -> Result<(), CalibracionError> {
+        if duracion == Milisegundos(0) {
+            return Err(CalibracionError::MuestraInvalida);
+        }
+        self.muestras.push(Muestra { duracion, gramos });
+        Ok(())
+    }
+
+    pub fn get_muestras(&self) -> &Vec<Muestra> {
+        &self.muestras
+    }
+
+    /// Ajusta la curva de entrega a las muestras registradas por mínimos cuadrados.
+    ///
+    /// # Errores:
+    /// - `CalibracionError::MuestrasInsuficientes`: Hay menos de dos duraciones distintas.
+    /// - `CalibracionError::CurvaInvalida`: El caudal ajustado no es positivo.
+    pub fn ajustar(&self) -> Result<Curva, CalibracionError> {
+        let n = self.muestras.len() as f64;
+        let media_x = self
+            .muestras
+            .iter()
+            .map(|m| f64::from(m.duracion.0))
+            .sum::<f64>()
+            / n;
+        let media_y = self
+            .muestras
+            .iter()
+            .map(|m| f64::from(m.gramos.0))
+            .sum::<f64>()
+            / n;
+
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for muestra in &self.muestras {
+            let dx = f64::from(muestra.duracion.0) - media_x;
+            sxy += dx * (f64::from(muestra.gramos.0) - media_y);
+            sxx += dx * dx;
+        }
+        if self.muestras.len() < 2 || sxx == 0.0 {
+            return Err(CalibracionError::MuestrasInsuficientes);
+        }
+
+        // Pendiente en g/ms
+        let pendiente = sxy / sxx;
+        let entrega = (pendiente * 1000.0).round();
+        if entrega < 1.0 || entrega > f64::from(u32::MAX) {
+            return Err(CalibracionError::CurvaInvalida);
+        }
+        let desfase = (media_y - pendiente * media_x).round();
+
+        Ok(Curva::new(
+            GramosPorSegundo(entrega as u32),
+            desfase.clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32,
+        ))
+    }
+}
+
+impl Default for Calibracion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Conjunto de curvas calibradas, una por dosificador y tipo de alimento.
+///
+/// Los dosificadores se identifican por un nombre estable (por ejemplo `"linea-1"`),
+/// ya que su `id` cambia en cada ejecución. Se guardan en un archivo de texto con una
+/// curva por línea: `dosificador;alimento;entrega;desfase`.
+///
+/// # Ejemplo:
+///! ```
+///! let mut calibraciones = Calibraciones::cargar("calibraciones.txt")?;
+///! calibraciones.set_curva("linea-1", "pellet-4mm", calibracion.ajustar()?);
+///! calibraciones.guardar("calibraciones.txt")?;
+///! calibraciones.aplicar("linea-1", "pellet-4mm", &mut doser);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(Debug, Clone, Default)]
+pub struct Calibraciones {
+    curvas: HashMap<(String, String), Curva>,
+}
+
+impl Calibraciones {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Guarda o reemplaza la curva de `dosificador` con el tipo de `alimento`.
+    ///
+    /// # Errores:
+    /// - `CalibracionError::NombreInvalido`: El nombre está vacío o contiene `;` o saltos de línea.
+    pub fn set_curva(
+        &mut self,
+        dosificador: &str,
+        alimento: &str,
+        curva: Curva,
+    ) -> Result<(), CalibracionError> {
+        if !nombre_valido(dosificador) || !nombre_valido(alimento) {
+            return Err(CalibracionError::NombreInvalido);
+        }
+        self.curvas
+            .insert((dosificador.to_string(), alimento.to_string()), curva);
+        Ok(())
+    }
+
+    pub fn get_curva(&self, dosificador: &str, alimento: &str) -> Option<Curva> {
+        self.curvas
+            .get(&(dosificador.to_string(), alimento.to_string()))
+            .copied()
+    }
+
+    /// Instala en `doser` la curva calibrada para `dosificador` y `alimento`.
+    ///
+    /// # Retorna:
+    /// `true` si había una curva; si no, el dosificador queda con su entrega nominal.
+    pub fn aplicar(&self, dosificador: &str, alimento: &str, doser: &mut Dosificador) -> bool {
+        match self.get_curva(dosificador, alimento) {
+            Some(curva) => {
+                doser.set_curva(Some(curva));
+                true
+            }
+            None => {
+                doser.set_curva(None);
+                false
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.curvas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.curvas.is_empty()
+    }
+
+    /// Escribe todas las curvas en `ruta`, reemplazando su contenido.
+    pub fn guardar<P: AsRef<Path>>(&self, ruta: P) -> Result<(), CalibracionError> {
+        let mut claves: Vec<_> = self.curvas.keys().collect();
+        claves.sort();
+
+        let mut texto = String::new();
+        for clave in claves {
+            let curva = self.curvas[clave];
+            texto.push_str(&format!(
+                "{}{s}{}{s}{}{s}{}\n",
+                clave.0,
+                clave.1,
+                curva.get_entrega().0,
+                curva.get_desfase(),
+                s = SEPARADOR
+            ));
+        }
+        fs::write(ruta, texto).map_err(|e| CalibracionError::Archivo(e.kind()))
+    }
+
+    /// Lee las curvas guardadas en `ruta`. Si el archivo no existe retorna un conjunto vacío.
+    ///
+    /// # Errores:
+    /// - `CalibracionError::Formato`: Una línea no tiene el formato esperado (indica la línea, desde 1).
+    /// - `CalibracionError::Archivo`: No se pudo leer el archivo.
+    pub fn cargar<P: AsRef<Path>>(ruta: P) -> Result<Self, CalibracionError> {
+        let texto = match fs::read_to_string(ruta) {
+            Ok(texto) => texto,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(CalibracionError::Archivo(e.kind())),
+        };
+
+        let mut calibraciones = Self::new();
+        for (i, linea) in texto.lines().enumerate() {
+            let linea = linea.trim();
+            if linea.is_empty() || linea.starts_with('#') {
+                continue;
+            }
+            let error = CalibracionError::Formato(i + 1);
+
+            let campos: Vec<&str> = linea.split(SEPARADOR).collect();
+            let [dosificador, alimento, entrega, desfase] = campos[..] else {
+                return Err(error);
+            };
+            let entrega: u32 = entrega.trim().parse().map_err(|_| error)?;
+            let desfase: i32 = desfase.trim().parse().map_err(|_| error)?;
+            if entrega == 0 {
+                return Err(error);
+            }
+            calibraciones
+                .set_curva(
+                    dosificador,
+                    alimento,
+                    Curva::new(GramosPorSegundo(entrega), desfase),
+                )
+                .map_err(|_| error)?;
+        }
+        Ok(calibraciones)
+    }
+}
+
+fn nombre_valido(nombre: &str) -> bool {
+    !nombre.is_empty() && !nombre.contains(SEPARADOR) && !nombre.contains(['\n', '\r'])
+}
+
+/// Una `Curva` se serializa con su caudal en g/s y su desfase. El caudal no puede ser 0,
+/// igual que al leerla de un archivo de calibraciones.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer};
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Curva", deny_unknown_fields)]
+    struct Datos {
+        entrega: GramosPorSegundo,
+        desfase: i32,
+    }
+
+    impl<'de> Deserialize<'de> for Curva {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            if datos.entrega == GramosPorSegundo(0) {
+                return Err(D::Error::custom("entrega debe ser mayor que 0"));
+            }
+            Ok(Curva::new(datos.entrega, datos.desfase))
+        }
+    }
+}
@@ -1,10 +1,15 @@
+use types::unidades::{Gramos, GramosPorSegundo, KgPorSegundo, Milisegundos};
 use uuid::Uuid;
 
+use crate::calibracion::Curva;
+use crate::errors::DosificadorError;
+
 pub struct Dosificador {
-    // Capacidad de entrega en kg/s
-    // Aproximar al entero mas cercano
-    // No es una caracteristica que deba modificarse
-    entrega: u32,
+    // Capacidad de entrega nominal
+    entrega: GramosPorSegundo,
+
+    // Curva medida para el alimento en uso; si existe reemplaza a la entrega nominal
+    curva: Option<Curva>,
 
     estado: bool,
 
@@ -12,20 +17,89 @@ pub struct Dosificador {
 }
 
 impl Dosificador {
-    pub fn new(entrega: u32) -> Self {
-        Self {
+    /// Crea un dosificador con una capacidad de entrega de `entrega`.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let dosificador = Dosificador::new(KgPorSegundo(2)).unwrap();
+    ///! assert_eq!(dosificador.get_entrega_gramos(), GramosPorSegundo(2000));
+    ///! ```
+    ///
+    /// # Errores:
+    /// - `DosificadorError::FueraDeRango`: `entrega` es 0 o no cabe en `GramosPorSegundo`.
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn new(entrega: KgPorSegundo) -> Result<Self, DosificadorError> {
+        let entrega =
+            GramosPorSegundo::try_from(entrega).map_err(|_| DosificadorError::FueraDeRango)?;
+        Self::new_gramos(entrega)
+    }
+
+    /// Crea un dosificador con una capacidad de entrega de `entrega` en g/s,
+    /// para dosificadores que entregan fracciones de kilogramo por segundo.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let dosificador = Dosificador::new_gramos(GramosPorSegundo(1500)).unwrap(); // 1,5 kg/s
+    ///! assert_eq!(dosificador.calcular_gramos(Milisegundos(1500)), Gramos(2250));
+    ///! ```
+    ///
+    /// # Errores:
+    /// - `DosificadorError::FueraDeRango`: `entrega` es 0.
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn new_gramos(entrega: GramosPorSegundo) -> Result<Self, DosificadorError> {
+        if entrega == GramosPorSegundo(0) {
+            return Err(DosificadorError::FueraDeRango);
+        }
+        Ok(Self {
             entrega,
+            curva: None,
             estado: false,
             id: Uuid::new_v4(),
-        }
+        })
     }
+
     pub fn set_estado(&mut self, n: bool) -> &mut Dosificador {
         self.estado = n;
         self
     }
 
-    pub fn get_entrega(&self) -> u32 {
-        self.entrega
+    /// Instala la curva calibrada para el alimento en uso, o vuelve a la entrega nominal con `None`.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let mut dosificador = Dosificador::new(KgPorSegundo(2)).unwrap();
+    ///! dosificador.set_curva(Some(Curva::new(GramosPorSegundo(1800), -150)));
+    ///! assert_eq!(dosificador.calcular_gramos(Milisegundos(5000)), Gramos(8850));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_curva(&mut self, curva: Option<Curva>) -> &mut Dosificador {
+        self.curva = curva;
+        self
+    }
+
+    pub fn get_curva(&self) -> Option<Curva> {
+        self.curva
+    }
+
+    /// Obtiene la capacidad de entrega en kg/s, truncada al entero inferior.
+    pub fn get_entrega(&self) -> KgPorSegundo {
+        self.get_entrega_gramos().a_kg_por_segundo()
+    }
+
+    /// Obtiene la capacidad de entrega en g/s: la calibrada si hay curva y si no la nominal.
+    pub fn get_entrega_gramos(&self) -> GramosPorSegundo {
+        match self.curva {
+            Some(curva) => curva.get_entrega(),
+            None => self.entrega,
+        }
+    }
+
+    /// Calcula los gramos que entrega el dosificador si se mantiene abierto `ms`.
+    pub fn calcular_gramos(&self, ms: Milisegundos) -> Gramos {
+        match self.curva {
+            Some(curva) => curva.calcular_gramos(ms),
+            None => self.entrega * ms,
+        }
     }
 
     pub fn get_estado(&self) -> bool {
@@ -36,3 +110,48 @@ impl Dosificador {
         self.id
     }
 }
+
+/// Un `Dosificador` se serializa con su entrega nominal en g/s y su curva calibrada.
+/// La entrega nominal no puede ser 0.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Dosificador", deny_unknown_fields)]
+    struct Datos {
+        entrega: GramosPorSegundo,
+        #[serde(default)]
+        curva: Option<Curva>,
+        #[serde(default)]
+        estado: bool,
+        #[serde(default = "Uuid::new_v4")]
+        id: Uuid,
+    }
+
+    impl Serialize for Dosificador {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                entrega: self.entrega,
+                curva: self.curva,
+                estado: self.estado,
+                id: self.id,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Dosificador {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let mut doser = Dosificador::new_gramos(datos.entrega)
+                .map_err(|_| D::Error::custom("entrega debe ser mayor que 0"))?;
+            doser.set_curva(datos.curva).set_estado(datos.estado);
+            doser.id = datos.id;
+            Ok(doser)
+        }
+    }
+}
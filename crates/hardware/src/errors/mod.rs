@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum SiloError {
     SinAlimento,
     FueraDeRango,
@@ -6,40 +6,101 @@ pub enum SiloError {
 }
 impl PartialEq for SiloError {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (SiloError::FueraDeRango, SiloError::FueraDeRango) => true,
-            (SiloError::SinAlimento, SiloError::SinAlimento) => true,
-            (SiloError::ErrorInesperado, SiloError::ErrorInesperado) => true,
-            _ => false,
-        }
+        matches!(
+            (self, other),
+            (SiloError::FueraDeRango, SiloError::FueraDeRango)
+                | (SiloError::SinAlimento, SiloError::SinAlimento)
+                | (SiloError::ErrorInesperado, SiloError::ErrorInesperado)
+        )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum SopladorError {
     FueraDeRango,
+    AlimentoDesconocido,
     ErrorInesperado,
 }
 impl PartialEq for SopladorError {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (SopladorError::FueraDeRango, SopladorError::FueraDeRango) => true,
-            (SopladorError::ErrorInesperado, SopladorError::ErrorInesperado) => true,
-            _ => false,
-        }
+        matches!(
+            (self, other),
+            (SopladorError::FueraDeRango, SopladorError::FueraDeRango)
+                | (
+                    SopladorError::AlimentoDesconocido,
+                    SopladorError::AlimentoDesconocido
+                )
+                | (
+                    SopladorError::ErrorInesperado,
+                    SopladorError::ErrorInesperado
+                )
+        )
     }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
+pub enum DosificadorError {
+    FueraDeRango,
+}
+impl PartialEq for DosificadorError {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (
+                DosificadorError::FueraDeRango,
+                DosificadorError::FueraDeRango
+            )
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub enum SelectoraError {
     FueraDeRango,
     ErrorInesperado,
 }
 impl PartialEq for SelectoraError {
+    fn eq(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (SelectoraError::FueraDeRango, SelectoraError::FueraDeRango)
+                | (
+                    SelectoraError::ErrorInesperado,
+                    SelectoraError::ErrorInesperado
+                )
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum CalibracionError {
+    MuestraInvalida,
+    MuestrasInsuficientes,
+    CurvaInvalida,
+    NombreInvalido,
+    Formato(usize),
+    Archivo(std::io::ErrorKind),
+}
+impl PartialEq for CalibracionError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (SelectoraError::FueraDeRango, SelectoraError::FueraDeRango) => true,
-            (SelectoraError::ErrorInesperado, SelectoraError::ErrorInesperado) => true,
-            _ => false,
+            (CalibracionError::Formato(a), CalibracionError::Formato(b)) => a == b,
+            (CalibracionError::Archivo(a), CalibracionError::Archivo(b)) => a == b,
+            _ => matches!(
+                (self, other),
+                (
+                    CalibracionError::MuestraInvalida,
+                    CalibracionError::MuestraInvalida
+                ) | (
+                    CalibracionError::MuestrasInsuficientes,
+                    CalibracionError::MuestrasInsuficientes
+                ) | (
+                    CalibracionError::CurvaInvalida,
+                    CalibracionError::CurvaInvalida
+                ) | (
+                    CalibracionError::NombreInvalido,
+                    CalibracionError::NombreInvalido
+                )
+            ),
         }
     }
 }
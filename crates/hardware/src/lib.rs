@@ -1,7 +1,10 @@
+pub mod calibracion;
 pub mod dosificador;
+pub mod rampa;
 pub mod selectora;
 pub mod silo;
 pub mod soplador;
 
 pub mod errors;
 pub mod logs;
+pub mod traits;
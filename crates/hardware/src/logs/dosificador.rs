@@ -1,17 +1,16 @@
 use crate::dosificador::Dosificador;
-use crate::logs::Print;
+use crate::logs::{Print, Registro};
 
 impl Print for Dosificador {
-    /// Imprime el estado del dosificador.
-    ///
-    /// Muestra el `id` y si el dosificador está `encendido` o `apagado`.
+    /// Muestra la entrega y si el dosificador está `encendido` o `apagado`.
     ///
     /// # Ejemplo:
     ///! ```rust
-    ///! let dosificador = Dosificador::new();
-    ///! dosificador.print();
+    ///! let dosificador = Dosificador::new(KgPorSegundo(2)).unwrap();
+    ///! dosificador.print(); // [Dosificador][id]: [2kg/s] -- [Apagado] --
     ///! ```
-    fn print(&self) -> String {
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
         let estado: &'static str = {
             if self.get_estado() {
                 "Encendido"
@@ -20,13 +19,15 @@ impl Print for Dosificador {
             }
         };
 
-        let texto = format!(
-            "[Dosificador][{}][{}]: -- [{}] --",
-            self.get_id(),
-            self.get_entrega(),
-            estado
-        );
-        println!("{}", texto);
-        texto
+        format!("[{}] -- [{}] --", self.get_entrega(), estado)
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("Dosificador")
+            .id(self.get_id())
+            .campo("entrega", self.get_entrega())
+            .campo("entrega_gramos", self.get_entrega_gramos())
+            .campo("estado", self.get_estado())
+            .campo("calibrado", self.get_curva().is_some())
     }
 }
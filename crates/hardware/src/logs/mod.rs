@@ -1,12 +1,8 @@
 pub mod dosificador;
+pub mod selectora;
 pub mod silo;
 pub mod soplador;
 
-/// El trait `Print` permite que un tipo implemente el método `print`
-/// para mostrar su estado de manera legible.
-///
-/// # Métodos:
-/// - `print`: Imprime la información relevante del tipo en la consola.
-pub trait Print {
-    fn print(&self) -> String;
-}
+/// El trait `Print` es el mismo para el hardware y el sistema; se define en
+/// `types::logs` junto al `Registro` que entrega cada componente.
+pub use types::logs::{Print, Registro, Valor};
@@ -0,0 +1,30 @@
+use crate::logs::{Print, Registro};
+use crate::selectora::Selectora;
+
+impl Print for Selectora {
+    /// Muestra la salida actual de la selectora y la última posible.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let selectora = Selectora::new(5);
+    ///! selectora.print(); // [Selectora][id]: [Salida: 0 de 0..=5]
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
+        let posiciones = self.get_posiciones();
+        format!(
+            "[Salida: {} de {}..={}]",
+            self.get_posicion(),
+            posiciones.min,
+            posiciones.max
+        )
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("Selectora")
+            .id(self.get_id())
+            .campo("posicion", self.get_posicion())
+            .campo("posicion_maxima", self.get_posiciones().max)
+            .campo("espera", self.get_espera())
+    }
+}
@@ -1,30 +1,28 @@
-use crate::logs::Print;
+use crate::logs::{Print, Registro};
 use crate::silo::Silo;
 
 impl Print for Silo {
-    /// Imprime la información del silo y la cantidad de alimento que se va a entregar.
-    ///
-    /// # Parámetros:
-    /// - `pulso`: La cantidad de alimento que se entregará.
-    ///
-    /// # Retorna:
-    /// Una referencia al silo para permitir el encadenamiento de métodos.
+    /// Muestra el alimento actual e histórico del silo en kilogramos.
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new();
+    ///! let mut silo = Silo::new().unwrap();
     ///! silo.set_alimento(1000);
-    ///! silo.print(200);
+    ///! silo.print(); // [Silo][id]: [Actual: 1000kg][Historico: 1000kg]
     ///! ```
-    fn print(&self) -> String {
-        let texto = format!(
-            "\n[Silo][{}][Actual: {}kg][Historico: {}kg]",
-            self.get_id(),
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
+        format!(
+            "[Actual: {}][Historico: {}]",
             self.get_alimento(),
             self.get_historico()
-        );
+        )
+    }
 
-        println!("{}", texto);
-        texto
+    fn registro(&self) -> Registro {
+        Registro::new("Silo")
+            .id(self.get_id())
+            .campo("alimento_gramos", self.get_alimento_gramos())
+            .campo("historico_gramos", self.get_historico_gramos())
     }
 }
@@ -1,31 +1,32 @@
-use crate::logs::Print;
+use crate::logs::{Print, Registro};
 use crate::soplador::Soplador;
+
 impl Print for Soplador {
-    /// Imprime el estado del soplador.
-    ///
-    /// Muestra el `id`, la `potencia`, y si está `encendido` o `apagado`.
+    /// Muestra la `potencia` y si el soplador está `encendido` o `apagado`.
     ///
     /// # Ejemplo:
     ///! ```rust
     ///! let soplador = Soplador::new();
-    ///! soplador.print();
+    ///! soplador.print(); // [Soplador][id]: [0%] -- [Apagado] --
     ///! ```
-    fn print(&self) -> String {
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
         let estado = if self.get_estado() {
             "Encendido"
         } else {
             "Apagado"
         };
 
-        let texto = format!(
-            "[Soplador][{}][{}%]: -- [{}] --",
-            self.get_id(),
-            self.get_potencia(),
-            estado
-        );
-
-        println!("{}", &texto);
+        format!("[{}] -- [{}] --", self.get_potencia(), estado)
+    }
 
-        texto
+    fn registro(&self) -> Registro {
+        Registro::new("Soplador")
+            .id(self.get_id())
+            .campo("estado", self.get_estado())
+            .campo("listo", self.is_listo())
+            .campo("potencia", self.get_potencia())
+            .campo("potencia_actual", self.get_potencia_actual())
+            .campo("alimento", self.get_alimento())
     }
 }
@@ -0,0 +1,208 @@
+use crate::errors::SopladorError;
+
+use types::rango::{Rango, RangoError};
+use types::unidades::Milisegundos;
+
+/// Duración mínima de una rampa: el cambio de potencia es inmediato.
+pub const RAMPA_MIN: Milisegundos = Milisegundos(0);
+
+/// Duración máxima de una rampa. Es lo que un `Programa` espera a que el soplador quede
+/// listo, así que una rampa más larga no terminaría nunca a tiempo.
+pub const RAMPA_MAX: Milisegundos = Milisegundos(60000);
+
+/// Forma de la curva con que cambia la potencia del soplador.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Perfil {
+    /// La potencia cambia a velocidad constante.
+    Lineal,
+    /// La potencia parte y termina suave (curva en S), evitando golpes de presión en la línea.
+    CurvaS,
+}
+
+/// Rampa de arranque y detención de un soplador.
+///
+/// `subida` es el tiempo de cualquier aumento de potencia y `bajada` el de cualquier
+/// disminución, incluido el apagado. Ambas van de `RAMPA_MIN` a `RAMPA_MAX`.
+///
+/// # Ejemplo:
+///! ```
+///! let mut rampa = Rampa::new();
+///! rampa.set_perfil(Perfil::CurvaS);
+///! rampa.set_subida(Milisegundos(8000))?;
+///! rampa.set_bajada(Milisegundos(4000))?;
+///! soplador.set_rampa(rampa);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Rampa {
+    perfil: Perfil,
+    subida: Rango<Milisegundos>,
+    bajada: Rango<Milisegundos>,
+}
+
+impl Rampa {
+    /// Crea una rampa lineal de `TIEMPO_DE_ENCENDIDO` en ambos sentidos.
+    pub fn new() -> Self {
+        Self {
+            perfil: Perfil::Lineal,
+            subida: Rango::new(RAMPA_MIN, RAMPA_MAX, crate::soplador::TIEMPO_DE_ENCENDIDO).unwrap(),
+            bajada: Rango::new(RAMPA_MIN, RAMPA_MAX, crate::soplador::TIEMPO_DE_ENCENDIDO).unwrap(),
+        }
+    }
+
+    pub fn set_perfil(&mut self, perfil: Perfil) -> &mut Rampa {
+        self.perfil = perfil;
+        self
+    }
+
+    /// Establece la duración de los aumentos de potencia.
+    ///
+    /// # Errores:
+    /// - `SopladorError::FueraDeRango`: `ms` es mayor que `RAMPA_MAX`.
+    pub fn set_subida(&mut self, ms: Milisegundos) -> Result<(), SopladorError> {
+        match self.subida.set(ms, "[Rampa]") {
+            Ok(()) => Ok(()),
+            Err(RangoError::FueraDeRango) => Err(SopladorError::FueraDeRango),
+        }
+    }
+
+    /// Establece la duración de las disminuciones de potencia, incluido el apagado.
+    ///
+    /// # Errores:
+    /// - `SopladorError::FueraDeRango`: `ms` es mayor que `RAMPA_MAX`.
+    pub fn set_bajada(&mut self, ms: Milisegundos) -> Result<(), SopladorError> {
+        match self.bajada.set(ms, "[Rampa]") {
+            Ok(()) => Ok(()),
+            Err(RangoError::FueraDeRango) => Err(SopladorError::FueraDeRango),
+        }
+    }
+
+    pub fn get_perfil(&self) -> Perfil {
+        self.perfil
+    }
+
+    pub fn get_subida(&self) -> Milisegundos {
+        self.subida.get()
+    }
+
+    pub fn get_bajada(&self) -> Milisegundos {
+        self.bajada.get()
+    }
+}
+
+impl Default for Rampa {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Una `Rampa` se serializa con sus duraciones, sin los límites, y al deserializarla se
+/// cargan con sus setters.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Rampa", deny_unknown_fields)]
+    struct Datos {
+        perfil: Perfil,
+        subida: Milisegundos,
+        bajada: Milisegundos,
+    }
+
+    impl Serialize for Rampa {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                perfil: self.perfil,
+                subida: self.get_subida(),
+                bajada: self.get_bajada(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Rampa {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let fuera_de_rango = |campo: &str, n: Milisegundos| {
+                D::Error::custom(format!(
+                    "{} {} fuera de rango ({}..={})",
+                    campo, n, RAMPA_MIN, RAMPA_MAX
+                ))
+            };
+
+            let mut rampa = Rampa::new();
+            rampa.set_perfil(datos.perfil);
+            rampa
+                .set_subida(datos.subida)
+                .map_err(|_| fuera_de_rango("subida", datos.subida))?;
+            rampa
+                .set_bajada(datos.bajada)
+                .map_err(|_| fuera_de_rango("bajada", datos.bajada))?;
+            Ok(rampa)
+        }
+    }
+}
+
+/// Cambio de potencia en curso: de `desde` a `hasta` entre `inicio` e `inicio + duracion`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) struct Transicion {
+    pub desde: u32,
+    pub hasta: u32,
+    pub inicio: u64,
+    pub duracion: u32,
+    pub perfil: Perfil,
+}
+
+impl Transicion {
+    /// Una transición ya terminada en `potencia`.
+    pub fn fija(potencia: u32) -> Self {
+        Self {
+            desde: potencia,
+            hasta: potencia,
+            inicio: 0,
+            duracion: 0,
+            perfil: Perfil::Lineal,
+        }
+    }
+
+    /// Crea la transición de `desde` a `hasta` en `duracion` milisegundos con el perfil de `rampa`.
+    pub fn new(desde: u32, hasta: u32, inicio: u64, duracion: u32, rampa: &Rampa) -> Self {
+        Self {
+            desde,
+            hasta,
+            inicio,
+            duracion,
+            perfil: rampa.get_perfil(),
+        }
+    }
+
+    pub fn is_terminada(&self, ahora: u64) -> bool {
+        ahora >= self.inicio + u64::from(self.duracion)
+    }
+
+    /// Milisegundos que faltan para terminar la transición.
+    pub fn get_restante(&self, ahora: u64) -> u32 {
+        (self.inicio + u64::from(self.duracion)).saturating_sub(ahora) as u32
+    }
+
+    /// Potencia en el instante `ahora`.
+    pub fn calcular(&self, ahora: u64) -> u32 {
+        if self.is_terminada(ahora) {
+            return self.hasta;
+        }
+        let t = ahora.saturating_sub(self.inicio) as f64 / f64::from(self.duracion);
+        let avance = match self.perfil {
+            Perfil::Lineal => t,
+            Perfil::CurvaS => t * t * (3.0 - 2.0 * t),
+        };
+        let desde = f64::from(self.desde);
+        let hasta = f64::from(self.hasta);
+        (desde + (hasta - desde) * avance).round() as u32
+    }
+}
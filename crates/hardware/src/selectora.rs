@@ -1,6 +1,14 @@
 use types::rango::{Rango, RangoData};
+use types::unidades::Milisegundos;
 use uuid::Uuid;
 
+/// Límites del tiempo que tarda una selectora en cambiar de salida.
+pub const ESPERA_MIN: Milisegundos = Milisegundos(0);
+pub const ESPERA_MAX: Milisegundos = Milisegundos(120000);
+
+/// Tiempo por defecto que tarda una selectora en cambiar de salida.
+pub const ESPERA_INICIAL: Milisegundos = Milisegundos(20000);
+
 pub enum TAGS {
     NAME,
 }
@@ -16,7 +24,7 @@ impl TAGS {
 pub struct Selectora {
     posicion: Rango,
     id: Uuid,
-    espera: u32,
+    espera: Rango<Milisegundos>,
 }
 
 impl Selectora {
@@ -25,16 +33,20 @@ impl Selectora {
     ///! ```rust
     ///! let selectora = Selectora::new(5);
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn new(posicion_maxima: u32) -> Self {
         Self {
             posicion: Rango::new(0, posicion_maxima, 0).unwrap(),
             id: Uuid::new_v4(),
-            espera: 20000,
+            espera: Rango::new(ESPERA_MIN, ESPERA_MAX, ESPERA_INICIAL).unwrap(),
         }
     }
 
     pub fn set_posicion(&mut self, n: u32) -> Result<(), crate::errors::SelectoraError> {
-        match self.posicion.set(n, &TAGS::NAME.as_str()) {
+        match self
+            .posicion
+            .set_campo(n, TAGS::NAME.as_str(), self.id, "posicion")
+        {
             Ok(()) => Ok(()),
             Err(types::rango::RangoError::FueraDeRango) => {
                 Err(crate::errors::SelectoraError::FueraDeRango)
@@ -42,14 +54,30 @@ impl Selectora {
         }
     }
 
-    pub fn set_espera(&mut self, n: u32) {
-        self.espera = n;
+    /// Establece el tiempo que tarda la selectora en cambiar de salida.
+    ///
+    /// # Errores:
+    /// - `SelectoraError::FueraDeRango`: `n` está fuera de `ESPERA_MIN..=ESPERA_MAX`.
+    pub fn set_espera(&mut self, n: Milisegundos) -> Result<(), crate::errors::SelectoraError> {
+        match self
+            .espera
+            .set_campo(n, TAGS::NAME.as_str(), self.id, "espera")
+        {
+            Ok(()) => Ok(()),
+            Err(types::rango::RangoError::FueraDeRango) => {
+                Err(crate::errors::SelectoraError::FueraDeRango)
+            }
+        }
     }
 
     pub fn get_posicion(&self) -> u32 {
         self.posicion.get()
     }
 
+    pub fn get_espera(&self) -> Milisegundos {
+        self.espera.get()
+    }
+
     pub fn get_posiciones(&self) -> RangoData {
         self.posicion.get_rango()
     }
@@ -1,31 +1,43 @@
-use types::rango::Rango;
+use types::eventos::{self, Evento, Severidad};
+use types::rango::{LimitesError, Rango};
+use types::unidades::{Gramos, Kilogramos};
 use uuid::Uuid;
 
+/// Capacidad máxima de un silo: la mayor que cabe en `Gramos`.
+pub const CAPACIDAD_MAX: Kilogramos = Kilogramos(u32::MAX / 1000);
+
 /// La estructura `Silo` representa un silo de almacenamiento de alimento.
 ///
 /// Un silo tiene tres propiedades clave:
 /// 1. **alimento**: La cantidad actual de alimento (en kilogramos) almacenada en el silo.
 /// 2. **historico**: El total acumulado de alimento que ha pasado por el silo en el pasado.
-/// 3. **capacidad**: La capacidad máxima del silo (en kilogramos).
+/// 3. **capacidad**: La capacidad máxima del silo (en kilogramos). Puede reducirse en
+///    tiempo de ejecución con `reducir_capacidad`, por ejemplo durante una mantención.
 ///
 /// La estructura incluye métodos para manejar y monitorear el llenado y la entrega de alimento.
 ///
+/// Internamente el alimento se lleva en gramos para no perder precisión en pulsos
+/// menores a un kilogramo; los métodos sin sufijo trabajan en kilogramos y los
+/// terminados en `_gramos` en gramos.
+///
 /// # Ejemplo:
 ///! ```
-///! let mut silo = Silo::new(24000);
-///! silo.set_alimento(1000); // Establece la cantidad de alimento a 1000 kg.
-///! println!("Alimento actual: {}", silo.get_alimento()); // Imprime 1000.
-///! silo.entregar_pulso(200); // Reduce el alimento por 200 kg.
-///! println!("Alimento restante: {}", silo.get_alimento()); // Imprime 800.
+///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+///! silo.set_alimento(Kilogramos(1000));
+///! println!("Alimento actual: {}", silo.get_alimento()); // Imprime 1000kg.
+///! silo.entregar_pulso(Kilogramos(200));
+///! println!("Alimento restante: {}", silo.get_alimento()); // Imprime 800kg.
 ///! ```
+#[allow(clippy::suspicious_doc_comments)]
 pub struct Silo {
-    /// La cantidad de alimento en kilogramos actualmente almacenado en el silo.
+    /// La cantidad de alimento en gramos actualmente almacenado en el silo.
     ///
-    alimento: Rango,
+    alimento: Rango<Gramos>,
 
-    /// El total de alimento en kilogramos que ha pasado por el silo históricamente.
+    /// El total de alimento en gramos que ha pasado por el silo históricamente.
+    /// Es un `u64` para que el contador no se agote en la vida útil del silo.
     ///
-    historico: Rango,
+    historico: Rango<u64>,
 
     /// El identificador único del silo.
     id: Uuid,
@@ -38,16 +50,23 @@ impl Silo {
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let silo = Silo::new(24000);
+    ///! let silo = Silo::new(24000).unwrap();
     ///! ```
     ///
     /// Esto creará un silo nuevo con atributos `alimento: 0`, `historico: 0`, `capacidad: 24000` y `id: Uuid::new_v4()`.
-    pub fn new(capacidad: u32) -> Self {
-        Self {
-            alimento: Rango::new(0, capacidad, 0).unwrap(),
-            historico: Rango::new(0, 4294967295, 0).unwrap(),
+    /// La capacidad se expresa en kilogramos.
+    ///
+    /// # Errores:
+    /// - `SiloError::FueraDeRango`: `capacidad` supera `CAPACIDAD_MAX`.
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn new(capacidad: Kilogramos) -> Result<Self, crate::errors::SiloError> {
+        let capacidad =
+            Gramos::try_from(capacidad).map_err(|_| crate::errors::SiloError::FueraDeRango)?;
+        Ok(Self {
+            alimento: Rango::new(Gramos(0), capacidad, Gramos(0)).unwrap(),
+            historico: Rango::new(0, u64::MAX, 0).unwrap(),
             id: Uuid::new_v4(),
-        }
+        })
     }
 
     /// Realiza una entrega de alimento desde el silo.
@@ -62,29 +81,56 @@ impl Silo {
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new(24000);
-    ///! silo.set_alimento(1000);
-    ///! silo.entregar_pulso(200);
-    ///! assert_eq!(silo.get_alimento(), 800); // La cantidad de alimento disminuye a 800 kg.
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_alimento(Kilogramos(1000));
+    ///! silo.entregar_pulso(Kilogramos(200));
+    ///! assert_eq!(silo.get_alimento(), Kilogramos(800));
     ///! ```
-    pub fn entregar_pulso(&mut self, pulso: u32) -> Result<(), crate::errors::SiloError> {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn entregar_pulso(&mut self, pulso: Kilogramos) -> Result<(), crate::errors::SiloError> {
+        let gramos = Gramos::try_from(pulso).map_err(|_| crate::errors::SiloError::FueraDeRango)?;
+        self.entregar_gramos(gramos)
+    }
+
+    /// Realiza una entrega de alimento desde el silo expresada en gramos.
+    ///
+    /// # Parámetros:
+    /// - `gramos`: La cantidad de alimento a entregar en gramos.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_alimento(Kilogramos(1));
+    ///! silo.entregar_gramos(Gramos(250));
+    ///! assert_eq!(silo.get_alimento_gramos(), Gramos(750));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn entregar_gramos(&mut self, gramos: Gramos) -> Result<(), crate::errors::SiloError> {
         // Usa el tipo correcto de error
-        let alimento_actual = self.get_alimento();
+        let alimento_actual = self.get_alimento_gramos();
 
-        if pulso > alimento_actual {
+        if gramos > alimento_actual {
             return Err(crate::errors::SiloError::SinAlimento);
         }
 
-        match self.set_alimento(alimento_actual - pulso) {
+        match self.set_alimento_gramos(alimento_actual - gramos) {
             Ok(()) => Ok(()),
             Err(crate::errors::SiloError::FueraDeRango) => {
-                // Asegúrate de que el error coincida
-                println!("Error: Intento de set fuera del rango.");
+                eventos::emitir(
+                    Evento::new(Severidad::Error, "Silo", "Intento de set fuera del rango")
+                        .id(self.id),
+                );
                 Err(crate::errors::SiloError::SinAlimento)
             }
             Err(_) => {
-                // Error inesperado
-                println!("Error inesperado al intentar actualizar el alimento.");
+                eventos::emitir(
+                    Evento::new(
+                        Severidad::Error,
+                        "Silo",
+                        "Error inesperado al intentar actualizar el alimento",
+                    )
+                    .id(self.id),
+                );
                 Err(crate::errors::SiloError::ErrorInesperado)
             }
         }
@@ -93,21 +139,21 @@ impl Silo {
 
 /// Implementación de getter y setter
 impl Silo {
-    /// Actualiza el valor histórico del silo sumando los kilogramos de alimento pasados.
+    /// Actualiza el valor histórico del silo sumando los gramos de alimento pasados.
     ///
     /// # Parámetros:
-    /// - `n`: Los kilogramos de alimento que se van a agregar al histórico.
+    /// - `n`: Los gramos de alimento que se van a agregar al histórico.
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new(24000);
-    ///! silo.set_historico(1000);
-    ///! println!("Historico: {}", silo.get_historico()); // Imprime 1000.
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_historico(Gramos(1000));
+    ///! println!("Historico: {}", silo.get_historico()); // Imprime 1kg.
     ///! ```
-    fn set_historico(&mut self, n: u32) -> Result<(), crate::errors::SiloError> {
-        let mut x = self.historico.get();
-        x += n;
-        match self.historico.set(x, "[Silo]") {
+    #[allow(clippy::suspicious_doc_comments)]
+    fn set_historico(&mut self, n: Gramos) -> Result<(), crate::errors::SiloError> {
+        let x = self.historico.get().saturating_add(u64::from(n.0));
+        match self.historico.set_campo(x, "Silo", self.id, "historico") {
             Ok(()) => Ok(()),
             Err(_) => Err(crate::errors::SiloError::FueraDeRango),
         }
@@ -125,53 +171,163 @@ impl Silo {
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new(24000);
-    ///! silo.set_alimento(1000); // Establece la cantidad de alimento a 1000 kg.
-    ///! println!("Alimento actual: {}", silo.get_alimento()); // Imprime 1000.
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_alimento(Kilogramos(1000));
+    ///! println!("Alimento actual: {}", silo.get_alimento()); // Imprime 1000kg.
     ///! ```
-    pub fn set_alimento(&mut self, n: u32) -> Result<(), crate::errors::SiloError> {
-        if self.alimento.get() < n {
-            if let Err(_) = self.set_historico(n - self.alimento.get()) {
-                return Err(crate::errors::SiloError::FueraDeRango);
-            }
-        }
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_alimento(&mut self, n: Kilogramos) -> Result<(), crate::errors::SiloError> {
+        let gramos = Gramos::try_from(n).map_err(|_| crate::errors::SiloError::FueraDeRango)?;
+        self.set_alimento_gramos(gramos)
+    }
 
-        match self.alimento.set(n, "[Silo]") {
-            Ok(()) => Ok(()),
-            Err(_) => Err(crate::errors::SiloError::FueraDeRango),
+    /// Establece la cantidad de alimento actual en el silo expresada en gramos.
+    ///
+    /// Si la nueva cantidad es mayor, se actualiza el histórico con la diferencia.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_alimento_gramos(Gramos(1500)); // Establece 1,5 kg.
+    ///! assert_eq!(silo.get_alimento(), Kilogramos(1));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_alimento_gramos(&mut self, n: Gramos) -> Result<(), crate::errors::SiloError> {
+        // El histórico se actualiza solo si la carga se aceptó
+        let anterior = self.alimento.get();
+        if self
+            .alimento
+            .set_campo(n, "Silo", self.id, "alimento")
+            .is_err()
+        {
+            return Err(crate::errors::SiloError::FueraDeRango);
         }
+        let nuevo = self.alimento.get();
+        if anterior < nuevo {
+            self.set_historico(nuevo - anterior)?;
+        }
+        Ok(())
     }
 
     /// Obtiene la cantidad actual de alimento almacenado en el silo.
     ///
     /// # Retorna:
-    /// La cantidad de alimento en kilogramos.
+    /// La cantidad de alimento en kilogramos, truncada al entero inferior.
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new(24000);
-    ///! silo.set_alimento(1000);
-    ///! assert_eq!(silo.get_alimento(), 1000);
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_alimento(Kilogramos(1000));
+    ///! assert_eq!(silo.get_alimento(), Kilogramos(1000));
     ///! ```
-    pub fn get_alimento(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_alimento(&self) -> Kilogramos {
+        self.alimento.get().a_kilogramos()
+    }
+
+    /// Obtiene la cantidad actual de alimento almacenado en el silo en gramos.
+    pub fn get_alimento_gramos(&self) -> Gramos {
         self.alimento.get()
     }
 
     /// Obtiene el total histórico de alimento que ha pasado por el silo.
     ///
     /// # Retorna:
-    /// El total histórico de alimento en kilogramos.
+    /// El total histórico de alimento en kilogramos, truncado al entero inferior y
+    /// limitado a `u32::MAX` kilogramos.
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new(24000);
-    ///! silo.set_historico(1000);
-    ///! assert_eq!(silo.get_historico(), 1000);
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.set_alimento(Kilogramos(1000));
+    ///! assert_eq!(silo.get_historico(), Kilogramos(1000));
     ///! ```
-    pub fn get_historico(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_historico(&self) -> Kilogramos {
+        Kilogramos(u32::try_from(self.historico.get() / 1000).unwrap_or(u32::MAX))
+    }
+
+    /// Obtiene el total histórico de alimento que ha pasado por el silo en gramos.
+    ///
+    /// Es un contador `u64` y no `Gramos`, que no alcanza para toda la vida útil del silo.
+    pub fn get_historico_gramos(&self) -> u64 {
         self.historico.get()
     }
 
+    /// Reduce la capacidad del silo, por ejemplo mientras está en mantención. Mientras
+    /// dure no se puede cargar más alimento que `capacidad`; se vuelve a la capacidad
+    /// nominal con `restablecer_capacidad`.
+    ///
+    /// # Retorna:
+    /// `SiloError::FueraDeRango` si `capacidad` supera la nominal o el silo ya tiene más
+    /// alimento que `capacidad`; en ese caso la capacidad no cambia.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+    ///! silo.reducir_capacidad(Kilogramos(12000)).unwrap();
+    ///! assert!(silo.set_alimento(Kilogramos(13000)).is_err());
+    ///! silo.restablecer_capacidad();
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn reducir_capacidad(
+        &mut self,
+        capacidad: Kilogramos,
+    ) -> Result<(), crate::errors::SiloError> {
+        let restringido = match Gramos::try_from(capacidad) {
+            Ok(max) => self.alimento.restringir(Gramos(0), max),
+            Err(_) => Err(LimitesError::LimitesInvalidos),
+        };
+        match restringido {
+            Ok(()) => {
+                eventos::emitir(
+                    Evento::new(
+                        Severidad::Info,
+                        "Silo",
+                        format!(
+                            "Capacidad reducida a {} de {}",
+                            capacidad,
+                            self.get_capacidad_nominal()
+                        ),
+                    )
+                    .id(self.id),
+                );
+                Ok(())
+            }
+            Err(_) => {
+                eventos::emitir(
+                    Evento::new(
+                        Severidad::Aviso,
+                        "Silo",
+                        format!(
+                            "No se puede reducir la capacidad a {} con {} de alimento y {} de capacidad nominal",
+                            capacidad,
+                            self.get_alimento(),
+                            self.get_capacidad_nominal()
+                        ),
+                    )
+                    .id(self.id),
+                );
+                Err(crate::errors::SiloError::FueraDeRango)
+            }
+        }
+    }
+
+    /// Vuelve a la capacidad nominal después de `reducir_capacidad`.
+    pub fn restablecer_capacidad(&mut self) {
+        self.alimento.restablecer();
+    }
+
+    /// Obtiene la capacidad vigente del silo, reducida o no.
+    pub fn get_capacidad(&self) -> Kilogramos {
+        self.alimento.get_rango().max.a_kilogramos()
+    }
+
+    /// Obtiene la capacidad con que se creó el silo, sin contar `reducir_capacidad`.
+    pub fn get_capacidad_nominal(&self) -> Kilogramos {
+        self.alimento.get_nominal().max.a_kilogramos()
+    }
+
     /// Obtiene la cantidad de alimento máximo que se puede agregar al silo en dicho momento, es decir devuelve el espacio restante.
     ///
     /// # Retorna:
@@ -180,12 +336,13 @@ impl Silo {
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let mut silo = Silo::new(24000);
+    ///! let mut silo = Silo::new(24000).unwrap();
     ///! println!("El espacio restante es: {} y la capacidad máxima es: {}", self.get_espacio_restante(), self.get_capacidad());
     ///! ```
-    fn _get_espacio_restante(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    fn _get_espacio_restante(&self) -> Kilogramos {
         // En este caso puedo asegurar que siempre habrá un key::max
-        self.alimento.get_rango().max - self.alimento.get()
+        (self.alimento.get_rango().max - self.alimento.get()).a_kilogramos()
     }
 
     /// Obtiene el identificador único de la ración.
@@ -195,11 +352,89 @@ impl Silo {
     ///
     /// # Ejemplo:
     ///! ```
-    ///! let silo = Silo::new(24000);
+    ///! let silo = Silo::new(24000).unwrap();
     ///! let id = silo.get_id();
     ///! println!("El ID del silo es: {}", id);
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_id(&self) -> Uuid {
         self.id
     }
 }
+
+/// Un `Silo` se serializa con su capacidad nominal en kilogramos, como en `Silo::new`,
+/// la capacidad reducida si existe, y el alimento y el histórico en gramos. Al
+/// deserializarlo el alimento se carga con `set_alimento_gramos`, por lo que no puede
+/// superar la capacidad vigente.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Silo", deny_unknown_fields)]
+    struct Datos {
+        capacidad: Kilogramos,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        capacidad_reducida: Option<Kilogramos>,
+        alimento_gramos: Gramos,
+        #[serde(default)]
+        historico_gramos: u64,
+        #[serde(default = "Uuid::new_v4")]
+        id: Uuid,
+    }
+
+    impl Serialize for Silo {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                capacidad: self.get_capacidad_nominal(),
+                capacidad_reducida: (self.get_capacidad() != self.get_capacidad_nominal())
+                    .then_some(self.get_capacidad()),
+                alimento_gramos: self.get_alimento_gramos(),
+                historico_gramos: self.get_historico_gramos(),
+                id: self.id,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Silo {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let mut silo = Silo::new(datos.capacidad).map_err(|_| {
+                D::Error::custom(format!(
+                    "capacidad {} fuera de rango (0kg..={})",
+                    datos.capacidad, CAPACIDAD_MAX
+                ))
+            })?;
+            if let Some(capacidad) = datos.capacidad_reducida {
+                silo.reducir_capacidad(capacidad).map_err(|_| {
+                    D::Error::custom(format!(
+                        "capacidad_reducida {} supera la capacidad de {}",
+                        capacidad, datos.capacidad
+                    ))
+                })?;
+            }
+            silo.set_alimento_gramos(datos.alimento_gramos)
+                .map_err(|_| {
+                    D::Error::custom(format!(
+                        "alimento_gramos {} supera la capacidad de {}",
+                        datos.alimento_gramos,
+                        silo.get_capacidad()
+                    ))
+                })?;
+            silo.historico = Rango::new(
+                0,
+                u64::MAX,
+                datos
+                    .historico_gramos
+                    .max(u64::from(datos.alimento_gramos.0)),
+            )
+            .unwrap();
+            silo.id = datos.id;
+            Ok(silo)
+        }
+    }
+}
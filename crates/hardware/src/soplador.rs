@@ -1,30 +1,59 @@
-use utils::utils::sleep;
+use std::collections::HashMap;
+
+use utils::reloj::{self, RelojRef};
 use uuid::Uuid;
 
+use crate::errors::SopladorError;
+use crate::rampa::{Rampa, Transicion};
+
+use types::eventos::{self, Evento, Severidad};
 use types::rango::{Rango, RangoError};
+use types::unidades::{Milisegundos, Porcentaje};
 
-pub const POTENCIA_MAX: u32 = 100;
-pub const POTENCIA_MIN: u32 = 0;
+pub const POTENCIA_MAX: Porcentaje = Porcentaje(100);
+pub const POTENCIA_MIN: Porcentaje = Porcentaje(0);
 
 pub const ESTADO_INICIAL: bool = false;
 
-pub const TIEMPO_DE_ENCENDIDO: u32 = 5000;
+/// Duración por defecto de las rampas de subida y bajada.
+pub const TIEMPO_DE_ENCENDIDO: Milisegundos = Milisegundos(5000);
 
 /// Representa un **Soplador**, que puede ser encendido o apagado y tener su potencia ajustada.
 ///
 /// La estructura `Soplador` tiene las siguientes propiedades:
 /// - **estado**: El estado actual del soplador. `true` si está encendido, `false` si está apagado.
-/// - **potencia**: La potencia del soplador, que puede ir de 0 a 100 (representado por un número de 8 bits sin signo).
+/// - **potencia**: La potencia del soplador en `Porcentaje`, de 0 a 100.
 /// - **id**: Un identificador único para cada instancia del soplador.
+/// - **reloj**: El reloj con el que se simula el tiempo de encendido.
+/// - **rampa**: La rampa con la que cambia la potencia al encender, apagar o ajustar.
+///
+/// `get_potencia` es la potencia configurada; la que entrega en cada instante mientras
+/// recorre una rampa se obtiene con `get_potencia_actual`, y `is_listo` indica que está
+/// encendido y que la rampa terminó.
 pub struct Soplador {
     /// El estado del soplador (encendido o apagado).
     estado: bool,
 
-    /// La potencia del soplador, con un rango de 0 a 100.
-    potencia: Rango,
+    /// La potencia configurada del soplador, con un rango de 0 a 100.
+    potencia: Rango<Porcentaje>,
+
+    /// Rampa con la que cambia la potencia.
+    rampa: Rampa,
+
+    /// Cambio de potencia en curso o el último realizado.
+    transicion: Transicion,
+
+    /// Potencia configurada para cada tipo de alimento.
+    potencias: HashMap<String, Porcentaje>,
+
+    /// Tipo de alimento seleccionado con `set_alimento`.
+    alimento: Option<String>,
 
     /// El identificador único del soplador.
     id: Uuid,
+
+    /// El reloj usado para esperar el encendido, por defecto el reloj del sistema.
+    reloj: RelojRef,
 }
 
 impl Soplador {
@@ -36,19 +65,34 @@ impl Soplador {
     ///! assert_eq!(soplador.get_estado(), false);  // El soplador debería estar apagado por defecto.
     ///! assert_eq!(soplador.get_potencia(), 0);   // La potencia debería ser 0 por defecto.
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn new() -> Self {
         Self {
             estado: ESTADO_INICIAL,
             potencia: Rango::new(POTENCIA_MIN, POTENCIA_MAX, POTENCIA_MIN).unwrap(),
+            rampa: Rampa::new(),
+            transicion: Transicion::fija(0),
+            potencias: HashMap::new(),
+            alimento: None,
             id: Uuid::new_v4(),
+            reloj: reloj::real(),
         }
     }
 }
 
+impl Default for Soplador {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Implementación de los métodos getter y setter para `Soplador`.
 impl Soplador {
     /// Establece el estado del soplador (encendido o apagado).
-    /// Si el estado es `true`, el soplador se enciende y se simula una duración de 5 segundos con `sleep`.
+    ///
+    /// Al encender sube a la potencia configurada con la rampa de subida y al apagar baja
+    /// a 0 con la de bajada. No bloquea: el soplador queda listo (`is_listo`) cuando la
+    /// rampa de subida termina según su reloj. Encender un soplador encendido no lo reinicia.
     ///
     /// # Parámetros:
     /// - `n`: Un valor booleano que indica el estado deseado. `true` para encender el soplador, `false` para apagarlo.
@@ -62,18 +106,144 @@ impl Soplador {
     ///! soplador.set_estado(true); // Enciende el soplador.
     ///! assert_eq!(soplador.get_estado(), true); // Verifica que el estado ahora es `true` (encendido).
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn set_estado(&mut self, n: bool) -> &mut Soplador {
-        if n {
-            println!("[Soplador][{}]: Encendiendo... Duración 5s", self.id);
-            sleep(TIEMPO_DE_ENCENDIDO);
+        if n && !self.estado {
+            eventos::emitir(
+                Evento::new(
+                    Severidad::Info,
+                    "Soplador",
+                    format!("Encendiendo... Duración {}", self.rampa.get_subida()),
+                )
+                .id(self.id)
+                .cambio("estado", 0, 1),
+            );
+            self.iniciar_rampa(self.get_potencia(), self.rampa.get_subida());
+        } else if !n && self.estado {
+            eventos::emitir(
+                Evento::new(
+                    Severidad::Info,
+                    "Soplador",
+                    format!("Apagando... Duración {}", self.rampa.get_bajada()),
+                )
+                .id(self.id)
+                .cambio("estado", 1, 0),
+            );
+            self.iniciar_rampa(POTENCIA_MIN, self.rampa.get_bajada());
         }
         self.estado = n;
         self
     }
 
+    /// Establece la rampa con la que cambia la potencia desde el próximo cambio.
+    pub fn set_rampa(&mut self, rampa: Rampa) -> &mut Soplador {
+        self.rampa = rampa;
+        self
+    }
+
+    pub fn get_rampa(&self) -> Rampa {
+        self.rampa
+    }
+
+    /// Indica si el soplador está encendido y terminó su rampa, es decir, si ya se puede
+    /// dosificar sin que el pellet se quiebre o tape la línea.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let reloj = Arc::new(RelojManual::new(0));
+    ///! soplador.set_reloj(reloj.clone()).set_estado(true);
+    ///! assert!(!soplador.is_listo());
+    ///! reloj.avanzar(5000);
+    ///! assert!(soplador.is_listo());
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn is_listo(&self) -> bool {
+        self.estado && self.transicion.is_terminada(self.reloj.ahora())
+    }
+
+    /// Tiempo que falta para que termine la rampa en curso.
+    pub fn get_restante(&self) -> Milisegundos {
+        Milisegundos(self.transicion.get_restante(self.reloj.ahora()))
+    }
+
+    /// Obtiene la potencia que entrega el soplador en este instante, siguiendo la rampa en curso.
+    pub fn get_potencia_actual(&self) -> Porcentaje {
+        Porcentaje(self.transicion.calcular(self.reloj.ahora()))
+    }
+
+    /// Configura la potencia con la que se sopla un tipo de alimento.
+    ///
+    /// # Errores:
+    /// - `SopladorError::FueraDeRango`: `n` está fuera de los límites de `set_potencia`.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! soplador.set_potencia_alimento("pellet-4mm", Porcentaje(60))?;
+    ///! soplador.set_potencia_alimento("pellet-9mm", Porcentaje(85))?;
+    ///! soplador.set_alimento("pellet-9mm")?; // La potencia pasa a 85
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_potencia_alimento(
+        &mut self,
+        alimento: &str,
+        n: Porcentaje,
+    ) -> Result<(), SopladorError> {
+        let (n, _) = self
+            .potencia
+            .ajustar(n)
+            .ok_or(SopladorError::FueraDeRango)?;
+        self.potencias.insert(alimento.to_string(), n);
+        Ok(())
+    }
+
+    /// Selecciona el tipo de alimento y pasa a su potencia con la rampa correspondiente.
+    ///
+    /// # Errores:
+    /// - `SopladorError::AlimentoDesconocido`: No hay potencia configurada para `alimento`.
+    pub fn set_alimento(&mut self, alimento: &str) -> Result<(), SopladorError> {
+        let potencia = *self
+            .potencias
+            .get(alimento)
+            .ok_or(SopladorError::AlimentoDesconocido)?;
+        self.set_potencia(potencia)?;
+        self.alimento = Some(alimento.to_string());
+        Ok(())
+    }
+
+    /// Obtiene el tipo de alimento seleccionado, si hay uno.
+    pub fn get_alimento(&self) -> Option<&str> {
+        self.alimento.as_deref()
+    }
+
+    /// Comienza una rampa desde la potencia actual hasta `hasta`.
+    fn iniciar_rampa(&mut self, hasta: Porcentaje, duracion: Milisegundos) {
+        let ahora = self.reloj.ahora();
+        let desde = self.transicion.calcular(ahora);
+        self.transicion = Transicion::new(desde, hasta.0, ahora, duracion.0, &self.rampa);
+    }
+
+    /// Establece el reloj con el que el soplador espera su encendido.
+    ///
+    /// # Parámetros:
+    /// - `reloj`: El reloj compartido, por ejemplo un `RelojManual` para pruebas.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let reloj: RelojRef = Arc::new(RelojManual::new(0));
+    ///! let mut soplador = Soplador::new();
+    ///! soplador.set_reloj(reloj.clone()).set_estado(true); // No bloquea el hilo.
+    ///! assert_eq!(reloj.ahora(), 5000);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_reloj(&mut self, reloj: RelojRef) -> &mut Soplador {
+        self.reloj = reloj;
+        self
+    }
+
     /// Establece la potencia del soplador.
     ///
-    /// La potencia es un valor entre 0 y 100.
+    /// La potencia es un valor entre 0 y 100. Si el soplador está encendido cambia con la
+    /// rampa de subida o de bajada según corresponda.
     ///
     /// # Parámetros:
     /// - `n`: Un valor de potencia que debe estar en el rango de 0 a 100.
@@ -84,13 +254,25 @@ impl Soplador {
     /// # Ejemplo:
     ///! ```
     ///! let mut soplador = Soplador::new();
-    ///! soplador.set_potencia(75); // Establece la potencia del soplador al 75%.
-    ///! assert_eq!(soplador.get_potencia(), 75).expect("Se intentó insertar una potencia superior al rango"); // Verifica que la potencia ahora es 75.
+    ///! soplador.set_potencia(Porcentaje(75)).expect("Se intentó insertar una potencia superior al rango");
+    ///! assert_eq!(soplador.get_potencia(), Porcentaje(75));
     ///! ```
-    pub fn set_potencia(&mut self, n: u32) -> Result<(), crate::errors::SopladorError> {
-        match self.potencia.set(n, "[Soplador]") {
-            Ok(()) => Ok(()),
-            Err(RangoError::FueraDeRango) => Err(crate::errors::SopladorError::FueraDeRango),
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_potencia(&mut self, n: Porcentaje) -> Result<(), SopladorError> {
+        let anterior = self.get_potencia();
+        match self.potencia.set_campo(n, "Soplador", self.id, "potencia") {
+            Ok(()) => {
+                if self.estado && n != anterior {
+                    let duracion = if n > anterior {
+                        self.rampa.get_subida()
+                    } else {
+                        self.rampa.get_bajada()
+                    };
+                    self.iniciar_rampa(n, duracion);
+                }
+                Ok(())
+            }
+            Err(RangoError::FueraDeRango) => Err(SopladorError::FueraDeRango),
         }
     }
 
@@ -105,21 +287,23 @@ impl Soplador {
     ///! let soplador = Soplador::new();
     ///! assert_eq!(soplador.get_estado(), false); // El soplador está apagado por defecto.
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_estado(&self) -> bool {
         self.estado
     }
 
-    /// Obtiene la potencia actual del soplador.
+    /// Obtiene la potencia configurada del soplador.
     ///
     /// # Retorna:
-    /// Un valor de tipo `u8` entre 0 y 100 que representa la potencia del soplador.
+    /// Un `Porcentaje` entre 0 y 100 que representa la potencia del soplador.
     ///
     /// # Ejemplo:
     ///! ```
     ///! let soplador = Soplador::new();
-    ///! assert_eq!(soplador.get_potencia(), 0); // La potencia es 0 por defecto.
+    ///! assert_eq!(soplador.get_potencia(), Porcentaje(0)); // La potencia es 0 por defecto.
     ///! ```
-    pub fn get_potencia(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_potencia(&self) -> Porcentaje {
         self.potencia.get()
     }
 
@@ -134,7 +318,87 @@ impl Soplador {
     ///! let id = soplador.get_id();
     ///! println!("El ID del soplador es: {}", id);
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_id(&self) -> Uuid {
         self.id
     }
 }
+
+/// Un `Soplador` se serializa con su configuración y su estado, sin la rampa en curso
+/// ni el reloj. Al deserializarlo las potencias se cargan con sus setters y, si estaba
+/// encendido, queda listo a su potencia con el reloj del sistema.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use std::collections::BTreeMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Soplador", deny_unknown_fields)]
+    struct Datos {
+        #[serde(default)]
+        estado: bool,
+        #[serde(default)]
+        potencia: Porcentaje,
+        #[serde(default)]
+        rampa: Rampa,
+        #[serde(default)]
+        potencias: BTreeMap<String, Porcentaje>,
+        #[serde(default)]
+        alimento: Option<String>,
+        #[serde(default = "Uuid::new_v4")]
+        id: Uuid,
+    }
+
+    impl Serialize for Soplador {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                estado: self.estado,
+                potencia: self.get_potencia(),
+                rampa: self.rampa,
+                potencias: self.potencias.clone().into_iter().collect(),
+                alimento: self.alimento.clone(),
+                id: self.id,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Soplador {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let fuera_de_rango = |campo: &str, n: Porcentaje| {
+                D::Error::custom(format!(
+                    "{} {} fuera de rango ({}..={})",
+                    campo, n, POTENCIA_MIN, POTENCIA_MAX
+                ))
+            };
+
+            let mut soplador = Soplador::new();
+            soplador.set_rampa(datos.rampa);
+            for (alimento, potencia) in &datos.potencias {
+                soplador
+                    .set_potencia_alimento(alimento, *potencia)
+                    .map_err(|_| fuera_de_rango(&format!("potencias.{}", alimento), *potencia))?;
+            }
+            if let Some(alimento) = &datos.alimento {
+                soplador.set_alimento(alimento).map_err(|_| {
+                    D::Error::custom(format!("no hay potencia para el alimento '{}'", alimento))
+                })?;
+            }
+            soplador
+                .set_potencia(datos.potencia)
+                .map_err(|_| fuera_de_rango("potencia", datos.potencia))?;
+
+            if datos.estado {
+                soplador.estado = true;
+                soplador.transicion = Transicion::fija(soplador.get_potencia().0);
+            }
+            soplador.id = datos.id;
+            Ok(soplador)
+        }
+    }
+}
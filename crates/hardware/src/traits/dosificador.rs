@@ -0,0 +1,30 @@
+use crate::dosificador::Dosificador;
+use crate::traits::Dosificar;
+use types::unidades::{Gramos, GramosPorSegundo, KgPorSegundo, Milisegundos};
+use uuid::Uuid;
+
+impl Dosificar for Dosificador {
+    fn set_estado(&mut self, n: bool) -> &mut Self {
+        Dosificador::set_estado(self, n)
+    }
+
+    fn get_entrega(&self) -> KgPorSegundo {
+        Dosificador::get_entrega(self)
+    }
+
+    fn get_entrega_gramos(&self) -> GramosPorSegundo {
+        Dosificador::get_entrega_gramos(self)
+    }
+
+    fn calcular_gramos(&self, ms: Milisegundos) -> Gramos {
+        Dosificador::calcular_gramos(self, ms)
+    }
+
+    fn get_estado(&self) -> bool {
+        Dosificador::get_estado(self)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Dosificador::get_id(self)
+    }
+}
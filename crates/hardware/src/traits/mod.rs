@@ -0,0 +1,102 @@
+pub mod dosificador;
+pub mod selectora;
+pub mod silo;
+pub mod soplador;
+
+use types::rango::RangoData;
+use types::unidades::{
+    Gramos, GramosPorSegundo, KgPorSegundo, Kilogramos, Milisegundos, Porcentaje,
+};
+use uuid::Uuid;
+
+use crate::errors::{SelectoraError, SiloError, SopladorError};
+use crate::logs::Print;
+
+/// El trait `Soplar` abstrae un soplador de la línea de alimentación.
+///
+/// Lo implementa `Soplador` (simulado), pero también puede implementarlo un
+/// soplador controlado por PLC o un doble de pruebas.
+///
+/// # Métodos:
+/// - `set_estado`: Enciende o apaga el soplador.
+/// - `set_potencia`: Establece la potencia en porcentaje (0 a 100).
+/// - `is_listo`: Indica si terminó de encender y alcanzó su potencia; por defecto basta con estar encendido.
+/// - `get_restante`: Tiempo estimado para quedar listo; por defecto 0 si no se conoce.
+/// - `get_estado`, `get_potencia`, `get_id`: Consultan el estado actual.
+pub trait Soplar: Print {
+    fn set_estado(&mut self, n: bool) -> &mut Self;
+    fn set_potencia(&mut self, n: Porcentaje) -> Result<(), SopladorError>;
+    fn get_estado(&self) -> bool;
+    fn get_potencia(&self) -> Porcentaje;
+    fn get_id(&self) -> Uuid;
+
+    fn is_listo(&self) -> bool {
+        self.get_estado()
+    }
+
+    fn get_restante(&self) -> Milisegundos {
+        Milisegundos(0)
+    }
+}
+
+/// El trait `Dosificar` abstrae un dosificador (tornillo, válvula rotativa, etc.).
+///
+/// # Métodos:
+/// - `set_estado`: Abre (`true`) o cierra (`false`) el dosificador.
+/// - `get_entrega`: Capacidad de entrega en kg/s.
+/// - `get_entrega_gramos`: Capacidad de entrega en g/s, limitada a `u32::MAX`.
+/// - `calcular_gramos`: Gramos entregados con el dosificador abierto durante `ms`.
+/// - `get_estado`, `get_id`: Consultan el estado actual.
+pub trait Dosificar: Print {
+    fn set_estado(&mut self, n: bool) -> &mut Self;
+    fn get_entrega(&self) -> KgPorSegundo;
+    fn get_estado(&self) -> bool;
+    fn get_id(&self) -> Uuid;
+
+    fn get_entrega_gramos(&self) -> GramosPorSegundo {
+        GramosPorSegundo::try_from(self.get_entrega()).unwrap_or(GramosPorSegundo(u32::MAX))
+    }
+
+    fn calcular_gramos(&self, ms: Milisegundos) -> Gramos {
+        self.get_entrega_gramos() * ms
+    }
+}
+
+/// El trait `Almacenar` abstrae un almacenamiento de alimento, como un silo.
+///
+/// Las cantidades sin sufijo se expresan en kilogramos y las terminadas en `_gramos` en gramos.
+///
+/// # Métodos:
+/// - `entregar_pulso`, `entregar_gramos`: Descuentan del almacenamiento la cantidad entregada.
+/// - `set_alimento`: Establece la cantidad de alimento almacenado.
+/// - `get_alimento`, `get_alimento_gramos`, `get_historico`, `get_id`: Consultan el estado actual.
+pub trait Almacenar: Print {
+    fn entregar_gramos(&mut self, gramos: Gramos) -> Result<(), SiloError>;
+    fn set_alimento(&mut self, n: Kilogramos) -> Result<(), SiloError>;
+    fn get_alimento_gramos(&self) -> Gramos;
+    fn get_historico(&self) -> Kilogramos;
+    fn get_id(&self) -> Uuid;
+
+    fn entregar_pulso(&mut self, pulso: Kilogramos) -> Result<(), SiloError> {
+        let gramos = Gramos::try_from(pulso).map_err(|_| SiloError::FueraDeRango)?;
+        self.entregar_gramos(gramos)
+    }
+
+    fn get_alimento(&self) -> Kilogramos {
+        self.get_alimento_gramos().a_kilogramos()
+    }
+}
+
+/// El trait `Seleccionar` abstrae una selectora que dirige el alimento a una salida.
+///
+/// # Métodos:
+/// - `set_posicion`: Mueve la selectora a la salida indicada.
+/// - `get_espera`: Tiempo que tarda la selectora en cambiar de salida.
+/// - `get_posicion`, `get_posiciones`, `get_id`: Consultan el estado actual.
+pub trait Seleccionar: Print {
+    fn set_posicion(&mut self, n: u32) -> Result<(), SelectoraError>;
+    fn get_posicion(&self) -> u32;
+    fn get_posiciones(&self) -> RangoData;
+    fn get_espera(&self) -> Milisegundos;
+    fn get_id(&self) -> Uuid;
+}
@@ -0,0 +1,28 @@
+use crate::errors::SelectoraError;
+use crate::selectora::Selectora;
+use crate::traits::Seleccionar;
+use types::rango::RangoData;
+use types::unidades::Milisegundos;
+use uuid::Uuid;
+
+impl Seleccionar for Selectora {
+    fn set_posicion(&mut self, n: u32) -> Result<(), SelectoraError> {
+        Selectora::set_posicion(self, n)
+    }
+
+    fn get_posicion(&self) -> u32 {
+        Selectora::get_posicion(self)
+    }
+
+    fn get_posiciones(&self) -> RangoData {
+        Selectora::get_posiciones(self)
+    }
+
+    fn get_espera(&self) -> Milisegundos {
+        Selectora::get_espera(self)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Selectora::get_id(self)
+    }
+}
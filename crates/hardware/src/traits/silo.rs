@@ -0,0 +1,35 @@
+use crate::errors::SiloError;
+use crate::silo::Silo;
+use crate::traits::Almacenar;
+use types::unidades::{Gramos, Kilogramos};
+use uuid::Uuid;
+
+impl Almacenar for Silo {
+    fn entregar_gramos(&mut self, gramos: Gramos) -> Result<(), SiloError> {
+        Silo::entregar_gramos(self, gramos)
+    }
+
+    fn entregar_pulso(&mut self, pulso: Kilogramos) -> Result<(), SiloError> {
+        Silo::entregar_pulso(self, pulso)
+    }
+
+    fn set_alimento(&mut self, n: Kilogramos) -> Result<(), SiloError> {
+        Silo::set_alimento(self, n)
+    }
+
+    fn get_alimento(&self) -> Kilogramos {
+        Silo::get_alimento(self)
+    }
+
+    fn get_alimento_gramos(&self) -> Gramos {
+        Silo::get_alimento_gramos(self)
+    }
+
+    fn get_historico(&self) -> Kilogramos {
+        Silo::get_historico(self)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Silo::get_id(self)
+    }
+}
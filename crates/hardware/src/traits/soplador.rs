@@ -0,0 +1,35 @@
+use crate::errors::SopladorError;
+use crate::soplador::Soplador;
+use crate::traits::Soplar;
+use types::unidades::{Milisegundos, Porcentaje};
+use uuid::Uuid;
+
+impl Soplar for Soplador {
+    fn set_estado(&mut self, n: bool) -> &mut Self {
+        Soplador::set_estado(self, n)
+    }
+
+    fn set_potencia(&mut self, n: Porcentaje) -> Result<(), SopladorError> {
+        Soplador::set_potencia(self, n)
+    }
+
+    fn get_estado(&self) -> bool {
+        Soplador::get_estado(self)
+    }
+
+    fn get_potencia(&self) -> Porcentaje {
+        Soplador::get_potencia(self)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Soplador::get_id(self)
+    }
+
+    fn is_listo(&self) -> bool {
+        Soplador::is_listo(self)
+    }
+
+    fn get_restante(&self) -> Milisegundos {
+        Soplador::get_restante(self)
+    }
+}
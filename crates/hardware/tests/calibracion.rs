@@ -0,0 +1,226 @@
+use hardware::calibracion::{Calibracion, Calibraciones, Curva};
+use hardware::dosificador::Dosificador;
+use hardware::errors::CalibracionError;
+
+#[cfg(test)]
+mod calibracion {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use uuid::Uuid;
+
+    fn ruta_temporal() -> PathBuf {
+        env::temp_dir().join(format!("calibraciones-{}.txt", Uuid::new_v4()))
+    }
+
+    mod curva {
+        use super::*;
+        use types::unidades::{Gramos, GramosPorSegundo, Milisegundos};
+
+        #[test]
+        fn test_calcular_gramos() {
+            let curva = Curva::new(GramosPorSegundo(1800), -150);
+            assert_eq!(curva.calcular_gramos(Milisegundos(5000)), Gramos(8850));
+            assert_eq!(curva.calcular_gramos(Milisegundos(1500)), Gramos(2550));
+        }
+
+        #[test]
+        fn test_nunca_negativa() {
+            let curva = Curva::new(GramosPorSegundo(1000), -500);
+            assert_eq!(curva.calcular_gramos(Milisegundos(100)), Gramos(0));
+        }
+    }
+
+    mod ajustar {
+        use super::*;
+        use types::unidades::GramosPorSegundo;
+        use types::unidades::{Gramos, Milisegundos};
+
+        #[test]
+        fn test_recta_exacta() {
+            let mut calibracion = Calibracion::new();
+            calibracion
+                .registrar(Milisegundos(2000), Gramos(3450))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(5000), Gramos(8850))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(8000), Gramos(14250))
+                .unwrap();
+
+            assert_eq!(
+                calibracion.ajustar(),
+                Ok(Curva::new(GramosPorSegundo(1800), -150))
+            );
+        }
+
+        #[test]
+        fn test_minimos_cuadrados() {
+            // Pesajes con ruido alrededor de 2000 g/s sin desfase
+            let mut calibracion = Calibracion::new();
+            calibracion
+                .registrar(Milisegundos(1000), Gramos(2010))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(1000), Gramos(1990))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(3000), Gramos(5990))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(3000), Gramos(6010))
+                .unwrap();
+
+            assert_eq!(
+                calibracion.ajustar(),
+                Ok(Curva::new(GramosPorSegundo(2000), 0))
+            );
+            assert_eq!(calibracion.get_muestras().len(), 4);
+        }
+
+        #[test]
+        fn test_muestras_insuficientes() {
+            let mut calibracion = Calibracion::new();
+            assert_eq!(
+                calibracion.ajustar(),
+                Err(CalibracionError::MuestrasInsuficientes)
+            );
+
+            calibracion
+                .registrar(Milisegundos(2000), Gramos(4000))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(2000), Gramos(4100))
+                .unwrap();
+            assert_eq!(
+                calibracion.ajustar(),
+                Err(CalibracionError::MuestrasInsuficientes),
+                "Se ajustó una curva con una sola duración"
+            );
+        }
+
+        #[test]
+        fn test_curva_invalida() {
+            let mut calibracion = Calibracion::new();
+            calibracion
+                .registrar(Milisegundos(1000), Gramos(5000))
+                .unwrap();
+            calibracion
+                .registrar(Milisegundos(3000), Gramos(1000))
+                .unwrap();
+            assert_eq!(calibracion.ajustar(), Err(CalibracionError::CurvaInvalida));
+        }
+
+        #[test]
+        fn test_muestra_invalida() {
+            let mut calibracion = Calibracion::new();
+            assert_eq!(
+                calibracion.registrar(Milisegundos(0), Gramos(100)),
+                Err(CalibracionError::MuestraInvalida)
+            );
+        }
+    }
+
+    mod persistencia {
+        use super::*;
+        use types::unidades::GramosPorSegundo;
+
+        #[test]
+        fn test_guardar_y_cargar() {
+            let ruta = ruta_temporal();
+            let mut calibraciones = Calibraciones::new();
+            calibraciones
+                .set_curva(
+                    "linea-1",
+                    "pellet-4mm",
+                    Curva::new(GramosPorSegundo(1800), -150),
+                )
+                .unwrap();
+            calibraciones
+                .set_curva(
+                    "linea-1",
+                    "pellet-9mm",
+                    Curva::new(GramosPorSegundo(2300), 40),
+                )
+                .unwrap();
+            calibraciones.guardar(&ruta).unwrap();
+
+            let cargadas = Calibraciones::cargar(&ruta).unwrap();
+            fs::remove_file(&ruta).ok();
+
+            assert_eq!(cargadas.len(), 2);
+            assert_eq!(
+                cargadas.get_curva("linea-1", "pellet-4mm"),
+                Some(Curva::new(GramosPorSegundo(1800), -150))
+            );
+            assert_eq!(
+                cargadas.get_curva("linea-1", "pellet-9mm"),
+                Some(Curva::new(GramosPorSegundo(2300), 40))
+            );
+            assert_eq!(cargadas.get_curva("linea-2", "pellet-4mm"), None);
+        }
+
+        #[test]
+        fn test_archivo_inexistente() {
+            let cargadas = Calibraciones::cargar(ruta_temporal()).unwrap();
+            assert!(cargadas.is_empty());
+        }
+
+        #[test]
+        fn test_formato_invalido() {
+            let ruta = ruta_temporal();
+            fs::write(
+                &ruta,
+                "# curvas\nlinea-1;pellet;1800;-150\nlinea-1;pellet;rapido;0\n",
+            )
+            .unwrap();
+
+            let resultado = Calibraciones::cargar(&ruta);
+            fs::remove_file(&ruta).ok();
+
+            assert_eq!(resultado.unwrap_err(), CalibracionError::Formato(3));
+        }
+
+        #[test]
+        fn test_nombre_invalido() {
+            let mut calibraciones = Calibraciones::new();
+            assert_eq!(
+                calibraciones.set_curva("linea;1", "pellet", Curva::new(GramosPorSegundo(1800), 0)),
+                Err(CalibracionError::NombreInvalido)
+            );
+        }
+    }
+
+    mod dosificador {
+        use super::*;
+        use types::unidades::{Gramos, GramosPorSegundo, KgPorSegundo, Milisegundos};
+
+        #[test]
+        fn test_usa_la_curva() {
+            let mut doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            assert_eq!(doser.calcular_gramos(Milisegundos(5000)), Gramos(10000));
+
+            let mut calibraciones = Calibraciones::new();
+            calibraciones
+                .set_curva(
+                    "linea-1",
+                    "pellet-4mm",
+                    Curva::new(GramosPorSegundo(1800), -150),
+                )
+                .unwrap();
+
+            assert!(calibraciones.aplicar("linea-1", "pellet-4mm", &mut doser));
+            assert_eq!(doser.get_entrega_gramos(), GramosPorSegundo(1800));
+            assert_eq!(doser.get_entrega(), KgPorSegundo(1));
+            assert_eq!(doser.calcular_gramos(Milisegundos(5000)), Gramos(8850));
+
+            // Sin curva para el alimento vuelve a la entrega nominal
+            assert!(!calibraciones.aplicar("linea-1", "pellet-9mm", &mut doser));
+            assert_eq!(doser.calcular_gramos(Milisegundos(5000)), Gramos(10000));
+        }
+    }
+}
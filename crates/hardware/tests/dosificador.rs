@@ -1,6 +1,7 @@
 use hardware::dosificador::Dosificador;
+use types::unidades::KgPorSegundo;
 
-const ENTREGA: u32 = 10;
+const ENTREGA: KgPorSegundo = KgPorSegundo(10);
 
 // #[cfg(test)]
 // mod avisos {
@@ -21,13 +22,15 @@ const ENTREGA: u32 = 10;
 #[cfg(test)]
 mod dosificador {
     use super::*;
+    use types::unidades::{Gramos, GramosPorSegundo, KgPorSegundo, Milisegundos};
 
     use uuid::Uuid;
 
     /// Este test comprueba que el objeto inicial tiene los atributos básicos en sus valores
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_creacion_dosificador() {
-        let dosificador = Dosificador::new(ENTREGA);
+        let dosificador = Dosificador::new(ENTREGA).unwrap();
 
         // Verificar valores iniciales
         assert_eq!(dosificador.get_entrega(), ENTREGA);
@@ -35,21 +38,72 @@ mod dosificador {
 
         // Verificar unicidad del UUID (que es única la weá)
         let id1 = dosificador.get_id();
-        let id2 = Dosificador::new(ENTREGA).get_id();
+        let id2 = Dosificador::new(ENTREGA).unwrap().get_id();
         assert_ne!(id1, id2, "Las ID no son únicas");
     }
 
+    /// Prueba que una entrega que no cabe en g/s se rechaza en vez de acotarse
+    #[test]
+    fn test_entrega_fuera_de_rango() {
+        use hardware::errors::DosificadorError;
+
+        assert_eq!(
+            Dosificador::new(KgPorSegundo(u32::MAX)).err(),
+            Some(DosificadorError::FueraDeRango)
+        );
+    }
+
+    /// Prueba que un dosificador sin entrega se rechaza
+    #[test]
+    fn test_entrega_cero() {
+        use hardware::errors::DosificadorError;
+
+        assert_eq!(
+            Dosificador::new(KgPorSegundo(0)).err(),
+            Some(DosificadorError::FueraDeRango)
+        );
+        assert_eq!(
+            Dosificador::new_gramos(GramosPorSegundo(0)).err(),
+            Some(DosificadorError::FueraDeRango)
+        );
+        assert!(Dosificador::new_gramos(GramosPorSegundo(1)).is_ok());
+    }
+
     /// Prueba que el estado cambia mediante el método set_estado
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_set_estado() {
-        let mut dosificador = Dosificador::new(ENTREGA);
+        let mut dosificador = Dosificador::new(ENTREGA).unwrap();
         dosificador.set_estado(true);
         assert_eq!(dosificador.get_estado(), true);
     }
 
+    /// Prueba que la entrega se calcula en gramos con duraciones que no son segundos enteros
+    #[test]
+    fn test_calcular_gramos() {
+        let dosificador = Dosificador::new(ENTREGA).unwrap();
+        assert_eq!(dosificador.get_entrega_gramos(), GramosPorSegundo(10000));
+        assert_eq!(
+            dosificador.calcular_gramos(Milisegundos(1500)),
+            Gramos(15000)
+        );
+        assert_ne!(
+            dosificador.calcular_gramos(Milisegundos(1500)),
+            dosificador.calcular_gramos(Milisegundos(1000)),
+            "Los milisegundos se truncaron a segundos"
+        );
+
+        let dosificador = Dosificador::new_gramos(GramosPorSegundo(1500)).unwrap();
+        assert_eq!(dosificador.get_entrega(), KgPorSegundo(1));
+        assert_eq!(
+            dosificador.calcular_gramos(Milisegundos(1500)),
+            Gramos(2250)
+        );
+    }
+
     #[test]
     fn test_get_id() {
-        let dosificador = Dosificador::new(ENTREGA);
+        let dosificador = Dosificador::new(ENTREGA).unwrap();
         let id = dosificador.get_id();
 
         // Verificar que el ID sea un UUID v4 válido
@@ -0,0 +1,90 @@
+use std::sync::{Arc, OnceLock};
+
+use hardware::silo::Silo;
+use hardware::soplador::Soplador;
+use types::eventos::{self, Memoria, Severidad};
+use types::unidades::Kilogramos;
+
+const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
+
+/// El sumidero es global: todas las pruebas comparten la misma `Memoria` y filtran por `id`.
+fn memoria() -> Arc<Memoria> {
+    static MEMORIA: OnceLock<Arc<Memoria>> = OnceLock::new();
+    MEMORIA
+        .get_or_init(|| {
+            let memoria = Arc::new(Memoria::new());
+            eventos::set_sumidero(memoria.clone());
+            memoria
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod eventos_test {
+    use super::*;
+
+    mod silo {
+        use super::*;
+        use types::unidades::Kilogramos;
+
+        #[test]
+        fn test_cambio_de_alimento() {
+            let memoria = memoria();
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(Kilogramos(1000)).unwrap();
+
+            let cambios: Vec<_> = memoria
+                .get_eventos_de(silo.get_id())
+                .into_iter()
+                .filter(|evento| evento.campo.as_deref() == Some("alimento"))
+                .collect();
+            assert_eq!(cambios.len(), 1);
+            assert_eq!(cambios[0].origen, "Silo");
+            assert_eq!(cambios[0].anterior, Some(0.0));
+            assert_eq!(
+                cambios[0].nuevo,
+                Some(f64::from(silo.get_alimento_gramos().0))
+            );
+        }
+
+        #[test]
+        fn test_fuera_de_rango() {
+            let memoria = memoria();
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            assert!(silo.set_alimento(CAPACIDAD_SILOS + Kilogramos(1)).is_err());
+
+            assert!(memoria
+                .get_eventos_de(silo.get_id())
+                .iter()
+                .any(|evento| evento.severidad == Severidad::Aviso));
+        }
+    }
+
+    mod soplador {
+        use super::*;
+
+        #[test]
+        fn test_encendido_y_apagado() {
+            let memoria = memoria();
+            let mut soplador = Soplador::new();
+            soplador.set_estado(true);
+            soplador.set_estado(false);
+
+            let estados: Vec<_> = memoria
+                .get_eventos_de(soplador.get_id())
+                .into_iter()
+                .filter(|evento| evento.campo.as_deref() == Some("estado"))
+                .collect();
+            assert_eq!(estados.len(), 2);
+            assert_eq!(estados[0].severidad, Severidad::Info);
+            assert_eq!(
+                (estados[0].anterior, estados[0].nuevo),
+                (Some(0.0), Some(1.0))
+            );
+            assert_eq!(
+                (estados[1].anterior, estados[1].nuevo),
+                (Some(1.0), Some(0.0))
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use hardware::logs::Print;
+use hardware::logs::{Print, Valor};
 
 // #[cfg(test)]
 // mod avisos {
@@ -22,6 +22,7 @@ mod logs {
 
     mod soplador {
         use hardware::soplador::Soplador;
+        use types::unidades::Porcentaje;
 
         use super::*;
 
@@ -32,7 +33,7 @@ mod logs {
             let log = soplador.print();
             assert!(log.contains("[Soplador]"));
             assert!(log.contains(&soplador.get_id().to_string()));
-            assert!(log.contains(&format!("{}%", &soplador.get_potencia().to_string())));
+            assert!(log.contains(&format!("[{}]", soplador.get_potencia())));
             assert!(log.contains("Apagado")); // Estado base
         }
 
@@ -60,7 +61,7 @@ mod logs {
             assert!(log.contains("0%"), "No contiene la potencia en porcentaje");
 
             soplador
-                .set_potencia(100)
+                .set_potencia(Porcentaje(100))
                 .expect("Ha puesto una potencia sobre el rango permitido");
 
             let log = soplador.print().to_lowercase();
@@ -71,14 +72,16 @@ mod logs {
 
     mod dosificador {
         use hardware::dosificador::Dosificador;
+        use types::unidades::KgPorSegundo;
 
-        const ENTREGA: u32 = 10;
+        const ENTREGA: KgPorSegundo = KgPorSegundo(10);
 
         use super::*;
 
         #[test]
+        #[allow(clippy::useless_format)]
         fn test_estructura() {
-            let dosificador = Dosificador::new(ENTREGA);
+            let dosificador = Dosificador::new(ENTREGA).unwrap();
 
             let log = dosificador.print();
 
@@ -90,7 +93,7 @@ mod logs {
 
         #[test]
         fn test_cambio_de_estado() {
-            let mut dosificador = Dosificador::new(ENTREGA);
+            let mut dosificador = Dosificador::new(ENTREGA).unwrap();
 
             let log = dosificador.print().to_lowercase();
 
@@ -107,15 +110,16 @@ mod logs {
     mod silo {
         use super::*;
         use hardware::silo::Silo;
+        use types::unidades::Kilogramos;
 
-        const CAPACIDAD_SILOS: u32 = 24000;
+        const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
 
         mod estructura {
             use super::*;
 
             #[test]
             fn test_contiene_silo() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
                 let log = silo.print();
 
@@ -124,7 +128,7 @@ mod logs {
 
             #[test]
             fn test_contiene_id() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
                 let log = silo.print();
 
@@ -135,28 +139,27 @@ mod logs {
             }
 
             #[test]
+            #[allow(clippy::to_string_in_format_args)]
             fn test_contiene_actual() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
                 let log = silo.print();
 
                 assert!(
-                    log.contains(&format!("Actual: {}kg", silo.get_alimento().to_string())),
+                    log.contains(&format!("Actual: {}", silo.get_alimento().to_string())),
                     "No contiene el alimento del silo"
                 );
             }
 
             #[test]
+            #[allow(clippy::to_string_in_format_args)]
             fn test_contiene_historico() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
                 let log = silo.print();
 
                 assert!(
-                    log.contains(&format!(
-                        "Historico: {}kg",
-                        silo.get_historico().to_string()
-                    )),
+                    log.contains(&format!("Historico: {}", silo.get_historico().to_string())),
                     "No contien el alimento historico"
                 )
             }
@@ -165,33 +168,131 @@ mod logs {
         mod actualiza_con_setter {
             use super::*;
 
-            fn assert_actualizable(silo: &mut Silo, tag: &str, set: u32, assert: u32) {
+            fn assert_actualizable(
+                silo: &mut Silo,
+                tag: &str,
+                set: Kilogramos,
+                assert: Kilogramos,
+            ) {
                 silo.set_alimento(set)
                     .expect("Ha intentado ingresar una cantidad superior a la capacidad del silo");
-                assert!(silo.print().contains(&format!("{}: {}kg", tag, assert)));
+                assert!(silo.print().contains(&format!("{}: {}", tag, assert)));
             }
 
             #[test]
+            #[allow(clippy::needless_borrow)]
             fn test_alimento() {
                 const TAG: &str = "Actual";
 
-                let mut silo = Silo::new(CAPACIDAD_SILOS);
+                let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
                 assert!(silo.print().contains(&format!("{}: {}kg", TAG, 0)));
 
                 assert_actualizable(&mut silo, &TAG, CAPACIDAD_SILOS, CAPACIDAD_SILOS);
             }
 
             #[test]
+            #[allow(clippy::needless_borrow)]
             fn test_historico() {
                 const TAG: &str = "Historico";
 
-                let mut silo = Silo::new(CAPACIDAD_SILOS);
+                let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
                 assert!(silo.print().contains(&format!("{}: 0kg", &TAG)));
 
                 assert_actualizable(&mut silo, &TAG, CAPACIDAD_SILOS, CAPACIDAD_SILOS);
-                assert_actualizable(&mut silo, &TAG, 0, CAPACIDAD_SILOS);
-                assert_actualizable(&mut silo, &TAG, CAPACIDAD_SILOS, 2 * CAPACIDAD_SILOS);
+                assert_actualizable(&mut silo, &TAG, Kilogramos(0), CAPACIDAD_SILOS);
+                assert_actualizable(
+                    &mut silo,
+                    TAG,
+                    CAPACIDAD_SILOS,
+                    CAPACIDAD_SILOS + CAPACIDAD_SILOS,
+                );
             }
         }
     }
+
+    mod selectora {
+        use super::*;
+        use hardware::selectora::Selectora;
+
+        #[test]
+        fn test_estructura() {
+            let mut selectora = Selectora::new(5);
+            selectora.set_posicion(3).unwrap();
+
+            let log = selectora.print();
+
+            assert!(
+                log.contains("[Selectora]"),
+                "No contiene la palabra selectora"
+            );
+            assert!(
+                log.contains(&selectora.get_id().to_string()),
+                "No contiene la id"
+            );
+            assert!(log.contains("Salida: 3"), "No contiene la posición");
+        }
+
+        #[test]
+        fn test_registro() {
+            let selectora = Selectora::new(5);
+
+            let registro = selectora.registro();
+
+            assert_eq!(registro.origen, "Selectora");
+            assert_eq!(registro.id, Some(selectora.get_id()));
+            assert_eq!(registro.get("posicion"), Some(&Valor::Entero(0)));
+            assert_eq!(registro.get("posicion_maxima"), Some(&Valor::Entero(5)));
+        }
+    }
+
+    mod registro {
+        use super::*;
+        use hardware::dosificador::Dosificador;
+        use hardware::silo::Silo;
+        use hardware::soplador::Soplador;
+        use types::unidades::{KgPorSegundo, Kilogramos};
+
+        #[test]
+        fn test_silo() {
+            let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+            silo.set_alimento(Kilogramos(2)).unwrap();
+
+            let registro = silo.registro();
+
+            assert_eq!(registro.id, Some(silo.get_id()));
+            assert_eq!(registro.get("alimento_gramos"), Some(&Valor::Entero(2000)));
+            assert_eq!(registro.get("historico_gramos"), Some(&Valor::Entero(2000)));
+        }
+
+        #[test]
+        fn test_dosificador() {
+            let mut dosificador = Dosificador::new(KgPorSegundo(2)).unwrap();
+            dosificador.set_estado(true);
+
+            let registro = dosificador.registro();
+
+            assert_eq!(registro.get("estado"), Some(&Valor::Booleano(true)));
+            assert_eq!(registro.get("calibrado"), Some(&Valor::Booleano(false)));
+        }
+
+        #[test]
+        fn test_soplador_sin_alimento() {
+            let soplador = Soplador::new();
+
+            let registro = soplador.registro();
+
+            assert_eq!(registro.get("estado"), Some(&Valor::Booleano(false)));
+            assert_eq!(registro.get("alimento"), Some(&Valor::Nulo));
+        }
+
+        #[test]
+        fn test_print_usa_el_registro() {
+            let soplador = Soplador::new();
+
+            assert_eq!(
+                soplador.print(),
+                format!("[Soplador][{}]: {}", soplador.get_id(), soplador.texto())
+            );
+        }
+    }
 }
@@ -100,4 +100,31 @@ mod selectora {
             )
         }
     }
+
+    mod set_espera {
+        use super::*;
+        use hardware::errors::SelectoraError;
+        use hardware::selectora::{ESPERA_INICIAL, ESPERA_MAX};
+        use types::unidades::Milisegundos;
+
+        #[test]
+        fn test_cambio_espera() {
+            let mut selectora = Selectora::new(POSICION_MAXIMA);
+            assert_eq!(selectora.get_espera(), ESPERA_INICIAL);
+
+            selectora.set_espera(ESPERA_MAX).unwrap();
+            assert_eq!(selectora.get_espera(), ESPERA_MAX);
+        }
+
+        #[test]
+        fn test_no_pasa_el_maximo() {
+            let mut selectora = Selectora::new(POSICION_MAXIMA);
+
+            assert_eq!(
+                selectora.set_espera(ESPERA_MAX + Milisegundos(1)),
+                Err(SelectoraError::FueraDeRango)
+            );
+            assert_eq!(selectora.get_espera(), ESPERA_INICIAL);
+        }
+    }
 }
@@ -0,0 +1,276 @@
+#![cfg(feature = "serde")]
+
+use std::time::Duration;
+
+use hardware::calibracion::Curva;
+use hardware::dosificador::Dosificador;
+use hardware::logs::Print;
+use hardware::rampa::{Perfil, Rampa};
+use hardware::silo::Silo;
+use hardware::soplador::Soplador;
+use types::rango::{Politica, Rango};
+
+#[cfg(test)]
+mod serializacion {
+    use super::*;
+
+    fn ida_y_vuelta<T: serde::Serialize + serde::de::DeserializeOwned>(valor: &T) -> T {
+        serde_json::from_str(&serde_json::to_string(valor).unwrap()).unwrap()
+    }
+
+    mod rango {
+        use super::*;
+
+        #[test]
+        fn test_ida_y_vuelta() {
+            let rango = Rango::new(10, 20, 15).unwrap();
+            assert_eq!(ida_y_vuelta(&rango), rango);
+        }
+
+        #[test]
+        fn test_ida_y_vuelta_generico() {
+            let contador = Rango::new(0, u64::MAX, u64::from(u32::MAX) + 1).unwrap();
+            assert_eq!(ida_y_vuelta(&contador), contador);
+
+            let espera = Rango::new(
+                Duration::from_secs(1),
+                Duration::from_secs(60),
+                Duration::from_millis(2500),
+            )
+            .unwrap();
+            assert_eq!(ida_y_vuelta(&espera), espera);
+        }
+
+        #[test]
+        fn test_valor_fuera_de_rango() {
+            let error = serde_json::from_str::<Rango>(r#"{"valor": 25, "min": 10, "max": 20}"#)
+                .err()
+                .unwrap();
+            assert!(error.to_string().contains("fuera de rango (10..=20)"));
+        }
+
+        #[test]
+        fn test_ida_y_vuelta_con_politicas() {
+            let mut rango = Rango::new(0, 100, 50)
+                .unwrap()
+                .con_politica(Politica::Acotar)
+                .con_paso(5)
+                .unwrap()
+                .excluir_min()
+                .unwrap();
+            rango.restringir(0, 80).unwrap();
+
+            let texto = serde_json::to_string(&rango).unwrap();
+            assert!(texto.contains(r#""politica":"acotar""#));
+            assert_eq!(serde_json::from_str::<Rango>(&texto).unwrap(), rango);
+        }
+
+        #[test]
+        fn test_sin_politicas_no_cambia_el_formato() {
+            let rango = Rango::new(10, 20, 15).unwrap();
+            assert_eq!(
+                serde_json::to_string(&rango).unwrap(),
+                r#"{"valor":15,"min":10,"max":20}"#
+            );
+        }
+
+        #[test]
+        fn test_valor_fuera_del_paso() {
+            let texto = r#"{"valor": 52, "min": 0, "max": 100, "paso": 5}"#;
+            let error = serde_json::from_str::<Rango>(texto).err().unwrap();
+            assert!(error.to_string().contains("valor 52 fuera del paso 5"));
+
+            let texto = r#"{"valor": 55, "min": 0, "max": 100, "paso": 5}"#;
+            assert_eq!(serde_json::from_str::<Rango>(texto).unwrap().get(), 55);
+        }
+
+        #[test]
+        fn test_restringido_excluye_el_valor() {
+            let texto =
+                r#"{"valor": 90, "min": 0, "max": 100, "restringido": {"min": 0, "max": 80}}"#;
+            let error = serde_json::from_str::<Rango>(texto).err().unwrap();
+            assert!(error
+                .to_string()
+                .contains("valor 90 fuera de los límites restringidos (0..=80)"));
+        }
+    }
+
+    mod silo {
+        use super::*;
+        use types::unidades::{Gramos, Kilogramos};
+
+        #[test]
+        fn test_ida_y_vuelta() {
+            let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+            silo.set_alimento(Kilogramos(1000)).unwrap();
+            silo.entregar_gramos(Gramos(1500)).unwrap();
+
+            let copia = ida_y_vuelta(&silo);
+            assert_eq!(copia.get_alimento_gramos(), Gramos(998_500));
+            assert_eq!(copia.get_historico_gramos(), silo.get_historico_gramos());
+            assert_eq!(copia.get_id(), silo.get_id());
+        }
+
+        #[test]
+        fn test_supera_capacidad() {
+            let texto = r#"{"capacidad": 10, "alimento_gramos": 10001}"#;
+            assert!(serde_json::from_str::<Silo>(texto).is_err());
+
+            let texto = r#"{"capacidad": 5000000, "alimento_gramos": 0}"#;
+            let error = serde_json::from_str::<Silo>(texto).err().unwrap();
+            assert!(error
+                .to_string()
+                .contains("capacidad 5000000kg fuera de rango (0kg..=4294967kg)"));
+        }
+
+        #[test]
+        fn test_historico_mayor_a_u32() {
+            let historico = u64::from(u32::MAX) * 3;
+            let texto = format!(
+                r#"{{"capacidad": 10, "alimento_gramos": 0, "historico_gramos": {}}}"#,
+                historico
+            );
+            let silo: Silo = serde_json::from_str(&texto).unwrap();
+            assert_eq!(silo.get_historico_gramos(), historico);
+            assert_eq!(silo.get_historico(), Kilogramos((historico / 1000) as u32));
+        }
+
+        #[test]
+        fn test_capacidad_reducida() {
+            let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+            silo.reducir_capacidad(Kilogramos(12000)).unwrap();
+
+            let copia = ida_y_vuelta(&silo);
+            assert_eq!(copia.get_capacidad(), Kilogramos(12000));
+            assert_eq!(copia.get_capacidad_nominal(), Kilogramos(24000));
+
+            let texto = r#"{"capacidad": 10, "capacidad_reducida": 5, "alimento_gramos": 6000}"#;
+            let error = serde_json::from_str::<Silo>(texto).err().unwrap();
+            assert!(error
+                .to_string()
+                .contains("alimento_gramos 6000g supera la capacidad de 5kg"));
+        }
+    }
+
+    mod dosificador {
+        use super::*;
+        use types::unidades::{Gramos, GramosPorSegundo, Milisegundos};
+
+        #[test]
+        fn test_ida_y_vuelta() {
+            let mut doser = Dosificador::new_gramos(GramosPorSegundo(1500)).unwrap();
+            doser.set_curva(Some(Curva::new(GramosPorSegundo(1800), -150)));
+
+            let copia = ida_y_vuelta(&doser);
+            assert_eq!(
+                copia.get_curva(),
+                Some(Curva::new(GramosPorSegundo(1800), -150))
+            );
+            assert_eq!(copia.get_id(), doser.get_id());
+            assert_eq!(copia.calcular_gramos(Milisegundos(5000)), Gramos(8850));
+        }
+
+        #[test]
+        fn test_entrega_cero() {
+            assert!(serde_json::from_str::<Dosificador>(r#"{"entrega": 0}"#).is_err());
+        }
+
+        #[test]
+        fn test_curva_con_entrega_cero() {
+            assert!(serde_json::from_str::<Curva>(r#"{"entrega": 0, "desfase": -150}"#).is_err());
+            assert!(serde_json::from_str::<Dosificador>(
+                r#"{"entrega": 1500, "curva": {"entrega": 0, "desfase": 0}}"#
+            )
+            .is_err());
+            assert_eq!(
+                serde_json::from_str::<Curva>(r#"{"entrega": 1800, "desfase": -150}"#).unwrap(),
+                Curva::new(GramosPorSegundo(1800), -150)
+            );
+        }
+    }
+
+    mod soplador {
+        use super::*;
+        use types::unidades::{Milisegundos, Porcentaje};
+
+        #[test]
+        fn test_ida_y_vuelta() {
+            let mut rampa = Rampa::new();
+            rampa.set_perfil(Perfil::CurvaS);
+            rampa.set_subida(Milisegundos(8000)).unwrap();
+            let mut soplador = Soplador::new();
+            soplador.set_rampa(rampa);
+            soplador
+                .set_potencia_alimento("pellet-4mm", Porcentaje(60))
+                .unwrap();
+            soplador.set_alimento("pellet-4mm").unwrap();
+
+            let texto = serde_json::to_string(&soplador).unwrap();
+            assert!(texto.contains(r#""perfil":"curva_s""#));
+
+            let copia: Soplador = serde_json::from_str(&texto).unwrap();
+            assert_eq!(copia.get_rampa(), rampa);
+            assert_eq!(copia.get_potencia(), Porcentaje(60));
+            assert_eq!(copia.get_alimento(), Some("pellet-4mm"));
+            assert!(!copia.get_estado());
+        }
+
+        #[test]
+        fn test_encendido_queda_listo() {
+            let copia: Soplador =
+                serde_json::from_str(r#"{"estado": true, "potencia": 80}"#).unwrap();
+            assert!(copia.is_listo());
+            assert_eq!(copia.get_potencia_actual(), Porcentaje(80));
+        }
+
+        #[test]
+        fn test_potencia_fuera_de_rango() {
+            let error = serde_json::from_str::<Soplador>(r#"{"potencia": 150}"#)
+                .err()
+                .unwrap();
+            assert!(error
+                .to_string()
+                .contains("potencia 150% fuera de rango (0%..=100%)"));
+
+            let texto = r#"{"potencias": {"pellet-4mm": 101}}"#;
+            assert!(serde_json::from_str::<Soplador>(texto).is_err());
+        }
+
+        #[test]
+        fn test_rampa_fuera_de_rango() {
+            let texto = r#"{"rampa": {"perfil": "lineal", "subida": 60001, "bajada": 5000}}"#;
+            let error = serde_json::from_str::<Soplador>(texto).err().unwrap();
+            assert!(error
+                .to_string()
+                .contains("subida 60001ms fuera de rango (0ms..=60000ms)"));
+        }
+
+        #[test]
+        fn test_alimento_desconocido() {
+            let texto = r#"{"potencia": 50, "alimento": "pellet-9mm"}"#;
+            assert!(serde_json::from_str::<Soplador>(texto).is_err());
+        }
+    }
+
+    mod registro {
+        use super::*;
+        use types::unidades::Kilogramos;
+
+        #[test]
+        fn test_json() {
+            let mut silo = Silo::new(Kilogramos(24000)).unwrap();
+            silo.set_alimento(Kilogramos(1)).unwrap();
+
+            let json = serde_json::to_value(silo.registro()).unwrap();
+
+            assert_eq!(
+                json,
+                serde_json::json!({
+                    "origen": "Silo",
+                    "id": silo.get_id().to_string(),
+                    "campos": {"alimento_gramos": 1000, "historico_gramos": 1000},
+                })
+            );
+        }
+    }
+}
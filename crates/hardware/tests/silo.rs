@@ -1,8 +1,9 @@
 use hardware::errors::SiloError;
 use hardware::silo::Silo;
+use types::unidades::Kilogramos;
 
-const CAPACIDAD_SILOS: u32 = 24000;
-const GRAMOS_PULSO: u32 = 25;
+const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
+const PULSO: Kilogramos = Kilogramos(25);
 
 // #[cfg(test)]
 // mod avisos {
@@ -26,18 +27,33 @@ mod silo {
 
     mod new {
         use super::{Silo, CAPACIDAD_SILOS};
+        use types::unidades::Kilogramos;
         /// Prueba la creación de un nuevo dosificador y verifica sus valores iniciales.
         #[test]
         fn test_alimento_inicial() {
-            let silo = Silo::new(CAPACIDAD_SILOS);
-            assert_eq!(silo.get_alimento(), 0);
+            let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            assert_eq!(silo.get_alimento(), Kilogramos(0));
         }
 
         #[test]
         fn test_historico_inicial() {
-            let silo = Silo::new(CAPACIDAD_SILOS);
+            let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
-            assert_eq!(silo.get_historico(), 0);
+            assert_eq!(silo.get_historico(), Kilogramos(0));
+        }
+
+        /// Prueba que una capacidad que no cabe en gramos se rechaza en vez de acotarse
+        #[test]
+        fn test_capacidad_maxima() {
+            use hardware::errors::SiloError;
+            use hardware::silo::CAPACIDAD_MAX;
+
+            let silo = Silo::new(CAPACIDAD_MAX).unwrap();
+            assert_eq!(silo.get_capacidad(), CAPACIDAD_MAX);
+            assert_eq!(
+                Silo::new(CAPACIDAD_MAX + Kilogramos(1)).err(),
+                Some(SiloError::FueraDeRango)
+            );
         }
 
         mod id {
@@ -47,17 +63,17 @@ mod silo {
             /// Prueba que la id sea unica
             #[test]
             fn test_unicidad() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
                 // Verifica unicidad de id
                 let id1 = silo.get_id();
-                let id2 = Silo::new(CAPACIDAD_SILOS).get_id();
+                let id2 = Silo::new(CAPACIDAD_SILOS).unwrap().get_id();
                 assert_ne!(id1, id2, "Las ID no son únicas");
             }
 
             /// Prueba que la id sea un UUID válido
             #[test]
             fn test_validez() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
                 let id = silo.get_id();
                 let parsed_id = Uuid::parse_str(&id.to_string());
                 assert!(parsed_id.is_ok(), "El ID no es un UUID válido");
@@ -65,7 +81,7 @@ mod silo {
 
             #[test]
             fn tets_is_v4() {
-                let silo = Silo::new(CAPACIDAD_SILOS);
+                let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
                 let id = silo.get_id();
                 let parsed_id = Uuid::parse_str(&id.to_string());
                 assert_eq!(
@@ -80,101 +96,102 @@ mod silo {
     /// Prueba el setter `set_entrega` para modificar la capacidad de entrega del dosificador.
     #[test]
     fn test_aumenta_alimento() {
-        let mut silo = Silo::new(CAPACIDAD_SILOS);
+        let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
         // Comprobamos que set alimento funciona bien
-        silo.set_alimento(10)
+        silo.set_alimento(Kilogramos(10))
             .expect("[test_aumenta_alimento]: No se ha podido actualizar [Linea 70]");
-        assert_eq!(silo.get_alimento(), 10);
+        assert_eq!(silo.get_alimento(), Kilogramos(10));
     }
 
     mod set_historico {
         use super::{Silo, CAPACIDAD_SILOS};
+        use types::unidades::Kilogramos;
         #[test]
         fn test_aumenta() {
-            let mut silo = Silo::new(CAPACIDAD_SILOS);
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
             // Comprobamos que el historico aumenta
-            silo.set_alimento(11)
+            silo.set_alimento(Kilogramos(11))
                 .expect("[test_aumenta]: No se ha actualizado el alimento");
-            assert_eq!(silo.get_historico(), 11);
+            assert_eq!(silo.get_historico(), Kilogramos(11));
         }
         #[test]
         fn test_no_disminuye() {
-            let mut silo = Silo::new(CAPACIDAD_SILOS);
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
-            assert_eq!(silo.get_historico(), 0); // Valor inicial
+            assert_eq!(silo.get_historico(), Kilogramos(0)); // Valor inicial
 
-            silo.set_alimento(11)
+            silo.set_alimento(Kilogramos(11))
                 .expect("[test_no_disminuye]: No se ha actualizado el alimento [11]");
-            assert_eq!(silo.get_historico(), 11);
+            assert_eq!(silo.get_historico(), Kilogramos(11));
             // Comprobamos que el historico no disminuye
-            silo.set_alimento(8)
+            silo.set_alimento(Kilogramos(8))
                 .expect("[test_no_disminuye]: No se ha actualizado el alimento [8]");
 
-            assert_ne!(silo.get_historico(), 8);
+            assert_ne!(silo.get_historico(), Kilogramos(8));
         }
 
         #[test]
         fn test_aumenta_varias_veces() {
-            let mut silo = Silo::new(CAPACIDAD_SILOS);
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
             silo.set_alimento(CAPACIDAD_SILOS).expect(
                 "[test_aumenta_varias_veces]: No se ha podido actualizar alimento [Linea 105]",
             );
             assert_eq!(silo.get_historico(), CAPACIDAD_SILOS);
 
-            silo.set_alimento(0)
+            silo.set_alimento(Kilogramos(0))
                 .expect("[test_aumenta_varias_veces]: No se ha podido actualiza [Linea 111]");
             assert_eq!(silo.get_historico(), CAPACIDAD_SILOS);
 
             silo.set_alimento(CAPACIDAD_SILOS).expect(
                 "[test_aumenta_varias_veces]: No se ha podido actualizar alimento [Linea 105]",
             );
-            assert_eq!(silo.get_historico(), 2 * CAPACIDAD_SILOS);
+            assert_eq!(silo.get_historico(), CAPACIDAD_SILOS + CAPACIDAD_SILOS);
         }
     }
 
     /// Verifica que el valor de `entrega` se obtiene correctamente.
     #[test]
     fn test_get_alimento() {
-        let silo = Silo::new(CAPACIDAD_SILOS);
-        assert_eq!(silo.get_alimento(), 0); // Valor inicial
+        let silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+        assert_eq!(silo.get_alimento(), Kilogramos(0)); // Valor inicial
     }
 
     // Revisar por que no pasó los tests
     mod entregar_pulso {
-        use super::{Silo, SiloError, CAPACIDAD_SILOS, GRAMOS_PULSO};
+        use super::{Silo, SiloError, CAPACIDAD_SILOS, PULSO};
+        use types::unidades::Kilogramos;
 
         /// Verificar que los pulsos se entregan
         #[test]
         fn test_actualiza_alimento() {
-            let mut silo = Silo::new(CAPACIDAD_SILOS);
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
             silo.set_alimento(CAPACIDAD_SILOS).expect(
                 "[test_actualiza_alimento]: No se ha podido actualizar el alimento [Linea 141]",
             );
 
-            silo.entregar_pulso(GRAMOS_PULSO)
+            silo.entregar_pulso(PULSO)
                 .expect("[test_actualiza_alimento]: No se ha podido entregar pulso [Linea 145]");
-            assert_eq!(silo.get_alimento(), CAPACIDAD_SILOS - GRAMOS_PULSO);
+            assert_eq!(silo.get_alimento(), CAPACIDAD_SILOS - PULSO);
 
-            silo.entregar_pulso(CAPACIDAD_SILOS - GRAMOS_PULSO)
+            silo.entregar_pulso(CAPACIDAD_SILOS - PULSO)
                 .expect("[test_actualiza_alimento]:  No se ha podido entregar pulso [Linea 149]");
-            assert_eq!(silo.get_alimento(), 0);
+            assert_eq!(silo.get_alimento(), Kilogramos(0));
         }
 
         #[test]
+        #[allow(clippy::expect_fun_call)]
         fn test_no_actualiza_historico() {
-            let mut silo = Silo::new(CAPACIDAD_SILOS);
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
             silo.set_alimento(CAPACIDAD_SILOS)
                 .expect("Ha sobrepasado la capacidad");
             let historico = silo.get_historico();
 
-            silo.entregar_pulso(GRAMOS_PULSO).expect(&format!(
-                "El silo es incapaz de entregar {} gramos",
-                GRAMOS_PULSO
-            ));
+            silo.entregar_pulso(PULSO)
+                .expect(&format!("El silo es incapaz de entregar {}", PULSO));
             assert_eq!(
                 silo.get_historico(),
                 historico,
@@ -184,12 +201,113 @@ mod silo {
 
         #[test]
         fn test_no_entrega_vacio() {
-            let mut silo = Silo::new(CAPACIDAD_SILOS);
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
 
-            let resultado = silo.entregar_pulso(GRAMOS_PULSO);
+            let resultado = silo.entregar_pulso(PULSO);
 
             // Compara el resultado con el error esperado dentro de SiloError
             assert_eq!(resultado, Err(SiloError::SinAlimento));
         }
     }
+
+    mod entregar_gramos {
+        use super::{Silo, SiloError, CAPACIDAD_SILOS};
+        use types::unidades::{Gramos, Kilogramos};
+
+        #[test]
+        fn test_fracciones_de_kilo() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(Kilogramos(1))
+                .expect("Ha sobrepasado la capacidad");
+
+            silo.entregar_gramos(Gramos(250))
+                .expect("El silo es incapaz de entregar 250 gramos");
+            assert_eq!(silo.get_alimento_gramos(), Gramos(750));
+            assert_eq!(
+                silo.get_alimento(),
+                Kilogramos(0),
+                "Los kg no se truncan hacia abajo"
+            );
+
+            assert_eq!(
+                silo.entregar_gramos(Gramos(751)),
+                Err(SiloError::SinAlimento)
+            );
+            silo.entregar_gramos(Gramos(750))
+                .expect("El silo es incapaz de entregar lo que queda");
+            assert_eq!(silo.get_alimento_gramos(), Gramos(0));
+        }
+    }
+
+    mod reducir_capacidad {
+        use super::{Silo, SiloError, CAPACIDAD_SILOS};
+        use types::unidades::{Gramos, Kilogramos};
+
+        #[test]
+        fn test_limita_la_carga() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.reducir_capacidad(Kilogramos(12000))
+                .expect("No se ha podido reducir la capacidad");
+
+            assert_eq!(silo.get_capacidad(), Kilogramos(12000));
+            assert_eq!(silo.get_capacidad_nominal(), CAPACIDAD_SILOS);
+            assert_eq!(
+                silo.set_alimento(Kilogramos(12001)),
+                Err(SiloError::FueraDeRango)
+            );
+            silo.set_alimento(Kilogramos(12000))
+                .expect("Ha sobrepasado la capacidad reducida");
+            silo.entregar_gramos(Gramos(500))
+                .expect("El silo es incapaz de entregar con la capacidad reducida");
+        }
+
+        /// Prueba que una carga rechazada por la capacidad reducida no suma al histórico
+        #[test]
+        fn test_carga_rechazada_no_suma_historico() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(Kilogramos(5000)).unwrap();
+            silo.reducir_capacidad(Kilogramos(12000)).unwrap();
+
+            assert_eq!(
+                silo.set_alimento(Kilogramos(20000)),
+                Err(SiloError::FueraDeRango)
+            );
+            assert_eq!(silo.get_historico(), Kilogramos(5000));
+            assert_eq!(silo.get_alimento(), Kilogramos(5000));
+        }
+
+        #[test]
+        fn test_restablecer() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.reducir_capacidad(Kilogramos(12000)).unwrap();
+            silo.restablecer_capacidad();
+
+            assert_eq!(silo.get_capacidad(), CAPACIDAD_SILOS);
+            silo.set_alimento(CAPACIDAD_SILOS)
+                .expect("Ha sobrepasado la capacidad nominal");
+        }
+
+        #[test]
+        fn test_sobre_la_nominal() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            assert_eq!(
+                silo.reducir_capacidad(CAPACIDAD_SILOS + Kilogramos(1)),
+                Err(SiloError::FueraDeRango)
+            );
+            assert_eq!(silo.get_capacidad(), CAPACIDAD_SILOS);
+        }
+
+        #[test]
+        fn test_bajo_el_alimento_actual() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(Kilogramos(15000)).unwrap();
+
+            assert_eq!(
+                silo.reducir_capacidad(Kilogramos(12000)),
+                Err(SiloError::FueraDeRango)
+            );
+            assert_eq!(silo.get_capacidad(), CAPACIDAD_SILOS);
+            assert_eq!(silo.get_alimento(), Kilogramos(15000));
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use hardware::soplador::Soplador;
+use types::unidades::Porcentaje;
 
 // #[cfg(test)]
 // mod avisos {
@@ -22,9 +23,11 @@ mod soplador {
 
     mod new {
         use super::Soplador;
+        use types::unidades::Porcentaje;
 
         /// Prueba que el estado inicial sea false
         #[test]
+        #[allow(clippy::bool_assert_comparison)]
         fn test_estado_inicial() {
             let soplador = Soplador::new();
             assert_eq!(
@@ -38,7 +41,11 @@ mod soplador {
         #[test]
         fn test_potencia_inicial() {
             let soplador = Soplador::new();
-            assert_eq!(soplador.get_potencia(), 0, "La potencia inicial no es 0");
+            assert_eq!(
+                soplador.get_potencia(),
+                Porcentaje(0),
+                "La potencia inicial no es 0"
+            );
         }
 
         mod id {
@@ -80,6 +87,7 @@ mod soplador {
 
     /// Prueba que cambia de estado
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_set_estado() {
         let mut soplador = Soplador::new();
 
@@ -89,6 +97,7 @@ mod soplador {
 
     mod set_potencia {
         use super::Soplador;
+        use types::unidades::Porcentaje;
 
         /// Prueba que cambia
         #[test]
@@ -97,9 +106,13 @@ mod soplador {
 
             // Probamos que cambia el valor
             soplador
-                .set_potencia(100)
+                .set_potencia(Porcentaje(100))
                 .expect("[test_set_un_valor] Linea 99");
-            assert_eq!(soplador.get_potencia(), 100, "La potencia no ha cambiado");
+            assert_eq!(
+                soplador.get_potencia(),
+                Porcentaje(100),
+                "La potencia no ha cambiado"
+            );
         }
 
         /// Probamos que no pase el 100
@@ -107,7 +120,7 @@ mod soplador {
         fn set_sobre_el_rango() {
             let mut soplador = Soplador::new();
             // Intentamos establecer una potencia fuera del límite superior
-            let result = soplador.set_potencia(102);
+            let result = soplador.set_potencia(Porcentaje(102));
 
             // La potencia debería estar en un rango menor o igual a 100
             // comentario: Al recibir como argumento un u32 el compilador no compila
@@ -127,6 +140,7 @@ mod soplador {
 
         /// Probamos que se obtiene el estado
         #[test]
+        #[allow(clippy::bool_comparison)]
         fn test_is_booleano() {
             let soplador = Soplador::new();
 
@@ -138,6 +152,7 @@ mod soplador {
 
         /// Probamos que efectivamente obtenemos valores actualizados
         #[test]
+        #[allow(clippy::bool_assert_comparison)]
         fn test_actualiza_el_valor() {
             let mut soplador = Soplador::new();
 
@@ -155,15 +170,168 @@ mod soplador {
 
     /// Verifica que los setters `set_alimento` y `entregar_pulso` se pueden encadenar correctamente.
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_chain_setters() {
         let mut soplador = Soplador::new();
 
         soplador
             .set_estado(true)
-            .set_potencia(100)
+            .set_potencia(Porcentaje(100))
             .expect("[test_chain_setters] Linea 191");
 
         assert_eq!(soplador.get_estado(), true);
-        assert_eq!(soplador.get_potencia(), 100);
+        assert_eq!(soplador.get_potencia(), Porcentaje(100));
+    }
+
+    mod rampa {
+        use super::Soplador;
+        use hardware::errors::SopladorError;
+        use hardware::rampa::{Perfil, Rampa, RAMPA_MAX};
+        use std::sync::Arc;
+        use types::unidades::{Milisegundos, Porcentaje};
+        use utils::reloj::RelojManual;
+
+        fn crear_soplador(reloj: &Arc<RelojManual>, perfil: Perfil) -> Soplador {
+            let mut rampa = Rampa::new();
+            rampa.set_perfil(perfil);
+            rampa.set_subida(Milisegundos(4000)).unwrap();
+            rampa.set_bajada(Milisegundos(2000)).unwrap();
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone()).set_rampa(rampa);
+            soplador.set_potencia(Porcentaje(80)).unwrap();
+            soplador
+        }
+
+        /// Prueba que el encendido sube la potencia de forma lineal hasta quedar listo
+        #[test]
+        fn test_subida_lineal() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj, Perfil::Lineal);
+
+            soplador.set_estado(true);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(0));
+            assert!(!soplador.is_listo());
+            assert_eq!(soplador.get_restante(), Milisegundos(4000));
+
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(20));
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(40));
+            assert!(!soplador.is_listo());
+
+            reloj.avanzar(2000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(80));
+            assert_eq!(soplador.get_restante(), Milisegundos(0));
+            assert!(soplador.is_listo(), "El soplador no quedó listo");
+        }
+
+        /// Prueba que la curva en S parte y termina más suave que la lineal
+        #[test]
+        fn test_subida_curva_s() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj, Perfil::CurvaS);
+
+            soplador.set_estado(true);
+            reloj.avanzar(1000);
+            assert!(soplador.get_potencia_actual() < Porcentaje(20));
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(40));
+            reloj.avanzar(1000);
+            assert!(soplador.get_potencia_actual() > Porcentaje(60));
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(80));
+        }
+
+        /// Prueba que el apagado baja la potencia con la rampa de bajada
+        #[test]
+        fn test_bajada() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj, Perfil::Lineal);
+            soplador.set_estado(true);
+            reloj.avanzar(4000);
+
+            soplador.set_estado(false);
+            assert!(!soplador.get_estado());
+            assert!(!soplador.is_listo());
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(40));
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(0));
+        }
+
+        /// Prueba que una rampa más larga que `RAMPA_MAX` se rechaza
+        #[test]
+        fn test_rampa_fuera_de_rango() {
+            let mut rampa = Rampa::new();
+            rampa.set_subida(RAMPA_MAX).unwrap();
+            assert_eq!(
+                rampa.set_subida(RAMPA_MAX + Milisegundos(1)),
+                Err(SopladorError::FueraDeRango)
+            );
+            assert_eq!(
+                rampa.set_bajada(RAMPA_MAX + Milisegundos(1)),
+                Err(SopladorError::FueraDeRango)
+            );
+            assert_eq!(rampa.get_subida(), RAMPA_MAX);
+            assert_eq!(rampa.get_bajada(), hardware::soplador::TIEMPO_DE_ENCENDIDO);
+        }
+
+        /// Prueba que un cambio de potencia encendido vuelve a pasar por la rampa
+        #[test]
+        fn test_cambio_de_potencia() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj, Perfil::Lineal);
+            soplador.set_estado(true);
+            reloj.avanzar(4000);
+
+            soplador.set_potencia(Porcentaje(40)).unwrap();
+            assert_eq!(soplador.get_potencia(), Porcentaje(40));
+            assert!(!soplador.is_listo());
+            reloj.avanzar(1000);
+            assert_eq!(soplador.get_potencia_actual(), Porcentaje(60));
+            reloj.avanzar(1000);
+            assert!(soplador.is_listo());
+        }
+
+        /// Prueba que encender un soplador encendido no reinicia la rampa
+        #[test]
+        fn test_encender_dos_veces() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj, Perfil::Lineal);
+            soplador.set_estado(true);
+            reloj.avanzar(4000);
+
+            soplador.set_estado(true);
+            assert!(soplador.is_listo());
+        }
+
+        /// Prueba la potencia por tipo de alimento
+        #[test]
+        fn test_potencia_por_alimento() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj, Perfil::Lineal);
+
+            soplador
+                .set_potencia_alimento("pellet-4mm", Porcentaje(60))
+                .unwrap();
+            soplador
+                .set_potencia_alimento("pellet-9mm", Porcentaje(90))
+                .unwrap();
+            assert_eq!(
+                soplador.set_potencia_alimento("pellet-2mm", Porcentaje(101)),
+                Err(SopladorError::FueraDeRango)
+            );
+
+            soplador.set_alimento("pellet-9mm").unwrap();
+            assert_eq!(soplador.get_potencia(), Porcentaje(90));
+            assert_eq!(soplador.get_alimento(), Some("pellet-9mm"));
+
+            assert_eq!(
+                soplador.set_alimento("pellet-2mm"),
+                Err(SopladorError::AlimentoDesconocido)
+            );
+            assert_eq!(soplador.get_potencia(), Porcentaje(90));
+            assert_eq!(soplador.get_alimento(), Some("pellet-9mm"));
+        }
     }
 }
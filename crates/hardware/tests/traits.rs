@@ -0,0 +1,104 @@
+use hardware::dosificador::Dosificador;
+use hardware::selectora::Selectora;
+use hardware::silo::Silo;
+use hardware::soplador::Soplador;
+use hardware::traits::{Almacenar, Dosificar, Seleccionar, Soplar};
+use types::unidades::{KgPorSegundo, Kilogramos, Milisegundos, Porcentaje};
+
+const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
+const ENTREGA: KgPorSegundo = KgPorSegundo(10);
+const POSICION_MAXIMA: u32 = 5;
+
+#[cfg(test)]
+mod traits {
+    use super::*;
+
+    mod soplar {
+        use super::*;
+
+        fn potenciar<S: Soplar>(soplador: &mut S, potencia: Porcentaje) {
+            soplador
+                .set_potencia(potencia)
+                .expect("Ha puesto una potencia sobre el rango permitido");
+        }
+
+        #[test]
+        fn test_soplador_implementa() {
+            let mut soplador = Soplador::new();
+
+            potenciar(&mut soplador, Porcentaje(80));
+
+            assert_eq!(Soplar::get_potencia(&soplador), Porcentaje(80));
+            assert_eq!(Soplar::get_id(&soplador), soplador.get_id());
+        }
+    }
+
+    mod dosificar {
+        use super::*;
+
+        fn abrir<D: Dosificar>(doser: &mut D) -> bool {
+            doser.set_estado(true).get_estado()
+        }
+
+        #[test]
+        fn test_dosificador_implementa() {
+            let mut dosificador = Dosificador::new(ENTREGA).unwrap();
+
+            assert!(abrir(&mut dosificador), "El dosificador no se ha abierto");
+            assert_eq!(Dosificar::get_entrega(&dosificador), ENTREGA);
+        }
+    }
+
+    mod almacenar {
+        use super::*;
+
+        fn descontar<A: Almacenar>(silo: &mut A, pulso: Kilogramos) -> Kilogramos {
+            silo.entregar_pulso(pulso)
+                .expect("El silo es incapaz de entregar el pulso");
+            silo.get_alimento()
+        }
+
+        #[test]
+        fn test_silo_implementa() {
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            Almacenar::set_alimento(&mut silo, CAPACIDAD_SILOS)
+                .expect("Ha intentado ingresar mas alimento que la capacidad del silo");
+
+            assert_eq!(
+                descontar(&mut silo, Kilogramos(100)),
+                CAPACIDAD_SILOS - Kilogramos(100)
+            );
+            assert_eq!(Almacenar::get_historico(&silo), CAPACIDAD_SILOS);
+        }
+    }
+
+    mod seleccionar {
+        use super::*;
+        use hardware::errors::SelectoraError;
+
+        fn mover<Sel: Seleccionar>(selectora: &mut Sel, n: u32) -> Result<(), SelectoraError> {
+            selectora.set_posicion(n)
+        }
+
+        #[test]
+        fn test_selectora_implementa() {
+            let mut selectora = Selectora::new(POSICION_MAXIMA);
+
+            mover(&mut selectora, 3).expect("Posición fuera del rango permitido");
+
+            assert_eq!(Seleccionar::get_posicion(&selectora), 3);
+            assert_eq!(Seleccionar::get_posiciones(&selectora).max, POSICION_MAXIMA);
+            assert_eq!(
+                mover(&mut selectora, POSICION_MAXIMA + 1),
+                Err(SelectoraError::FueraDeRango)
+            );
+        }
+
+        #[test]
+        fn test_espera_por_defecto() {
+            let selectora = Selectora::new(POSICION_MAXIMA);
+
+            assert_eq!(Seleccionar::get_espera(&selectora), Milisegundos(20000));
+        }
+    }
+}
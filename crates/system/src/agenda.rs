@@ -0,0 +1,581 @@
+use std::fmt;
+
+use hardware::selectora::Selectora;
+use hardware::traits::{Almacenar, Dosificar, Seleccionar, Soplar};
+
+use crate::control::Control;
+use crate::errors::AgendaError;
+use crate::estado::Monitor;
+use crate::programa::Programa;
+use crate::racion::Racion;
+use crate::reporte::Reporte;
+use crate::sol::{Luz, Ubicacion};
+use types::eventos::{self, Evento, Severidad};
+use types::unidades::Milisegundos;
+
+use utils::executor::bloquear;
+use utils::reloj::{self, RelojRef};
+
+/// Milisegundos en un día.
+pub const DIA: u64 = 24 * 60 * 60 * 1000;
+
+/// Milisegundos en un minuto.
+const MINUTO: u32 = 60 * 1000;
+
+/// Desfase máximo de la zona horaria respecto de UTC, en minutos.
+pub const ZONA_HORARIA_MAX: i32 = 14 * 60;
+
+/// Atraso máximo por defecto con que todavía se inicia una ración agendada: 30 minutos.
+pub const TOLERANCIA_INICIAL: Milisegundos = Milisegundos::minutos(30);
+
+/// Hora del día en la zona horaria de la agenda, con precisión de minutos.
+///
+/// # Ejemplo:
+///! ```
+///! let hora = Hora::new(10, 30)?;
+///! assert_eq!(hora.to_string(), "10:30");
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct Hora {
+    /// Milisegundos desde la medianoche.
+    ms: u32,
+}
+
+impl Hora {
+    /// Crea la hora `hora:minuto`. La medianoche es `Hora::new(0, 0)`.
+    ///
+    /// # Errores:
+    /// - `AgendaError::HoraInvalida`: La hora no es menor a 24 o el minuto no es menor a 60.
+    pub fn new(hora: u32, minuto: u32) -> Result<Self, AgendaError> {
+        if hora >= 24 || minuto >= 60 {
+            return Err(AgendaError::HoraInvalida);
+        }
+        Ok(Self {
+            ms: (hora * 60 + minuto) * MINUTO,
+        })
+    }
+
+    /// Hora del día que corresponde a `ms` milisegundos desde la medianoche.
+    /// Se descartan los segundos.
+    pub(crate) fn desde_ms(ms: u64) -> Self {
+        let minutos = (ms % DIA) as u32 / MINUTO;
+        Self {
+            ms: minutos * MINUTO,
+        }
+    }
+
+    pub fn get_hora(&self) -> u32 {
+        self.ms / MINUTO / 60
+    }
+
+    pub fn get_minuto(&self) -> u32 {
+        self.ms / MINUTO % 60
+    }
+
+    /// Obtiene los milisegundos desde la medianoche.
+    pub fn get_ms(&self) -> u32 {
+        self.ms
+    }
+}
+
+impl fmt::Display for Hora {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.get_hora(), self.get_minuto())
+    }
+}
+
+/// Intervalo del día en que se permite alimentar, por ejemplo las horas de luz.
+///
+/// Incluye `inicio` y excluye `termino`.
+///
+/// # Ejemplo:
+///! ```
+///! let ventana = Ventana::new(Hora::new(6, 0)?, Hora::new(18, 0)?)?;
+///! assert!(ventana.contiene(Hora::new(7, 0)?));
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Ventana {
+    inicio: Hora,
+    termino: Hora,
+}
+
+impl Ventana {
+    /// # Errores:
+    /// - `AgendaError::VentanaInvalida`: `inicio` no es anterior a `termino`.
+    pub fn new(inicio: Hora, termino: Hora) -> Result<Self, AgendaError> {
+        if inicio >= termino {
+            return Err(AgendaError::VentanaInvalida);
+        }
+        Ok(Self { inicio, termino })
+    }
+
+    pub fn get_inicio(&self) -> Hora {
+        self.inicio
+    }
+
+    pub fn get_termino(&self) -> Hora {
+        self.termino
+    }
+
+    pub fn contiene(&self, hora: Hora) -> bool {
+        self.inicio <= hora && hora < self.termino
+    }
+}
+
+/// Cómo se ajustan las horas de la agenda a la luz del día cuando hay una `Ubicacion`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Ajuste {
+    /// Las raciones se ejecutan a su hora y se omiten las que caen antes del amanecer
+    /// o después del atardecer.
+    Omitir,
+    /// Las horas antes del amanecer pasan al amanecer y las posteriores al atardecer
+    /// pasan al atardecer.
+    Recortar,
+    /// Las horas se reparten proporcionalmente: la ventana de referencia se estira o
+    /// encoge hasta coincidir con la luz del día. Las horas fuera de la referencia
+    /// quedan en sus extremos.
+    Escalar(Ventana),
+}
+
+/// Una ración agendada a una hora del día.
+pub struct Horario<'a> {
+    hora: Hora,
+    racion: &'a Racion<'a>,
+}
+
+impl<'a> Horario<'a> {
+    pub fn get_hora(&self) -> Hora {
+        self.hora
+    }
+
+    pub fn get_racion(&self) -> &'a Racion<'a> {
+        self.racion
+    }
+}
+
+/// Qué ocurrió con una ración agendada.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Resultado {
+    /// La ración se ejecutó; el `Reporte` indica cómo terminó.
+    Ejecutado(Reporte),
+    /// La hora quedó fuera de todas las ventanas de alimentación.
+    FueraDeVentana,
+    /// La ración anterior seguía en curso y se superó la tolerancia de atraso.
+    Atrasado,
+}
+
+/// Registro de una ración agendada: su hora en la agenda, el instante en que correspondía
+/// (ya ajustado a la luz del día) y el resultado.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RegistroAgenda {
+    pub hora: Hora,
+    pub programado: u64,
+    pub resultado: Resultado,
+}
+
+/// Agenda diaria de alimentación.
+///
+/// Guarda las raciones del día, cada una a una hora, y las ejecuta con un `Programa`
+/// cuando el reloj llega a esa hora. Si hay ventanas de alimentación, solo se ejecutan
+/// las raciones cuya hora cae dentro de alguna; si no hay, se ejecutan todas.
+///
+/// Las horas se interpretan en la zona horaria de la agenda (UTC por defecto), ya que
+/// el reloj cuenta milisegundos desde la época Unix. Las raciones se ejecutan de a una:
+/// si una ración se extiende más allá de la hora de la siguiente, esta comienza al
+/// terminar la anterior, salvo que el atraso supere la tolerancia.
+///
+/// Con una `Ubicacion`, además, solo se alimenta con luz de día: el amanecer y el
+/// atardecer se calculan cada día y las horas se ajustan según el `Ajuste`.
+///
+/// # Ejemplo:
+///! ```
+///! let mut agenda = Agenda::new();
+///! agenda
+///!     .agregar(Hora::new(7, 0)?, &manana)
+///!     .agregar(Hora::new(10, 30)?, &media_manana)
+///!     .agregar(Hora::new(14, 0)?, &tarde)
+///!     .agregar_ventana(Ventana::new(Hora::new(6, 0)?, Hora::new(18, 0)?)?);
+///! agenda.set_zona_horaria(-3 * 60)?;
+///! agenda
+///!     .set_ubicacion(Some(Ubicacion::new(-41.47, -72.94)?))
+///!     .set_ajuste(Ajuste::Recortar);
+///! let registros = agenda.iniciar(u64::MAX, &mut soplador, &mut doser, &mut silo);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct Agenda<'a> {
+    /// Raciones agendadas, ordenadas por hora.
+    horarios: Vec<Horario<'a>>,
+
+    ventanas: Vec<Ventana>,
+
+    /// Desfase de la hora local respecto de UTC, en minutos.
+    zona_horaria: i32,
+
+    /// Atraso máximo con que todavía se inicia una ración.
+    tolerancia: Milisegundos,
+
+    /// Ubicación con la que se calcula la luz de cada día, si se alimenta solo de día.
+    ubicacion: Option<Ubicacion>,
+
+    ajuste: Ajuste,
+
+    reloj: RelojRef,
+
+    /// Estado de la ración en curso, compartido con cada `Programa` que se ejecuta.
+    monitor: Monitor,
+
+    /// Órdenes para la ración en curso, compartidas con cada `Programa` que se ejecuta.
+    control: Control,
+}
+
+impl<'a> Agenda<'a> {
+    pub fn new() -> Self {
+        Self {
+            horarios: Vec::new(),
+            ventanas: Vec::new(),
+            zona_horaria: 0,
+            tolerancia: TOLERANCIA_INICIAL,
+            ubicacion: None,
+            ajuste: Ajuste::Omitir,
+            reloj: reloj::real(),
+            monitor: Monitor::new(),
+            control: Control::new(),
+        }
+    }
+
+    /// Agenda `racion` todos los días a la `hora` indicada.
+    pub fn agregar(&mut self, hora: Hora, racion: &'a Racion<'a>) -> &mut Self {
+        // Las raciones a la misma hora se ejecutan en el orden en que se agregaron
+        let posicion = self.horarios.partition_point(|h| h.hora <= hora);
+        self.horarios.insert(posicion, Horario { hora, racion });
+        self
+    }
+
+    pub fn agregar_ventana(&mut self, ventana: Ventana) -> &mut Self {
+        self.ventanas.push(ventana);
+        self
+    }
+
+    /// Establece el desfase de la hora local respecto de UTC, en minutos.
+    ///
+    /// # Errores:
+    /// - `AgendaError::ZonaHorariaInvalida`: El desfase supera las 14 horas.
+    pub fn set_zona_horaria(&mut self, minutos: i32) -> Result<(), AgendaError> {
+        if minutos.abs() > ZONA_HORARIA_MAX {
+            return Err(AgendaError::ZonaHorariaInvalida);
+        }
+        self.zona_horaria = minutos;
+        Ok(())
+    }
+
+    /// Establece el atraso máximo con que todavía se inicia una ración.
+    pub fn set_tolerancia(&mut self, ms: Milisegundos) -> &mut Self {
+        self.tolerancia = ms;
+        self
+    }
+
+    /// Establece la ubicación del centro para alimentar solo con luz de día, o `None` para
+    /// ignorar la luz.
+    pub fn set_ubicacion(&mut self, ubicacion: Option<Ubicacion>) -> &mut Self {
+        self.ubicacion = ubicacion;
+        self
+    }
+
+    /// Establece cómo se ajustan las horas a la luz del día.
+    pub fn set_ajuste(&mut self, ajuste: Ajuste) -> &mut Self {
+        self.ajuste = ajuste;
+        self
+    }
+
+    /// Establece el reloj con el que se espera cada hora y se ejecutan las raciones.
+    pub fn set_reloj(&mut self, reloj: RelojRef) -> &mut Self {
+        self.reloj = reloj;
+        self
+    }
+
+    /// Obtiene un `Monitor` para consultar la ración que se está ejecutando.
+    pub fn get_monitor(&self) -> Monitor {
+        self.monitor.clone()
+    }
+
+    /// Obtiene un `Control` para pausar, reanudar o detener la ración que se está ejecutando.
+    pub fn get_control(&self) -> Control {
+        self.control.clone()
+    }
+
+    pub fn get_horarios(&self) -> &Vec<Horario<'a>> {
+        &self.horarios
+    }
+
+    pub fn get_ventanas(&self) -> &Vec<Ventana> {
+        &self.ventanas
+    }
+
+    pub fn get_zona_horaria(&self) -> i32 {
+        self.zona_horaria
+    }
+
+    pub fn get_tolerancia(&self) -> Milisegundos {
+        self.tolerancia
+    }
+
+    pub fn get_ubicacion(&self) -> Option<Ubicacion> {
+        self.ubicacion
+    }
+
+    pub fn get_ajuste(&self) -> Ajuste {
+        self.ajuste
+    }
+
+    /// Calcula la luz del día local que contiene el `instante`, si la agenda tiene ubicación.
+    pub fn get_luz(&self, instante: u64) -> Option<Luz> {
+        let medianoche = instante.saturating_sub(self.get_local(instante) % DIA);
+        self.luz_desde(medianoche)
+    }
+
+    /// Indica si se puede alimentar a la `hora` dada según las ventanas de la agenda.
+    pub fn is_en_ventana(&self, hora: Hora) -> bool {
+        self.ventanas.is_empty() || self.ventanas.iter().any(|v| v.contiene(hora))
+    }
+
+    /// Hora local del `instante`, en milisegundos desde la época Unix.
+    pub fn get_hora(&self, instante: u64) -> Hora {
+        Hora::desde_ms(self.get_local(instante))
+    }
+
+    /// Calcula la próxima ración agendada a partir de `desde`, incluido.
+    ///
+    /// # Retorna:
+    /// El instante en que corresponde y su índice en `get_horarios`, o `None` si la agenda está vacía.
+    pub fn get_siguiente(&self, desde: u64) -> Option<(u64, usize)> {
+        self.siguiente(desde, None)
+    }
+
+    /// Como `get_siguiente`, pero solo considera las raciones posteriores a `anterior`,
+    /// de modo que las agendadas a la misma hora se entreguen una tras otra.
+    fn siguiente(&self, desde: u64, anterior: Option<(u64, usize)>) -> Option<(u64, usize)> {
+        let local = self.get_local(desde);
+        let medianoche = desde.saturating_sub(local % DIA);
+
+        (0..2)
+            .flat_map(|dia| {
+                let medianoche = medianoche + dia * DIA;
+                let luz = self.luz_desde(medianoche);
+                self.horarios
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, horario)| (self.ajustar(medianoche, horario.hora, luz), i))
+            })
+            .find(|&(instante, i)| instante >= desde && Some((instante, i)) > anterior)
+    }
+
+    /// Ejecuta las raciones agendadas hasta el instante `hasta`, bloqueando el hilo actual.
+    ///
+    /// Para una agenda sin término se usa `u64::MAX`.
+    ///
+    /// # Retorna:
+    /// Un `RegistroAgenda` por cada ración cuya hora llegó, incluidas las que no se ejecutaron.
+    pub fn iniciar<S, D, A>(
+        &self,
+        hasta: u64,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+    ) -> Vec<RegistroAgenda>
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+    {
+        bloquear(self.iniciar_async(hasta, soplador, doser, silo))
+    }
+
+    /// Igual que `iniciar`, pero las raciones con salida se entregan a través de `selectora`.
+    pub fn iniciar_en<S, D, A, L>(
+        &self,
+        hasta: u64,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        selectora: &mut L,
+    ) -> Vec<RegistroAgenda>
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        bloquear(self.iniciar_en_async(hasta, soplador, doser, silo, selectora))
+    }
+
+    /// Versión asíncrona de `iniciar`.
+    pub async fn iniciar_async<S, D, A>(
+        &self,
+        hasta: u64,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+    ) -> Vec<RegistroAgenda>
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+    {
+        self.correr(hasta, soplador, doser, silo, None::<&mut Selectora>)
+            .await
+    }
+
+    /// Versión asíncrona de `iniciar_en`.
+    pub async fn iniciar_en_async<S, D, A, L>(
+        &self,
+        hasta: u64,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        selectora: &mut L,
+    ) -> Vec<RegistroAgenda>
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        self.correr(hasta, soplador, doser, silo, Some(selectora))
+            .await
+    }
+
+    async fn correr<S, D, A, L>(
+        &self,
+        hasta: u64,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        mut selectora: Option<&mut L>,
+    ) -> Vec<RegistroAgenda>
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        let mut registros = Vec::new();
+        let mut desde = self.reloj.ahora();
+        let mut anterior = None;
+
+        while let Some((programado, i)) =
+            self.siguiente(desde, anterior).filter(|(t, _)| *t <= hasta)
+        {
+            self.esperar_hasta(programado).await;
+
+            let horario = &self.horarios[i];
+            let atraso = self.reloj.ahora().saturating_sub(programado);
+            let de_dia = self
+                .get_luz(programado)
+                .is_none_or(|luz| luz.contiene(programado));
+            let resultado = if !de_dia || !self.is_en_ventana(self.get_hora(programado)) {
+                eventos::emitir(Evento::new(
+                    Severidad::Info,
+                    "Agenda",
+                    format!("{}: Fuera de la ventana de alimentación", horario.hora),
+                ));
+                Resultado::FueraDeVentana
+            } else if atraso > u64::from(self.tolerancia.0) {
+                eventos::emitir(Evento::new(
+                    Severidad::Aviso,
+                    "Agenda",
+                    format!("{}: Omitida, atraso de {}ms", horario.hora, atraso),
+                ));
+                Resultado::Atrasado
+            } else {
+                eventos::emitir(Evento::new(
+                    Severidad::Info,
+                    "Agenda",
+                    format!("{}: Iniciando ración", horario.hora),
+                ));
+                let mut programa = Programa::new(horario.racion);
+                programa
+                    .set_reloj(self.reloj.clone())
+                    .set_monitor(self.monitor.clone())
+                    .set_control(self.control.clone());
+                let reporte = match selectora.as_deref_mut() {
+                    Some(selectora) => {
+                        programa
+                            .iniciar_en_async(soplador, doser, silo, selectora)
+                            .await
+                    }
+                    None => programa.iniciar_async(soplador, doser, silo).await,
+                };
+                Resultado::Ejecutado(reporte)
+            };
+
+            registros.push(RegistroAgenda {
+                hora: horario.hora,
+                programado,
+                resultado,
+            });
+            desde = programado;
+            anterior = Some((programado, i));
+        }
+
+        registros
+    }
+
+    /// Espera hasta el `instante` según el reloj de la agenda.
+    async fn esperar_hasta(&self, instante: u64) {
+        loop {
+            let restante = instante.saturating_sub(self.reloj.ahora());
+            if restante == 0 {
+                return;
+            }
+            self.reloj
+                .esperar(restante.min(u64::from(u32::MAX)) as u32)
+                .await;
+        }
+    }
+
+    /// Luz del día local que comienza en `medianoche`, calculada al mediodía.
+    fn luz_desde(&self, medianoche: u64) -> Option<Luz> {
+        self.ubicacion
+            .map(|ubicacion| ubicacion.get_luz(medianoche + DIA / 2))
+    }
+
+    /// Instante en que corresponde la `hora` del día que comienza en `medianoche`, según el `Ajuste`.
+    fn ajustar(&self, medianoche: u64, hora: Hora, luz: Option<Luz>) -> u64 {
+        let nominal = medianoche + u64::from(hora.get_ms());
+        let Some(Luz::Dia {
+            amanecer,
+            atardecer,
+        }) = luz
+        else {
+            return nominal;
+        };
+
+        match self.ajuste {
+            Ajuste::Omitir => nominal,
+            Ajuste::Recortar => nominal.clamp(amanecer, atardecer),
+            Ajuste::Escalar(referencia) => {
+                let inicio = referencia.get_inicio().get_ms();
+                let largo = u64::from(referencia.get_termino().get_ms() - inicio);
+                let avance = u64::from(
+                    hora.get_ms()
+                        .clamp(inicio, referencia.get_termino().get_ms())
+                        - inicio,
+                );
+                amanecer + (atardecer - amanecer) * avance / largo
+            }
+        }
+    }
+
+    /// Milisegundos desde la época Unix corridos a la zona horaria de la agenda.
+    fn get_local(&self, instante: u64) -> u64 {
+        instante.saturating_add_signed(i64::from(self.zona_horaria) * i64::from(MINUTO))
+    }
+}
+
+impl Default for Agenda<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
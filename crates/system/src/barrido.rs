@@ -0,0 +1,191 @@
+use types::rango::{Rango, RangoError};
+use types::unidades::{Metros, MetrosPorSegundo, Milisegundos, Porcentaje};
+
+use crate::errors::BarridoError;
+
+pub const POTENCIA_MIN: Porcentaje = Porcentaje(1);
+pub const POTENCIA_MAX: Porcentaje = Porcentaje(100);
+
+/// Largo de la línea.
+pub const LARGO_MIN: Metros = Metros(1);
+pub const LARGO_MAX: Metros = Metros(3000);
+
+/// Velocidad del pellet en la línea.
+pub const VELOCIDAD_MIN: MetrosPorSegundo = MetrosPorSegundo(5);
+pub const VELOCIDAD_MAX: MetrosPorSegundo = MetrosPorSegundo(40);
+pub const VELOCIDAD_INICIAL: MetrosPorSegundo = MetrosPorSegundo(20);
+
+/// Porcentaje de tiempo adicional sobre el tránsito teórico.
+pub const MARGEN_MIN: u32 = 0;
+pub const MARGEN_MAX: u32 = 300;
+pub const MARGEN_INICIAL: u32 = 50;
+
+const TAG: &str = "[Barrido]";
+
+/// Fase de barrido de la línea que se ejecuta al terminar un ciclo.
+///
+/// Con el dosificador cerrado, mantiene el soplador a `potencia` el tiempo necesario
+/// para que el último pellet recorra la línea, de modo que no quede alimento en la
+/// tubería al cambiar de salida o al apagar el soplador.
+///
+/// La duración es `largo / velocidad` más un `margen` porcentual.
+///
+/// # Ejemplo:
+///! ```
+///! let mut barrido = Barrido::new();
+///! barrido.set_largo(Metros(400)).unwrap();
+///! barrido.set_velocidad(MetrosPorSegundo(20)).unwrap();
+///! barrido.set_margen(50).unwrap();      // 50% adicional
+///! assert_eq!(barrido.get_duracion(), Milisegundos(30000));
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone)]
+pub struct Barrido {
+    /// Potencia del soplador durante el barrido.
+    potencia: Rango<Porcentaje>,
+
+    /// Largo de la línea.
+    largo: Rango<Metros>,
+
+    /// Velocidad del pellet en la línea.
+    velocidad: Rango<MetrosPorSegundo>,
+
+    /// Porcentaje de tiempo adicional sobre el tránsito teórico.
+    margen: Rango,
+}
+
+impl Barrido {
+    /// Crea un barrido a potencia máxima para una línea de `LARGO_MIN` metros.
+    pub fn new() -> Self {
+        Self {
+            potencia: Rango::new(POTENCIA_MIN, POTENCIA_MAX, POTENCIA_MAX).unwrap(),
+            largo: Rango::new(LARGO_MIN, LARGO_MAX, LARGO_MIN).unwrap(),
+            velocidad: Rango::new(VELOCIDAD_MIN, VELOCIDAD_MAX, VELOCIDAD_INICIAL).unwrap(),
+            margen: Rango::new(MARGEN_MIN, MARGEN_MAX, MARGEN_INICIAL).unwrap(),
+        }
+    }
+}
+
+impl Default for Barrido {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Barrido {
+    pub fn set_potencia(&mut self, n: Porcentaje) -> Result<(), BarridoError> {
+        match self.potencia.set(n, TAG) {
+            Ok(()) => Ok(()),
+            Err(RangoError::FueraDeRango) => Err(BarridoError::PotenciaFueraDeRango),
+        }
+    }
+
+    pub fn set_largo(&mut self, n: Metros) -> Result<(), BarridoError> {
+        match self.largo.set(n, TAG) {
+            Ok(()) => Ok(()),
+            Err(RangoError::FueraDeRango) => Err(BarridoError::LargoFueraDeRango),
+        }
+    }
+
+    pub fn set_velocidad(&mut self, n: MetrosPorSegundo) -> Result<(), BarridoError> {
+        match self.velocidad.set(n, TAG) {
+            Ok(()) => Ok(()),
+            Err(RangoError::FueraDeRango) => Err(BarridoError::VelocidadFueraDeRango),
+        }
+    }
+
+    pub fn set_margen(&mut self, n: u32) -> Result<(), BarridoError> {
+        match self.margen.set(n, TAG) {
+            Ok(()) => Ok(()),
+            Err(RangoError::FueraDeRango) => Err(BarridoError::MargenFueraDeRango),
+        }
+    }
+
+    pub fn get_potencia(&self) -> Porcentaje {
+        self.potencia.get()
+    }
+
+    pub fn get_largo(&self) -> Metros {
+        self.largo.get()
+    }
+
+    pub fn get_velocidad(&self) -> MetrosPorSegundo {
+        self.velocidad.get()
+    }
+
+    pub fn get_margen(&self) -> u32 {
+        self.margen.get()
+    }
+
+    /// Calcula la duración del barrido.
+    pub fn get_duracion(&self) -> Milisegundos {
+        let transito = self.get_largo() / self.get_velocidad();
+        Milisegundos((u64::from(transito.0) * u64::from(100 + self.get_margen()) / 100) as u32)
+    }
+}
+
+/// Un `Barrido` se serializa con sus valores y se deserializa con sus setters, por lo
+/// que un valor fuera de rango impide cargarlo.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use std::fmt::Display;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Barrido", deny_unknown_fields)]
+    struct Datos {
+        potencia: Porcentaje,
+        largo: Metros,
+        velocidad: MetrosPorSegundo,
+        margen: u32,
+    }
+
+    impl Serialize for Barrido {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                potencia: self.get_potencia(),
+                largo: self.get_largo(),
+                velocidad: self.get_velocidad(),
+                margen: self.get_margen(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Barrido {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let fuera_de_rango =
+                |campo: &str, n: &dyn Display, min: &dyn Display, max: &dyn Display| {
+                    D::Error::custom(format!(
+                        "{} {} fuera de rango ({}..={})",
+                        campo, n, min, max
+                    ))
+                };
+
+            let mut barrido = Barrido::new();
+            barrido.set_potencia(datos.potencia).map_err(|_| {
+                fuera_de_rango("potencia", &datos.potencia, &POTENCIA_MIN, &POTENCIA_MAX)
+            })?;
+            barrido
+                .set_largo(datos.largo)
+                .map_err(|_| fuera_de_rango("largo", &datos.largo, &LARGO_MIN, &LARGO_MAX))?;
+            barrido.set_velocidad(datos.velocidad).map_err(|_| {
+                fuera_de_rango(
+                    "velocidad",
+                    &datos.velocidad,
+                    &VELOCIDAD_MIN,
+                    &VELOCIDAD_MAX,
+                )
+            })?;
+            barrido
+                .set_margen(datos.margen)
+                .map_err(|_| fuera_de_rango("margen", &datos.margen, &MARGEN_MIN, &MARGEN_MAX))?;
+            Ok(barrido)
+        }
+    }
+}
@@ -1,40 +1,60 @@
+use hardware::traits::Dosificar;
+
+use crate::barrido::Barrido;
+use crate::errors::CicloError;
+
 use types::rango::{Rango, RangoData};
+use types::unidades::{Gramos, GramosPorSegundo, Milisegundos};
 use uuid::Uuid;
 
-pub const ESPERA_MIN: u32 = 1000;
-pub const ESPERA_MAX: u32 = 20000;
+pub const ESPERA_MIN: Milisegundos = Milisegundos(1000);
+pub const ESPERA_MAX: Milisegundos = Milisegundos(20000);
 
 pub const PULSOS_MIN: u32 = 0;
 pub const PULSOS_MAX: u32 = 10000;
 
-pub const DURACION_MIN: u32 = 1000;
-pub const DURACION_MAX: u32 = 10000;
+pub const DURACION_MIN: Milisegundos = Milisegundos(1000);
+pub const DURACION_MAX: Milisegundos = Milisegundos(10000);
 
 pub struct CicloData {
     pub pulsos: RangoData,
-    pub duracion: RangoData,
-    pub espera: RangoData,
+    pub duracion: RangoData<Milisegundos>,
+    pub espera: RangoData<Milisegundos>,
 }
 
-const TAG: &str = "[Ciclo]";
+const ORIGEN: &str = "Ciclo";
 
 /// Representa una **Ración**, que contiene parámetros para la duración y el comportamiento de los pulsos.
 ///
 /// La estructura `Ciclo` tiene los siguientes campos:
-/// - **pulso_duracion**: La duración de cada pulso en milisegundos. No debería durar más de un minuto en producción.
+/// - **pulso_duracion**: La duración de cada pulso. No debería durar más de un minuto en producción.
 /// - **pulsos**: El número total de pulsos por ración.
-/// - **pulso_espera**: El tiempo entre cada pulso en milisegundos.
+/// - **pulso_espera**: El tiempo entre cada pulso.
+/// - **pulso_purga**: Si al final de los pulsos se entrega un pulso adicional de purga.
+/// - **salida**: La salida de la selectora a la que se dirige el ciclo, si tiene una.
+/// - **barrido**: El barrido de la línea al terminar el ciclo, si tiene uno.
 /// - **id**: Un identificador único para cada instancia de la ración.
+///
+/// Un ciclo entrega exactamente `pulsos` pulsos, más uno si `pulso_purga` está activo.
 #[derive(PartialEq, Debug)]
 pub struct Ciclo {
-    /// Duración de cada pulso en milisegundos. No debe ser mayor a un minuto en producción.
-    pulso_duracion: Rango,
+    /// Duración de cada pulso. No debe ser mayor a un minuto en producción.
+    pulso_duracion: Rango<Milisegundos>,
 
     /// Número total de pulsos por ración.
     pulsos: Rango,
 
-    /// Tiempo de espera entre pulsos en milisegundos.
-    pulso_espera: Rango,
+    /// Tiempo de espera entre pulsos.
+    pulso_espera: Rango<Milisegundos>,
+
+    /// Indica si se entrega un pulso adicional de purga al final del ciclo.
+    pulso_purga: bool,
+
+    /// Salida de la selectora a la que se entrega el ciclo. Si es `None` se usa la de la ración.
+    salida: Option<u32>,
+
+    /// Barrido de la línea al terminar el ciclo. Si es `None` se usa el de la ración.
+    barrido: Option<Barrido>,
 
     /// Identificador único de la ración.
     id: Uuid,
@@ -52,22 +72,133 @@ impl Ciclo {
     ///! let Ciclo = Ciclo::new();
     ///! assert_eq!(Ciclo.get_pulsos(), 0); // La cantidad de pulsos debería ser 0 por defecto.
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn new() -> Self {
         Self {
             pulso_duracion: Rango::new(DURACION_MIN, DURACION_MAX, DURACION_MIN).unwrap(),
             pulsos: Rango::new(PULSOS_MIN, PULSOS_MAX, PULSOS_MIN).unwrap(),
             pulso_espera: Rango::new(ESPERA_MIN, ESPERA_MAX, ESPERA_MIN).unwrap(),
+            pulso_purga: false,
+            salida: None,
+            barrido: None,
             id: Uuid::new_v4(),
         }
     }
 }
 
+impl Ciclo {
+    /// Crea un ciclo que entrega al menos `gramos` con el dosificador dado.
+    ///
+    /// Usa la menor cantidad de pulsos que permite `DURACION_MAX` y, para esa cantidad, la
+    /// menor duración que alcanza la masa, por lo que el exceso es de pocos gramos por pulso.
+    /// Si se indica `caudal_max` (promediando pulsos y esperas) se alarga la espera
+    /// entre pulsos y, si no alcanza con `ESPERA_MAX`, se reparte la masa en más pulsos.
+    ///
+    /// # Errores:
+    /// - `CicloError::MasaFueraDeRango`: La masa no se puede entregar dentro de los límites
+    ///   de pulsos y duración, por ejemplo si es menor a un pulso de `DURACION_MIN`.
+    /// - `CicloError::CaudalFueraDeRango`: El caudal es 0 o no se puede respetar ni con la
+    ///   espera máxima.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+    ///! let ciclo = Ciclo::por_masa(Gramos(125000), &doser, None).unwrap();
+    ///! assert_eq!(ciclo.get_pulsos(), 7);          // 7 pulsos de 8929 ms
+    ///! assert!(ciclo.calcular_entrega(&doser).unwrap() >= Gramos(125000));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn por_masa<D: Dosificar>(
+        gramos: Gramos,
+        doser: &D,
+        caudal_max: Option<GramosPorSegundo>,
+    ) -> Result<Ciclo, CicloError> {
+        if caudal_max == Some(GramosPorSegundo(0)) {
+            return Err(CicloError::CaudalFueraDeRango);
+        }
+
+        let mut ciclo = Ciclo::new();
+        if gramos == Gramos(0) {
+            return Ok(ciclo);
+        }
+
+        let pulso_max = doser.calcular_gramos(DURACION_MAX);
+        if pulso_max == Gramos(0) || gramos < doser.calcular_gramos(DURACION_MIN) {
+            return Err(CicloError::MasaFueraDeRango);
+        }
+
+        let desde = gramos.0.div_ceil(pulso_max.0).max(1);
+        for pulsos in desde..=PULSOS_MAX {
+            let objetivo = Gramos(gramos.0.div_ceil(pulsos));
+            let duracion = match duracion_minima(doser, objetivo) {
+                Some(duracion) => duracion,
+                // Con más pulsos cada uno es más corto y termina bajo DURACION_MIN
+                None => break,
+            };
+
+            let espera = match caudal_max {
+                None => ESPERA_MIN,
+                Some(caudal) => {
+                    let periodo =
+                        u64::from(doser.calcular_gramos(duracion).0) * 1000 / u64::from(caudal.0);
+                    let espera = periodo
+                        .saturating_sub(u64::from(duracion.0))
+                        .max(u64::from(ESPERA_MIN.0))
+                        .min(u64::from(u32::MAX)) as u32;
+                    Milisegundos(espera)
+                }
+            };
+            if espera > ESPERA_MAX {
+                continue;
+            }
+
+            ciclo.set_pulsos(pulsos)?;
+            ciclo.set_pulso_duracion(duracion)?;
+            ciclo.set_pulso_espera(espera)?;
+            return Ok(ciclo);
+        }
+
+        match caudal_max {
+            Some(_) => Err(CicloError::CaudalFueraDeRango),
+            None => Err(CicloError::MasaFueraDeRango),
+        }
+    }
+}
+
+/// Menor duración dentro de los límites con la que un pulso entrega al menos `gramos`.
+fn duracion_minima<D: Dosificar>(doser: &D, gramos: Gramos) -> Option<Milisegundos> {
+    if doser.calcular_gramos(DURACION_MIN) > gramos {
+        return None;
+    }
+    if doser.calcular_gramos(DURACION_MAX) < gramos {
+        return None;
+    }
+
+    // Búsqueda binaria: la entrega crece con la duración
+    let (mut bajo, mut alto) = (DURACION_MIN.0, DURACION_MAX.0);
+    while bajo < alto {
+        let medio = bajo + (alto - bajo) / 2;
+        if doser.calcular_gramos(Milisegundos(medio)) >= gramos {
+            alto = medio;
+        } else {
+            bajo = medio + 1;
+        }
+    }
+    Some(Milisegundos(bajo))
+}
+
+impl Default for Ciclo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Implementación de los métodos getter y setter para la estructura `Ciclo`.
 impl Ciclo {
     /// Establece la duración de cada pulso en milisegundos.
     ///
     /// # Parámetros:
-    /// - `n`: La duración del pulso en milisegundos.
+    /// - `n`: La duración del pulso.
     ///
     /// # Retorna:
     /// Una referencia mutable al objeto `Ciclo` para permitir el encadenamiento de llamadas (method chaining).
@@ -75,11 +206,15 @@ impl Ciclo {
     /// # Ejemplo:
     ///! ```
     ///! let mut Ciclo = Ciclo::new();
-    ///! Ciclo.set_pulso_duracion(500); // Establece la duración del pulso a 500 ms.
-    ///! assert_eq!(Ciclo.get_pulso_duracion(), 500); // Verifica que la duración del pulso sea 500 ms.
+    ///! Ciclo.set_pulso_duracion(Milisegundos(5000)); // Establece la duración del pulso a 5 s.
+    ///! assert_eq!(Ciclo.get_pulso_duracion(), Milisegundos(5000));
     ///! ```
-    pub fn set_pulso_duracion(&mut self, n: u32) -> Result<(), crate::errors::CicloError> {
-        match self.pulso_duracion.set(n, TAG) {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_pulso_duracion(&mut self, n: Milisegundos) -> Result<(), crate::errors::CicloError> {
+        match self
+            .pulso_duracion
+            .set_campo(n, ORIGEN, self.id, "pulso_duracion")
+        {
             Ok(()) => Ok(()),
             Err(types::rango::RangoError::FueraDeRango) => {
                 Err(crate::errors::CicloError::DuracionFueraDeRango)
@@ -101,8 +236,9 @@ impl Ciclo {
     ///! Ciclo.set_pulsos(10); // Establece 10 pulsos por ración.
     ///! assert_eq!(Ciclo.get_pulsos(), 10); // Verifica que el número de pulsos sea 10.
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn set_pulsos(&mut self, n: u32) -> Result<(), crate::errors::CicloError> {
-        match self.pulsos.set(n, TAG) {
+        match self.pulsos.set_campo(n, ORIGEN, self.id, "pulsos") {
             Ok(()) => Ok(()),
             Err(types::rango::RangoError::FueraDeRango) => {
                 Err(crate::errors::CicloError::CantidadFueraDeRango)
@@ -110,10 +246,10 @@ impl Ciclo {
         }
     }
 
-    /// Establece el tiempo de espera entre pulsos en milisegundos.
+    /// Establece el tiempo de espera entre pulsos.
     ///
     /// # Parámetros:
-    /// - `n`: El tiempo de espera entre pulsos en milisegundos.
+    /// - `n`: El tiempo de espera entre pulsos.
     ///
     /// # Retorna:
     /// Una referencia mutable al objeto `Ciclo` para permitir el encadenamiento de llamadas.
@@ -121,11 +257,15 @@ impl Ciclo {
     /// # Ejemplo:
     ///! ```
     ///! let mut Ciclo = Ciclo::new();
-    ///! Ciclo.set_pulso_espera(100); // Establece el tiempo de espera entre pulsos a 100 ms.
-    ///! assert_eq!(Ciclo.get_pulso_espera(), 100); // Verifica que el tiempo de espera sea 100 ms.
+    ///! Ciclo.set_pulso_espera(Milisegundos(2000)); // Establece el tiempo de espera entre pulsos a 2 s.
+    ///! assert_eq!(Ciclo.get_pulso_espera(), Milisegundos(2000));
     ///! ```
-    pub fn set_pulso_espera(&mut self, n: u32) -> Result<(), crate::errors::CicloError> {
-        match self.pulso_espera.set(n, TAG) {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_pulso_espera(&mut self, n: Milisegundos) -> Result<(), crate::errors::CicloError> {
+        match self
+            .pulso_espera
+            .set_campo(n, ORIGEN, self.id, "pulso_espera")
+        {
             Ok(()) => Ok(()),
             Err(types::rango::RangoError::FueraDeRango) => {
                 Err(crate::errors::CicloError::EsperaFueraDeRango)
@@ -133,6 +273,65 @@ impl Ciclo {
         }
     }
 
+    /// Activa o desactiva el pulso de purga al final del ciclo.
+    ///
+    /// El pulso de purga tiene la misma duración que los demás y se entrega después
+    /// de los `pulsos` configurados.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut ciclo = Ciclo::new();
+    ///! ciclo.set_pulsos(50).unwrap();
+    ///! ciclo.set_pulso_purga(true);
+    ///! assert_eq!(ciclo.get_total_pulsos(), 51);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_pulso_purga(&mut self, n: bool) -> &mut Ciclo {
+        self.pulso_purga = n;
+        self
+    }
+
+    /// Establece la salida de la selectora a la que se entrega el ciclo.
+    ///
+    /// Con `None` el ciclo se entrega en la salida de la ración, o donde esté la selectora
+    /// si la ración tampoco tiene una.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut ciclo = Ciclo::new();
+    ///! ciclo.set_salida(Some(3)); // Jaula conectada a la salida 3
+    ///! assert_eq!(ciclo.get_salida(), Some(3));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_salida(&mut self, n: Option<u32>) -> &mut Ciclo {
+        self.salida = n;
+        self
+    }
+
+    /// Obtiene la salida de la selectora del ciclo, si tiene una.
+    pub fn get_salida(&self) -> Option<u32> {
+        self.salida
+    }
+
+    /// Establece el barrido de la línea que se ejecuta al terminar el ciclo.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut barrido = Barrido::new();
+    ///! barrido.set_largo(400).unwrap();
+    ///! ciclo.set_barrido(Some(barrido));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_barrido(&mut self, n: Option<Barrido>) -> &mut Ciclo {
+        self.barrido = n;
+        self
+    }
+
+    /// Obtiene el barrido del ciclo, si tiene uno.
+    pub fn get_barrido(&self) -> Option<&Barrido> {
+        self.barrido.as_ref()
+    }
+
     /// Obtiene el número total de pulsos por ración.
     ///
     /// # Retorna:
@@ -143,35 +342,73 @@ impl Ciclo {
     ///! let Ciclo = Ciclo::new();
     ///! assert_eq!(Ciclo.get_pulsos(), 0); // Debería ser 0 por defecto.
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_pulsos(&self) -> u32 {
         self.pulsos.get()
     }
 
-    /// Obtiene el tiempo de espera entre pulsos en milisegundos.
+    /// Indica si el ciclo entrega un pulso de purga al final.
+    pub fn get_pulso_purga(&self) -> bool {
+        self.pulso_purga
+    }
+
+    /// Obtiene la cantidad de pulsos que entrega el ciclo, incluido el pulso de purga.
     ///
-    /// # Retorna:
-    /// El tiempo de espera entre pulsos (de tipo `u32`).
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut ciclo = Ciclo::new();
+    ///! ciclo.set_pulsos(50).unwrap();
+    ///! assert_eq!(ciclo.get_total_pulsos(), 50);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_total_pulsos(&self) -> u32 {
+        self.get_pulsos() + u32::from(self.pulso_purga)
+    }
+
+    /// Calcula los gramos que entrega el ciclo completo con el dosificador dado.
+    ///
+    /// # Errores:
+    /// - `CicloError::EntregaDesbordada`: La entrega supera `u32::MAX` gramos, por ejemplo
+    ///   con `PULSOS_MAX` pulsos de `DURACION_MAX` y un dosificador de gran caudal.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut ciclo = Ciclo::new();
+    ///! ciclo.set_pulsos(50).unwrap();
+    ///! ciclo.set_pulso_duracion(Milisegundos(5500)).unwrap();
+    ///! let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+    ///! assert_eq!(ciclo.calcular_entrega(&doser), Ok(Gramos(550000))); // 50 pulsos de 11 kg
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn calcular_entrega<D: Dosificar>(&self, doser: &D) -> Result<Gramos, CicloError> {
+        let pulso = doser.calcular_gramos(self.get_pulso_duracion());
+        let total = u64::from(self.get_total_pulsos()) * u64::from(pulso.0);
+        u32::try_from(total)
+            .map(Gramos)
+            .map_err(|_| CicloError::EntregaDesbordada)
+    }
+
+    /// Obtiene el tiempo de espera entre pulsos.
     ///
     /// # Ejemplo:
     ///! ```
     ///! let Ciclo = Ciclo::new();
-    ///! assert_eq!(Ciclo.get_pulso_espera(), 0); // Debería ser 0 por defecto.
+    ///! assert_eq!(Ciclo.get_pulso_espera(), ESPERA_MIN);
     ///! ```
-    pub fn get_pulso_espera(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_pulso_espera(&self) -> Milisegundos {
         self.pulso_espera.get()
     }
 
-    /// Obtiene la duración de cada pulso en milisegundos.
-    ///
-    /// # Retorna:
-    /// La duración del pulso en milisegundos (de tipo `u32`).
+    /// Obtiene la duración de cada pulso.
     ///
     /// # Ejemplo:
     ///! ```
     ///! let Ciclo = Ciclo::new();
-    ///! assert_eq!(Ciclo.get_pulso_duracion(), 0); // Debería ser 0 por defecto.
+    ///! assert_eq!(Ciclo.get_pulso_duracion(), DURACION_MIN);
     ///! ```
-    pub fn get_pulso_duracion(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_pulso_duracion(&self) -> Milisegundos {
         self.pulso_duracion.get()
     }
 
@@ -188,6 +425,7 @@ impl Ciclo {
     ///! let Ciclo = Ciclo::new();
     ///! let parametros = Ciclo.get_all();
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_all(&self) -> CicloData {
         CicloData {
             pulsos: self.pulsos.get_rango(),
@@ -207,7 +445,93 @@ impl Ciclo {
     ///! let id = Ciclo.get_id();
     ///! println!("El ID de la ración es: {}", id);
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_id(&self) -> Uuid {
         self.id
     }
 }
+
+/// Un `Ciclo` se serializa con sus valores y se deserializa con sus setters, por lo
+/// que un valor fuera de rango impide cargarlo en lugar de saltarse `Rango::set`.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use std::fmt::Display;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Ciclo", deny_unknown_fields)]
+    struct Datos {
+        pulsos: u32,
+        pulso_duracion: Milisegundos,
+        pulso_espera: Milisegundos,
+        #[serde(default)]
+        pulso_purga: bool,
+        #[serde(default)]
+        salida: Option<u32>,
+        #[serde(default)]
+        barrido: Option<Barrido>,
+        #[serde(default = "Uuid::new_v4")]
+        id: Uuid,
+    }
+
+    impl Serialize for Ciclo {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                pulsos: self.get_pulsos(),
+                pulso_duracion: self.get_pulso_duracion(),
+                pulso_espera: self.get_pulso_espera(),
+                pulso_purga: self.pulso_purga,
+                salida: self.salida,
+                barrido: self.barrido.clone(),
+                id: self.id,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Ciclo {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let fuera_de_rango =
+                |campo: &str, n: &dyn Display, min: &dyn Display, max: &dyn Display| {
+                    D::Error::custom(format!(
+                        "{} {} fuera de rango ({}..={})",
+                        campo, n, min, max
+                    ))
+                };
+
+            let mut ciclo = Ciclo::new();
+            ciclo
+                .set_pulsos(datos.pulsos)
+                .map_err(|_| fuera_de_rango("pulsos", &datos.pulsos, &PULSOS_MIN, &PULSOS_MAX))?;
+            ciclo
+                .set_pulso_duracion(datos.pulso_duracion)
+                .map_err(|_| {
+                    fuera_de_rango(
+                        "pulso_duracion",
+                        &datos.pulso_duracion,
+                        &DURACION_MIN,
+                        &DURACION_MAX,
+                    )
+                })?;
+            ciclo.set_pulso_espera(datos.pulso_espera).map_err(|_| {
+                fuera_de_rango(
+                    "pulso_espera",
+                    &datos.pulso_espera,
+                    &ESPERA_MIN,
+                    &ESPERA_MAX,
+                )
+            })?;
+            ciclo
+                .set_pulso_purga(datos.pulso_purga)
+                .set_salida(datos.salida)
+                .set_barrido(datos.barrido);
+            ciclo.id = datos.id;
+            Ok(ciclo)
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::{Arc, Mutex};
+
+use types::unidades::Milisegundos;
+
+/// Intervalo máximo entre dos revisiones del `Control` durante una espera.
+pub const PASO_CONTROL: Milisegundos = Milisegundos(1000);
+
+/// Orden vigente para un `Programa` en ejecución.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Orden {
+    /// Ejecución normal.
+    Continuar,
+    /// Retiene el programa antes del siguiente pulso hasta que se reanude.
+    Pausar,
+    /// Termina el pulso en curso y detiene el programa de forma ordenada.
+    Abortar,
+    /// Detiene el programa de inmediato, incluso a mitad de un pulso.
+    ParadaEmergencia,
+}
+
+/// Motivo por el que terminó la ejecución de un `Programa`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Fin {
+    /// Se entregaron todos los ciclos de la ración.
+    Completado,
+    /// Se detuvo con `Control::abortar`.
+    Abortado,
+    /// Se detuvo con `Control::parada_emergencia`.
+    ParadaEmergencia,
+    /// El silo se quedó sin alimento antes de completar la ración.
+    SinAlimento,
+    /// El silo rechazó una entrega por un error distinto a la falta de alimento.
+    ErrorSilo,
+    /// La selectora no se pudo llevar a la salida del ciclo.
+    ErrorSelectora,
+    /// El soplador no terminó su rampa de encendido a tiempo.
+    ErrorSoplador,
+}
+
+/// Manejador compartido para pausar, reanudar o detener un `Programa` en ejecución.
+///
+/// El programa revisa la orden entre pulsos y, durante las esperas, cada `PASO_CONTROL`
+/// milisegundos. Al detenerse siempre cierra primero el dosificador y luego apaga el soplador.
+///
+/// Las órdenes de detención se mantienen: un programa que comparta el control termina
+/// de inmediato hasta que se llame a `rearmar`.
+///
+/// # Ejemplo:
+///! ```
+///! let control = programa.get_control();
+///! // ... desde otra tarea o hilo:
+///! control.pausar();
+///! control.reanudar();
+///! control.parada_emergencia();
+///! control.rearmar(); // Listo para volver a ejecutar
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(Clone)]
+pub struct Control {
+    orden: Arc<Mutex<Orden>>,
+}
+
+impl Control {
+    pub fn new() -> Self {
+        Self {
+            orden: Arc::new(Mutex::new(Orden::Continuar)),
+        }
+    }
+
+    /// Retiene el programa antes del siguiente pulso, con el dosificador cerrado.
+    pub fn pausar(&self) {
+        self.set_orden(Orden::Pausar);
+    }
+
+    /// Reanuda un programa pausado. No tiene efecto si el programa ya se está deteniendo.
+    pub fn reanudar(&self) {
+        let mut orden = self.orden.lock().unwrap();
+        if *orden == Orden::Pausar {
+            *orden = Orden::Continuar;
+        }
+    }
+
+    /// Detiene el programa al terminar el pulso en curso.
+    pub fn abortar(&self) {
+        let mut orden = self.orden.lock().unwrap();
+        if *orden != Orden::ParadaEmergencia {
+            *orden = Orden::Abortar;
+        }
+    }
+
+    /// Detiene el programa de inmediato. Tiene prioridad sobre cualquier otra orden.
+    pub fn parada_emergencia(&self) {
+        self.set_orden(Orden::ParadaEmergencia);
+    }
+
+    /// Vuelve a `Orden::Continuar` tras abortar o una parada de emergencia, para que el
+    /// programa se pueda ejecutar otra vez. No se debe llamar con un programa en ejecución.
+    pub fn rearmar(&self) {
+        *self.orden.lock().unwrap() = Orden::Continuar;
+    }
+
+    /// Obtiene la orden vigente.
+    pub fn get_orden(&self) -> Orden {
+        *self.orden.lock().unwrap()
+    }
+
+    fn set_orden(&self, orden: Orden) {
+        let mut actual = self.orden.lock().unwrap();
+        if *actual != Orden::ParadaEmergencia {
+            *actual = orden;
+        }
+    }
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        Self::new()
+    }
+}
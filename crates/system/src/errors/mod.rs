@@ -3,9 +3,28 @@ pub enum CicloError {
     CantidadFueraDeRango,
     DuracionFueraDeRango,
     EsperaFueraDeRango,
+    MasaFueraDeRango,
+    CaudalFueraDeRango,
+    EntregaDesbordada,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum BarridoError {
+    PotenciaFueraDeRango,
+    LargoFueraDeRango,
+    VelocidadFueraDeRango,
+    MargenFueraDeRango,
 }
 
 #[derive(PartialEq, Debug)]
 pub enum RacionError {
     EsperaFueraDeRango,
 }
+
+#[derive(PartialEq, Debug)]
+pub enum AgendaError {
+    HoraInvalida,
+    VentanaInvalida,
+    ZonaHorariaInvalida,
+    UbicacionInvalida,
+}
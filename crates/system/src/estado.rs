@@ -0,0 +1,87 @@
+use std::sync::{Arc, Mutex};
+
+use types::unidades::Gramos;
+
+/// Fase en la que se encuentra un `Programa` en ejecución.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Fase {
+    /// El programa aún no ha comenzado.
+    Detenido,
+    /// La selectora se está moviendo a la salida del ciclo.
+    Seleccionando,
+    /// El soplador se está encendiendo.
+    Encendiendo,
+    /// El dosificador está abierto entregando un pulso.
+    Pulso,
+    /// El dosificador está cerrado esperando el siguiente pulso.
+    EsperaPulso,
+    /// El dosificador está cerrado y el soplador vacía la línea.
+    Barrido,
+    /// Espera entre un ciclo y el siguiente.
+    EsperaCiclo,
+    /// El programa está retenido por `Control::pausar`.
+    Pausado,
+    /// La ración terminó.
+    Finalizado,
+}
+
+/// Instantánea del estado de un `Programa`.
+///
+/// # Campos:
+/// - `fase`: La fase actual de la ejecución.
+/// - `ciclo`: Índice del ciclo en ejecución dentro de la ración (comienza en 0).
+/// - `pulso`: Índice del pulso en ejecución dentro del ciclo (comienza en 0).
+/// - `entregado`: Gramos entregados desde que comenzó la ración.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct EstadoPrograma {
+    pub fase: Fase,
+    pub ciclo: usize,
+    pub pulso: u32,
+    pub entregado: Gramos,
+}
+
+/// Manejador compartido para consultar el estado de un `Programa` mientras se ejecuta.
+///
+/// Se obtiene con `Programa::get_monitor` antes de iniciar y se puede clonar y enviar
+/// a otros hilos o tareas.
+///
+/// # Ejemplo:
+///! ```
+///! let monitor = programa.get_monitor();
+///! // ... en otra tarea mientras el programa corre:
+///! println!("Ciclo {} pulso {}", monitor.get().ciclo, monitor.get().pulso);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(Clone)]
+pub struct Monitor {
+    estado: Arc<Mutex<EstadoPrograma>>,
+}
+
+impl Monitor {
+    pub fn new() -> Self {
+        Self {
+            estado: Arc::new(Mutex::new(EstadoPrograma {
+                fase: Fase::Detenido,
+                ciclo: 0,
+                pulso: 0,
+                entregado: Gramos(0),
+            })),
+        }
+    }
+
+    /// Obtiene una copia del estado actual.
+    pub fn get(&self) -> EstadoPrograma {
+        *self.estado.lock().unwrap()
+    }
+
+    /// Modifica el estado compartido. Solo lo usa el programa en ejecución.
+    pub(crate) fn actualizar(&self, f: impl FnOnce(&mut EstadoPrograma)) {
+        f(&mut self.estado.lock().unwrap());
+    }
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
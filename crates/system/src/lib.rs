@@ -1,6 +1,13 @@
+pub mod agenda;
+pub mod barrido;
 pub mod ciclo;
+pub mod control;
+pub mod estado;
+pub mod plan;
 pub mod programa;
 pub mod racion;
+pub mod reporte;
+pub mod sol;
 
 pub mod errors;
 pub mod logs;
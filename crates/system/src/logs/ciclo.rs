@@ -1,25 +1,42 @@
 use crate::ciclo::Ciclo;
-use crate::logs::Print;
+use crate::logs::{Print, Registro};
 
 impl Print for Ciclo {
-    /// Imprime la información detallada de una ración.
-    ///
-    /// Muestra el `id`, los `pulsos`, la duración del pulso en milisegundos (`DP`),
+    /// Muestra los `pulsos`, la duración del pulso en milisegundos (`DP`),
     /// y el tiempo de espera entre pulsos (`EP`).
     ///
     /// # Ejemplo:
     ///! ```rust
     ///! let ciclo = Ciclo::new();
-    ///! ciclo.print();
+    ///! ciclo.print(); // [Ciclo][id]: [0 P][0ms DP][0ms EP]
     ///! ```
-    fn print(&self) -> &Ciclo {
-        println!(
-            "\n[Ración][{}][{} P][{}ms DP][{}ms EP]\n",
-            self.get_id(),
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
+        format!(
+            "[{} P][{} DP][{} EP]",
             self.get_pulsos(),
             self.get_pulso_duracion(),
             self.get_pulso_espera()
-        );
-        self
+        )
+    }
+
+    fn registro(&self) -> Registro {
+        let barrido = self.get_barrido().map(|barrido| {
+            Registro::new("Barrido")
+                .campo("potencia", barrido.get_potencia())
+                .campo("largo", barrido.get_largo())
+                .campo("velocidad", barrido.get_velocidad())
+                .campo("margen", barrido.get_margen())
+                .campo("duracion", barrido.get_duracion())
+        });
+
+        Registro::new("Ciclo")
+            .id(self.get_id())
+            .campo("pulsos", self.get_pulsos())
+            .campo("pulso_purga", self.get_pulso_purga())
+            .campo("pulso_duracion", self.get_pulso_duracion())
+            .campo("pulso_espera", self.get_pulso_espera())
+            .campo("salida", self.get_salida())
+            .campo("barrido", barrido)
     }
 }
@@ -1,11 +1,8 @@
 mod ciclo;
+mod programa;
 mod racion;
+mod reporte;
 
-/// El trait `Print` permite que un tipo implemente el método `print`
-/// para mostrar su estado de manera legible.
-///
-/// # Métodos:
-/// - `print`: Imprime la información relevante del tipo en la consola.
-pub trait Print {
-    fn print(&self) -> &Self;
-}
+/// El trait `Print` es el mismo para el hardware y el sistema; se define en
+/// `types::logs` junto al `Registro` que entrega cada componente.
+pub use types::logs::{Print, Registro, Valor};
@@ -0,0 +1,49 @@
+use crate::logs::{Print, Registro};
+use crate::programa::Programa;
+
+impl<'a> Print for Programa<'a> {
+    /// Muestra la cantidad de ciclos del programa y su avance según el `Monitor`.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let programa = Programa::new(&racion);
+    ///! programa.print(); // [Programa][id]: [3 ciclos][Detenido][Ciclo: 0][Pulso: 0][0g]
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
+        let estado = self.get_monitor().get();
+        format!(
+            "[{} ciclos][{:?}][Ciclo: {}][Pulso: {}][{}]",
+            self.get_pasos().len(),
+            estado.fase,
+            estado.ciclo,
+            estado.pulso,
+            estado.entregado
+        )
+    }
+
+    /// Incluye cada paso con el `id` de su ciclo, la salida y el descanso que le siguen.
+    fn registro(&self) -> Registro {
+        let estado = self.get_monitor().get();
+        let pasos = self
+            .get_pasos()
+            .iter()
+            .map(|paso| {
+                Registro::new("Paso")
+                    .campo("ciclo", paso.ciclo.get_id())
+                    .campo("salida", paso.salida)
+                    .campo("grupo", paso.grupo)
+                    .campo("descanso", paso.descanso)
+            })
+            .collect::<Vec<Registro>>();
+
+        Registro::new("Programa")
+            .id(self.get_id())
+            .campo("mantener_encendido", self.get_mantener_encendido())
+            .campo("fase", format!("{:?}", estado.fase))
+            .campo("ciclo", estado.ciclo)
+            .campo("pulso", estado.pulso)
+            .campo("entregado", estado.entregado)
+            .campo("pasos", pasos)
+    }
+}
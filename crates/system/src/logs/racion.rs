@@ -1,24 +1,38 @@
-use crate::logs::Print;
+use crate::logs::{Print, Registro};
 use crate::racion::Racion;
 
 impl<'a> Print for Racion<'a> {
-    /// Imprime los detalles de la racion, incluyendo su `id` y los `id`s de los ciclos asociados.
+    /// Muestra los `id`s de los ciclos de la ración.
     ///
     /// # Ejemplo:
     ///! ```rust
     ///! let racion = Racion::new(vec![&racion1, &racion2]);
-    ///! racion.print();
+    ///! racion.print(); // [Racion][id]: Ciclos <[id1, id2]>
     ///! ```
-    fn print(&self) -> &Racion<'a> {
-        println!(
-            "\n[Racion][{}]: Ciclos <[{}]>",
-            self.get_id(),
+    #[allow(clippy::suspicious_doc_comments)]
+    fn texto(&self) -> String {
+        format!(
+            "Ciclos <[{}]>",
             self.get_ciclos()
                 .iter()
-                .map(|racion| racion.get_id().to_string())
+                .map(|ciclo| ciclo.get_id().to_string())
                 .collect::<Vec<String>>()
                 .join(", ")
-        );
-        self
+        )
+    }
+
+    /// Incluye el registro de cada ciclo, en orden.
+    fn registro(&self) -> Registro {
+        Registro::new("Racion")
+            .id(self.get_id())
+            .campo("ciclo_espera", self.get_ciclo_espera())
+            .campo("salida", self.get_salida())
+            .campo(
+                "ciclos",
+                self.get_ciclos()
+                    .iter()
+                    .map(|ciclo| ciclo.registro())
+                    .collect::<Vec<Registro>>(),
+            )
     }
 }
@@ -0,0 +1,109 @@
+use types::eventos::{self, Evento, Severidad};
+
+use crate::logs::{Print, Registro};
+use crate::reporte::Reporte;
+
+impl Print for Reporte {
+    /// Muestra el motivo de término y lo entregado de lo solicitado.
+    fn texto(&self) -> String {
+        format!(
+            "[{:?}] {} de {} en {} ciclos",
+            self.fin,
+            self.entregado,
+            self.solicitado,
+            self.ciclos.len()
+        )
+    }
+
+    /// Incluye el detalle de cada ciclo y las alarmas levantadas.
+    fn registro(&self) -> Registro {
+        let ciclos = self
+            .ciclos
+            .iter()
+            .map(|ciclo| {
+                Registro::new("ReporteCiclo")
+                    .id(ciclo.ciclo)
+                    .campo("salida", ciclo.salida)
+                    .campo("pulsos", ciclo.pulsos.clone())
+                    .campo("entregado", ciclo.get_entregado())
+                    .campo("inicio", ciclo.inicio)
+                    .campo("termino", ciclo.termino)
+                    .campo("barrido", ciclo.barrido)
+            })
+            .collect::<Vec<Registro>>();
+
+        Registro::new("Reporte")
+            .id(self.racion)
+            .campo("fin", format!("{:?}", self.fin))
+            .campo("completo", self.is_completo())
+            .campo("solicitado", self.solicitado)
+            .campo("entregado", self.entregado)
+            .campo("inicio", self.inicio)
+            .campo("termino", self.termino)
+            .campo("silo_antes", self.silo_antes)
+            .campo("silo_despues", self.silo_despues)
+            .campo("soplador_encendido", self.soplador_encendido)
+            .campo("ciclos", ciclos)
+            .campo(
+                "alarmas",
+                self.alarmas
+                    .iter()
+                    .map(|alarma| format!("{:?}", alarma))
+                    .collect::<Vec<String>>(),
+            )
+    }
+
+    /// Emite el resumen del reporte como `Evento`s: entrega, niveles del silo, tiempos
+    /// y alarmas. Todos llevan el `id` de la ración y se retornan en líneas.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+    ///! reporte.print();
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    fn print(&self) -> String {
+        let mut lineas = Vec::new();
+        let mut evento = |severidad: Severidad, mensaje: String| {
+            let evento = Evento::new(severidad, "Reporte", mensaje).id(self.racion);
+            lineas.push(evento.to_string());
+            eventos::emitir(evento);
+        };
+
+        let severidad = if self.is_completo() {
+            Severidad::Info
+        } else {
+            Severidad::Aviso
+        };
+        evento(severidad, self.texto());
+        evento(
+            Severidad::Info,
+            format!(
+                "Silo {} -> {}, Duración {}, Soplador {}",
+                self.silo_antes,
+                self.silo_despues,
+                self.get_duracion(),
+                self.soplador_encendido
+            ),
+        );
+        for (i, ciclo) in self.ciclos.iter().enumerate() {
+            evento(
+                Severidad::Info,
+                format!(
+                    "[Ciclo: {}][{}][Salida: {}]: {} P, {}",
+                    i + 1,
+                    ciclo.ciclo,
+                    ciclo
+                        .salida
+                        .map_or_else(|| "-".to_string(), |s| s.to_string()),
+                    ciclo.get_pulsos(),
+                    ciclo.get_entregado()
+                ),
+            );
+        }
+        for alarma in &self.alarmas {
+            evento(Severidad::Aviso, format!("[Alarma]: {:?}", alarma));
+        }
+        lineas.join("\n")
+    }
+}
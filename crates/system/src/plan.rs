@@ -0,0 +1,239 @@
+use std::cmp::Reverse;
+
+use hardware::traits::{Almacenar, Dosificar, Seleccionar, Soplar};
+use uuid::Uuid;
+
+use crate::programa::{Paso, Programa};
+use crate::racion::Racion;
+use crate::reporte::Reporte;
+
+/// Forma en que un `Plan` reparte los ciclos entre las jaulas.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Modo {
+    /// Un ciclo por jaula en cada vuelta, hasta completar todas las raciones.
+    /// El descanso de cada jaula transcurre mientras se alimentan las demás.
+    Rotativo,
+    /// Cada jaula recibe su ración completa, de mayor a menor prioridad.
+    Prioridad,
+}
+
+/// Una jaula del plan: la salida de la selectora que la alimenta y su ración.
+///
+/// # Ejemplo:
+///! ```
+///! let mut jaula = Jaula::new("J-04", 3, &racion);
+///! jaula.set_prioridad(10);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct Jaula<'a> {
+    nombre: String,
+    salida: u32,
+    racion: &'a Racion<'a>,
+
+    /// Las jaulas con mayor prioridad se alimentan primero en `Modo::Prioridad`.
+    prioridad: u32,
+}
+
+impl<'a> Jaula<'a> {
+    pub fn new(nombre: &str, salida: u32, racion: &'a Racion<'a>) -> Self {
+        Self {
+            nombre: nombre.to_string(),
+            salida,
+            racion,
+            prioridad: 0,
+        }
+    }
+
+    pub fn set_prioridad(&mut self, n: u32) -> &mut Self {
+        self.prioridad = n;
+        self
+    }
+
+    pub fn get_nombre(&self) -> &str {
+        &self.nombre
+    }
+
+    pub fn get_salida(&self) -> u32 {
+        self.salida
+    }
+
+    pub fn get_racion(&self) -> &'a Racion<'a> {
+        self.racion
+    }
+
+    pub fn get_prioridad(&self) -> u32 {
+        self.prioridad
+    }
+}
+
+/// Un ciclo de la secuencia del plan: el índice de la jaula, el del ciclo dentro
+/// de su ración y la salida de la selectora.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Turno {
+    pub jaula: usize,
+    pub ciclo: usize,
+    pub salida: u32,
+}
+
+/// Plan de alimentación de varias jaulas desde un mismo soplador y una selectora.
+///
+/// Ordena los ciclos de todas las raciones para mover la selectora lo menos posible:
+/// recorre las salidas en orden ascendente a partir de la posición actual de la selectora
+/// y mantiene juntas las jaulas que comparten salida. Toda la secuencia se ejecuta como
+/// un único `Programa`, por lo que el soplador se enciende una sola vez.
+///
+/// # Ejemplo:
+///! ```
+///! let mut plan = Plan::new(Modo::Rotativo);
+///! plan.agregar(Jaula::new("J-01", 0, &racion_1));
+///! plan.agregar(Jaula::new("J-02", 1, &racion_2));
+///! let reporte = plan.iniciar(&mut soplador, &mut doser, &mut silo, &mut selectora);
+///! println!("J-02: {}g", reporte.get_entregado_en(1));
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct Plan<'a> {
+    jaulas: Vec<Jaula<'a>>,
+    modo: Modo,
+    id: Uuid,
+}
+
+impl<'a> Plan<'a> {
+    pub fn new(modo: Modo) -> Self {
+        Self {
+            jaulas: Vec::new(),
+            modo,
+            id: Uuid::new_v4(),
+        }
+    }
+
+    pub fn agregar(&mut self, jaula: Jaula<'a>) -> &mut Self {
+        self.jaulas.push(jaula);
+        self
+    }
+
+    pub fn set_modo(&mut self, modo: Modo) -> &mut Self {
+        self.modo = modo;
+        self
+    }
+
+    pub fn get_jaulas(&self) -> &Vec<Jaula<'a>> {
+        &self.jaulas
+    }
+
+    pub fn get_modo(&self) -> Modo {
+        self.modo
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Calcula el orden en que se entregan los ciclos partiendo con la selectora en `posicion`.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let turnos = plan.get_secuencia(selectora.get_posicion());
+    ///! println!("{} movimientos", Plan::contar_movimientos(&turnos, selectora.get_posicion()));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_secuencia(&self, posicion: u32) -> Vec<Turno> {
+        // Distancia en el recorrido ascendente desde la posición actual, volviendo a 0
+        let recorrido = |i: &usize| self.jaulas[*i].salida.wrapping_sub(posicion);
+
+        let mut orden: Vec<usize> = (0..self.jaulas.len()).collect();
+        let mut turnos = Vec::new();
+
+        match self.modo {
+            Modo::Rotativo => {
+                orden.sort_by_key(recorrido);
+                let vueltas = self
+                    .jaulas
+                    .iter()
+                    .map(|jaula| jaula.racion.get_ciclos().len())
+                    .max()
+                    .unwrap_or(0);
+
+                for ciclo in 0..vueltas {
+                    for &jaula in &orden {
+                        if ciclo < self.jaulas[jaula].racion.get_ciclos().len() {
+                            turnos.push(self.turno(jaula, ciclo));
+                        }
+                    }
+                }
+            }
+            Modo::Prioridad => {
+                orden.sort_by_key(|i| (Reverse(self.jaulas[*i].prioridad), recorrido(i)));
+                for jaula in orden {
+                    for ciclo in 0..self.jaulas[jaula].racion.get_ciclos().len() {
+                        turnos.push(self.turno(jaula, ciclo));
+                    }
+                }
+            }
+        }
+
+        turnos
+    }
+
+    /// Cuenta cuántas veces se mueve la selectora para recorrer `turnos` desde `posicion`.
+    pub fn contar_movimientos(turnos: &[Turno], posicion: u32) -> u32 {
+        let mut actual = posicion;
+        let mut movimientos = 0;
+        for turno in turnos {
+            if turno.salida != actual {
+                movimientos += 1;
+                actual = turno.salida;
+            }
+        }
+        movimientos
+    }
+
+    /// Crea el `Programa` que ejecuta el plan partiendo con la selectora en `posicion`.
+    ///
+    /// El `Reporte` resultante lleva el `id` del plan y la salida de cada ciclo, de modo
+    /// que lo entregado a cada jaula se obtiene con `Reporte::get_entregado_en`.
+    pub fn get_programa(&self, posicion: u32) -> Programa<'_> {
+        let pasos = self
+            .get_secuencia(posicion)
+            .into_iter()
+            .map(|turno| {
+                let racion = self.jaulas[turno.jaula].racion;
+                let ciclo = racion.get_ciclos()[turno.ciclo];
+                Paso {
+                    ciclo,
+                    salida: Some(turno.salida),
+                    barrido: racion.get_barrido_de(ciclo),
+                    grupo: turno.jaula,
+                    descanso: racion.get_ciclo_espera(),
+                }
+            })
+            .collect();
+        Programa::desde_pasos(self.id, pasos, false)
+    }
+
+    /// Ejecuta el plan completo con el reloj del sistema, moviendo `selectora` entre las jaulas.
+    pub fn iniciar<S, D, A, L>(
+        &self,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        selectora: &mut L,
+    ) -> Reporte
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        let posicion = selectora.get_posicion();
+        self.get_programa(posicion)
+            .iniciar_en(soplador, doser, silo, selectora)
+    }
+
+    fn turno(&self, jaula: usize, ciclo: usize) -> Turno {
+        Turno {
+            jaula,
+            ciclo,
+            salida: self.jaulas[jaula].salida,
+        }
+    }
+}
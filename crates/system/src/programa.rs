@@ -1,54 +1,839 @@
-use hardware::{dosificador::Dosificador, silo::Silo, soplador::Soplador};
+use std::collections::HashMap;
 
-use crate::logs::Print as SystemPrint;
+use hardware::selectora::Selectora;
+use hardware::traits::{Almacenar, Dosificar, Seleccionar, Soplar};
+
+use crate::barrido::Barrido;
+use crate::ciclo::Ciclo;
+use crate::control::{Control, Fin, Orden, PASO_CONTROL};
+use crate::estado::{Fase, Monitor};
+use crate::logs::Print;
 use crate::racion::Racion;
+use crate::reporte::{Alarma, Reporte, ReporteCiclo};
+
+use types::eventos::{self, Evento, Severidad};
+use types::unidades::{Gramos, Milisegundos, Tiempo};
+use utils::executor::bloquear;
+use utils::reloj::{self, RelojRef};
+use uuid::Uuid;
+
+/// Tiempo máximo que se espera a que el soplador quede listo: la rampa más larga que
+/// acepta un `Soplador`.
+pub const ESPERA_SOPLADOR_MAX: Milisegundos = hardware::rampa::RAMPA_MAX;
+
+/// Intervalo con que se consulta un soplador que no informa cuánto le falta.
+const PASO_SOPLADOR: Milisegundos = Milisegundos(250);
 
-use hardware::logs::Print as HardwarePrint;
+/// Un ciclo dentro de la secuencia que ejecuta un `Programa`.
+#[derive(Clone, Copy)]
+pub(crate) struct Paso<'a> {
+    pub ciclo: &'a Ciclo,
+    pub salida: Option<u32>,
+    pub barrido: Option<&'a Barrido>,
 
-use utils::utils::sleep;
+    /// Pasos del mismo grupo (una ración o una jaula) respetan el descanso entre sí.
+    pub grupo: usize,
+
+    /// Tiempo mínimo entre el término de un paso del grupo y el inicio del siguiente.
+    pub descanso: Milisegundos,
+}
 
 pub struct Programa<'a> {
-    racion: &'a Racion<'a>,
+    /// Identificador de la ración o plan que se ejecuta.
+    id: Uuid,
+
+    /// Ciclos a ejecutar, en orden.
+    pasos: Vec<Paso<'a>>,
+
+    /// Si al terminar el último ciclo también se espera su descanso.
+    descanso_final: bool,
+
+    /// Espera máxima hasta el siguiente ciclo con la que el soplador se mantiene
+    /// encendido en vez de apagarse tras el barrido.
+    mantener_encendido: Milisegundos,
+
+    /// Reloj con el que se miden todas las esperas del programa.
+    reloj: RelojRef,
+
+    /// Estado compartido que se puede consultar durante la ejecución.
+    monitor: Monitor,
+
+    /// Órdenes de pausa y detención recibidas durante la ejecución.
+    control: Control,
 }
 
 impl<'a> Programa<'a> {
     pub fn new(racion: &'a Racion<'a>) -> Self {
-        Self { racion }
+        let pasos = racion
+            .get_ciclos()
+            .iter()
+            .map(|ciclo| Paso {
+                ciclo,
+                salida: racion.get_salida_de(ciclo),
+                barrido: racion.get_barrido_de(ciclo),
+                grupo: 0,
+                descanso: racion.get_ciclo_espera(),
+            })
+            .collect();
+        Self::desde_pasos(racion.get_id(), pasos, true)
+    }
+
+    /// Crea un programa que ejecuta una secuencia de pasos ya ordenada, como la de un `Plan`.
+    pub(crate) fn desde_pasos(id: Uuid, pasos: Vec<Paso<'a>>, descanso_final: bool) -> Self {
+        Self {
+            id,
+            pasos,
+            descanso_final,
+            mantener_encendido: Milisegundos(0),
+            reloj: reloj::real(),
+            monitor: Monitor::new(),
+            control: Control::new(),
+        }
+    }
+
+    /// Establece el reloj con el que el programa espera pulsos y ciclos.
+    ///
+    /// Para simular una ración completa en milisegundos se debe compartir un
+    /// `RelojManual` con el programa y con el soplador.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let reloj: RelojRef = Arc::new(RelojManual::new(0));
+    ///! soplador.set_reloj(reloj.clone());
+    ///! Programa::new(&racion).set_reloj(reloj).iniciar(&mut soplador, &mut doser, &mut silo);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_reloj(&mut self, reloj: RelojRef) -> &mut Self {
+        self.reloj = reloj;
+        self
+    }
+
+    /// Mantiene el soplador encendido entre dos ciclos si el siguiente comienza dentro
+    /// de `ms`, evitando una bajada y una subida de rampa seguidas.
+    ///
+    /// Por defecto es 0: el soplador se apaga al terminar cada ciclo (tras el barrido)
+    /// salvo que el siguiente comience de inmediato, como al pasar de una jaula a otra
+    /// en un `Plan`. Al terminar el programa siempre queda apagado.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! programa.set_mantener_encendido(Milisegundos::minutos(2)); // Descansos de hasta 2 minutos
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_mantener_encendido(&mut self, ms: Milisegundos) -> &mut Self {
+        self.mantener_encendido = ms;
+        self
+    }
+
+    pub fn get_mantener_encendido(&self) -> Milisegundos {
+        self.mantener_encendido
+    }
+
+    /// Obtiene el identificador de la ración o plan que ejecuta el programa.
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub(crate) fn get_pasos(&self) -> &[Paso<'a>] {
+        &self.pasos
+    }
+
+    /// Obtiene un `Monitor` para consultar el estado del programa mientras se ejecuta.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let monitor = programa.get_monitor();
+    ///! assert_eq!(monitor.get().fase, Fase::Detenido);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_monitor(&self) -> Monitor {
+        self.monitor.clone()
+    }
+
+    /// Obtiene un `Control` para pausar, reanudar o detener el programa mientras se ejecuta.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let control = programa.get_control();
+    ///! control.abortar();
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_control(&self) -> Control {
+        self.control.clone()
+    }
+
+    /// Comparte un `Monitor` existente, como el de una `Agenda`, en vez del propio.
+    pub fn set_monitor(&mut self, monitor: Monitor) -> &mut Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Comparte un `Control` existente, como el de una `Agenda`, en vez del propio.
+    pub fn set_control(&mut self, control: Control) -> &mut Self {
+        self.control = control;
+        self
+    }
+
+    /// Ejecuta la ración sobre cualquier hardware que implemente los traits de `hardware::traits`,
+    /// ya sea simulado, un doble de pruebas o un controlador real.
+    ///
+    /// Bloquea el hilo actual hasta que la ración termina; es equivalente a ejecutar
+    /// `iniciar_async` con `utils::executor::bloquear`.
+    ///
+    /// # Retorna:
+    /// Un `Reporte` con el motivo por el que terminó la ejecución, lo entregado y las alarmas.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let mut programa = Programa::new(&racion);
+    ///! programa.iniciar(&mut Soplador::new(), &mut Dosificador::new(2).unwrap(), &mut silo);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn iniciar<S, D, A>(&mut self, soplador: &mut S, doser: &mut D, silo: &mut A) -> Reporte
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+    {
+        bloquear(self.iniciar_async(soplador, doser, silo))
+    }
+
+    /// Igual que `iniciar`, pero con una selectora que dirige cada ciclo a su salida.
+    ///
+    /// Antes de abrir el dosificador en cada ciclo mueve la selectora a la salida del
+    /// ciclo (o de la ración), espera su tiempo de cambio y comprueba que quedó en
+    /// posición. Si no lo logra detiene la ración con `Fin::ErrorSelectora`.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! ciclo.set_salida(Some(3));
+    ///! programa.iniciar_en(&mut soplador, &mut doser, &mut silo, &mut Selectora::new(5));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn iniciar_en<S, D, A, L>(
+        &mut self,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        selectora: &mut L,
+    ) -> Reporte
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        bloquear(self.iniciar_en_async(soplador, doser, silo, selectora))
+    }
+
+    /// Versión asíncrona de `iniciar`, independiente del executor.
+    ///
+    /// Cede el control en cada pulso y en cada espera, por lo que un mismo proceso
+    /// puede ejecutar varias líneas de alimentación a la vez y atender consultas
+    /// de estado a través de un `Monitor`.
+    ///
+    /// Si se detiene mediante el `Control`, o si el silo se queda sin alimento, cierra el
+    /// dosificador y luego apaga el soplador. Al faltar alimento entrega lo que queda en el
+    /// silo como un pulso parcial y levanta una `Alarma::SiloSinAlimento`.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let (a, b) = bloquear(unir(
+    ///!     linea_1.iniciar_async(&mut soplador_1, &mut doser_1, &mut silo_1),
+    ///!     linea_2.iniciar_async(&mut soplador_2, &mut doser_2, &mut silo_2),
+    ///! ));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub async fn iniciar_async<S, D, A>(
+        &mut self,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+    ) -> Reporte
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+    {
+        self.correr(soplador, doser, silo, None::<&mut Selectora>)
+            .await
+    }
+
+    /// Versión asíncrona de `iniciar_en`.
+    pub async fn iniciar_en_async<S, D, A, L>(
+        &mut self,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        selectora: &mut L,
+    ) -> Reporte
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        self.correr(soplador, doser, silo, Some(selectora)).await
+    }
+
+    async fn correr<S, D, A, L>(
+        &mut self,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        mut selectora: Option<&mut L>,
+    ) -> Reporte
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        let mut reporte = Reporte::new(
+            self.id,
+            self.get_solicitado(doser),
+            Tiempo(self.reloj.ahora()),
+            silo.get_alimento_gramos(),
+        );
+
+        reporte.fin = match self
+            .ejecutar(soplador, doser, silo, &mut selectora, &mut reporte)
+            .await
+        {
+            Ok(()) => {
+                self.apagar_soplador(soplador, &mut reporte);
+                Fin::Completado
+            }
+            Err(fin) => {
+                self.detener(soplador, doser, &mut reporte);
+                fin
+            }
+        };
+        reporte.cerrar(Tiempo(self.reloj.ahora()), silo.get_alimento_gramos());
+
+        self.monitor
+            .actualizar(|estado| estado.fase = Fase::Finalizado);
+        reporte
+    }
+
+    /// Gramos que debe entregar la ración completa con el dosificador dado, limitados
+    /// a `u32::MAX`. Una entrega mayor no cabe en ningún silo, así que la ración
+    /// terminará antes por falta de alimento.
+    fn get_solicitado<D: Dosificar>(&self, doser: &D) -> Gramos {
+        self.pasos
+            .iter()
+            .map(|paso| {
+                paso.ciclo
+                    .calcular_entrega(doser)
+                    .unwrap_or(Gramos(u32::MAX))
+            })
+            .fold(Gramos(0), Gramos::saturating_add)
     }
 
-    pub fn iniciar(&mut self, soplador: &mut Soplador, doser: &mut Dosificador, silo: &mut Silo) {
-        for (i, ciclo) in self.racion.get_ciclos().iter().enumerate() {
-            soplador.set_estado(true);
+    async fn ejecutar<S, D, A, L>(
+        &self,
+        soplador: &mut S,
+        doser: &mut D,
+        silo: &mut A,
+        selectora: &mut Option<&mut L>,
+        reporte: &mut Reporte,
+    ) -> Result<(), Fin>
+    where
+        S: Soplar,
+        D: Dosificar,
+        A: Almacenar,
+        L: Seleccionar,
+    {
+        // Instante en que terminó el último paso de cada grupo
+        let mut terminos: HashMap<usize, u64> = HashMap::new();
+
+        for (i, paso) in self.pasos.iter().enumerate() {
+            self.punto_de_control().await?;
+
+            let ciclo = paso.ciclo;
+            let salida = paso.salida;
+            self.monitor.actualizar(|estado| {
+                estado.fase = Fase::Seleccionando;
+                estado.ciclo = i;
+                estado.pulso = 0;
+            });
+            if let Some(salida) = salida {
+                match selectora.as_deref_mut() {
+                    Some(selectora) => self.seleccionar(selectora, salida, reporte).await?,
+                    None => {
+                        self.alarma(
+                            reporte,
+                            Alarma::SalidaSinSelectora {
+                                ciclo: ciclo.get_id(),
+                                salida,
+                            },
+                        );
+                        return Err(Fin::ErrorSelectora);
+                    }
+                }
+            }
+
+            // El cambio de salida cuenta como parte del descanso
+            let restante = self.get_descanso(paso, &terminos);
+            self.descansar(ciclo, restante).await?;
 
-            let id = ciclo.get_id();
-            println!("[Ración: {}][Tipo: {}]: En Ejecución", i + 1, id);
+            self.monitor
+                .actualizar(|estado| estado.fase = Fase::Encendiendo);
+            reporte.ciclos.push(ReporteCiclo::new(
+                ciclo.get_id(),
+                salida,
+                Tiempo(self.reloj.ahora()),
+            ));
+            if !soplador.get_estado() {
+                reporte.soplador_encendido(Tiempo(self.reloj.ahora()));
+                soplador.set_estado(true);
+            }
+            self.esperar_soplador(soplador, reporte).await?;
 
-            println!("Iniciando...");
+            eventos::emitir(
+                Evento::new(
+                    Severidad::Info,
+                    "Ciclo",
+                    format!("[Ración: {}]: En Ejecución", i + 1),
+                )
+                .id(ciclo.get_id()),
+            );
             ciclo.print();
 
-            let pulsos = ciclo.get_pulsos();
-            let pulso_duracion = ciclo.get_pulso_duracion();
-            let pulso_espera = ciclo.get_pulso_espera();
+            let resultado = self
+                .entregar(ciclo, doser, silo, reporte, paso.barrido.is_some())
+                .await;
+
+            // La línea se barre también si la ración se detiene de forma ordenada
+            if let Some(barrido) = paso.barrido {
+                let entrego = reporte
+                    .ciclos
+                    .last()
+                    .is_some_and(|ciclo| ciclo.get_entregado() > Gramos(0));
+                if entrego && resultado != Err(Fin::ParadaEmergencia) {
+                    self.barrer(soplador, barrido, reporte).await?;
+                }
+            }
+            resultado?;
+
+            terminos.insert(paso.grupo, self.reloj.ahora());
 
-            for _pulso in 0..=pulsos {
-                let entregado = doser.get_entrega() * (pulso_duracion / 1000);
+            // Con la línea ya barrida, el soplador solo sigue encendido si el próximo ciclo está cerca
+            let mantener = self.pasos.get(i + 1).is_some_and(|siguiente| {
+                self.get_descanso(siguiente, &terminos) <= self.mantener_encendido
+            });
+            if !mantener {
+                self.apagar_soplador(soplador, reporte);
+            }
+        }
 
-                doser.set_estado(true).print();
-                silo.entregar_pulso(entregado)
-                    .expect("Has intentado entregar un pulso mayor a la cantidad de alimento");
-                silo.print();
+        if let Some(ultimo) = self.pasos.last().filter(|_| self.descanso_final) {
+            self.descansar(ultimo.ciclo, ultimo.descanso).await?;
+        }
 
-                sleep(pulso_duracion);
+        Ok(())
+    }
 
-                doser.set_estado(false).print();
-                sleep(pulso_espera);
+    /// Tiempo que falta para que `paso` pueda comenzar según el término del último
+    /// paso de su grupo. Es 0 si es el primero de su grupo.
+    fn get_descanso(&self, paso: &Paso, terminos: &HashMap<usize, u64>) -> Milisegundos {
+        match terminos.get(&paso.grupo) {
+            Some(termino) => Milisegundos(
+                (termino + u64::from(paso.descanso.0))
+                    .saturating_sub(self.reloj.ahora())
+                    .min(u64::from(u32::MAX)) as u32,
+            ),
+            None => Milisegundos(0),
+        }
+    }
+
+    /// Entrega los pulsos de `ciclo`. Con `barrer` se omite la espera tras el último
+    /// pulso, ya que la reemplaza el barrido de la línea.
+    async fn entregar<D, A>(
+        &self,
+        ciclo: &Ciclo,
+        doser: &mut D,
+        silo: &mut A,
+        reporte: &mut Reporte,
+        barrer: bool,
+    ) -> Result<(), Fin>
+    where
+        D: Dosificar,
+        A: Almacenar,
+    {
+        let pulsos = ciclo.get_total_pulsos();
+        let pulso_duracion = ciclo.get_pulso_duracion();
+        let pulso_espera = ciclo.get_pulso_espera();
+
+        for pulso in 0..pulsos {
+            self.punto_de_control().await?;
+
+            let solicitado = doser.calcular_gramos(pulso_duracion);
+
+            // Con menos alimento que el pulso se entrega lo que queda como un pulso parcial
+            let resto = silo.get_alimento_gramos();
+            let (objetivo, duracion) = if solicitado > resto {
+                let duracion = fraccion(pulso_duracion.0, u64::from(resto.0), solicitado.0);
+                (resto, Milisegundos(duracion))
+            } else {
+                (solicitado, pulso_duracion)
+            };
+
+            doser.set_estado(true).print();
+            self.monitor.actualizar(|estado| {
+                estado.fase = Fase::Pulso;
+                estado.pulso = pulso;
+            });
+            let inicio = self.reloj.ahora();
+            let espera = self.esperar(duracion, Orden::ParadaEmergencia).await;
+
+            // El silo se descuenta con lo que realmente pasó por el dosificador
+            let entregado = match espera {
+                Ok(()) => objetivo,
+                Err(_) => {
+                    let transcurrido = self.reloj.ahora().saturating_sub(inicio);
+                    Gramos(fraccion(objetivo.0, transcurrido, duracion.0))
+                }
+            };
+            if let Err(error) = silo.entregar_gramos(entregado) {
+                self.alarma(
+                    reporte,
+                    Alarma::ErrorSilo {
+                        silo: silo.get_id(),
+                        error,
+                    },
+                );
+                espera?;
+                self.cerrar_dosificador(doser, reporte);
+                return Err(Fin::ErrorSilo);
+            }
+            silo.print();
+            self.registrar_pulso(reporte, pulso, entregado);
+
+            if objetivo < solicitado {
+                self.alarma(
+                    reporte,
+                    Alarma::SiloSinAlimento {
+                        silo: silo.get_id(),
+                        solicitado,
+                        entregado,
+                    },
+                );
+                espera?;
+                self.cerrar_dosificador(doser, reporte);
+                return Err(Fin::SinAlimento);
+            }
+            espera?;
+
+            self.cerrar_dosificador(doser, reporte);
+            if pulso + 1 < pulsos || !barrer {
+                self.monitor
+                    .actualizar(|estado| estado.fase = Fase::EsperaPulso);
+                self.esperar(pulso_espera, Orden::Abortar).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Barre la línea con el dosificador cerrado: mantiene el soplador a la potencia
+    /// del barrido durante su duración y luego restaura la potencia anterior.
+    ///
+    /// Solo una parada de emergencia lo interrumpe.
+    async fn barrer<S: Soplar>(
+        &self,
+        soplador: &mut S,
+        barrido: &Barrido,
+        reporte: &mut Reporte,
+    ) -> Result<(), Fin> {
+        self.monitor
+            .actualizar(|estado| estado.fase = Fase::Barrido);
+
+        let potencia = soplador.get_potencia();
+        if let Err(error) = soplador.set_potencia(barrido.get_potencia()) {
+            eventos::emitir(
+                Evento::new(
+                    Severidad::Aviso,
+                    "Barrido",
+                    format!("No se pudo fijar la potencia: {:?}", error),
+                )
+                .id(soplador.get_id()),
+            );
+        }
+        eventos::emitir(
+            Evento::new(
+                Severidad::Info,
+                "Barrido",
+                format!(
+                    "{} Duración {}",
+                    soplador.get_potencia(),
+                    barrido.get_duracion()
+                ),
+            )
+            .id(soplador.get_id()),
+        );
+
+        // La duración del barrido cuenta desde que el soplador alcanzó su potencia
+        let listo = self.esperar_soplador(soplador, reporte).await;
+        let inicio = Tiempo(self.reloj.ahora());
+        let resultado = match listo {
+            Ok(()) => {
+                self.esperar(barrido.get_duracion(), Orden::ParadaEmergencia)
+                    .await
+            }
+            Err(fin) => Err(fin),
+        };
+        if let Some(ciclo) = reporte.ciclos.last_mut() {
+            ciclo.barrido = Tiempo(self.reloj.ahora()).saturating_sub(inicio);
+        }
+
+        soplador.set_potencia(potencia).ok();
+        resultado
+    }
+
+    /// Espera a que el soplador termine su rampa antes de abrir el dosificador, para no
+    /// cargar la línea sin el caudal de aire necesario.
+    ///
+    /// Si no queda listo dentro de `ESPERA_SOPLADOR_MAX` levanta una alarma y detiene el
+    /// programa con `Fin::ErrorSoplador`.
+    async fn esperar_soplador<S: Soplar>(
+        &self,
+        soplador: &S,
+        reporte: &mut Reporte,
+    ) -> Result<(), Fin> {
+        let inicio = self.reloj.ahora();
+        while !soplador.is_listo() {
+            let transcurrido = self.reloj.ahora().saturating_sub(inicio);
+            if transcurrido >= u64::from(ESPERA_SOPLADOR_MAX.0) {
+                self.alarma(
+                    reporte,
+                    Alarma::SopladorNoListo {
+                        soplador: soplador.get_id(),
+                        espera: ESPERA_SOPLADOR_MAX,
+                    },
+                );
+                return Err(Fin::ErrorSoplador);
             }
-            println!(
-                "Ración {}: En Espera... Duración {}s",
-                id,
-                self.racion.get_ciclo_espera()
+            let tramo = match soplador.get_restante() {
+                Milisegundos(0) => PASO_SOPLADOR,
+                restante => restante,
+            };
+            let tramo = tramo.min(ESPERA_SOPLADOR_MAX - Milisegundos(transcurrido as u32));
+            self.esperar(tramo, Orden::Abortar).await?;
+        }
+        Ok(())
+    }
+
+    /// Espera entre ciclos: `ms` en `Fase::EsperaCiclo`.
+    async fn descansar(&self, ciclo: &Ciclo, ms: Milisegundos) -> Result<(), Fin> {
+        if ms == Milisegundos(0) {
+            return Ok(());
+        }
+        eventos::emitir(
+            Evento::new(
+                Severidad::Info,
+                "Ciclo",
+                format!("En Espera... Duración {}", ms),
+            )
+            .id(ciclo.get_id()),
+        );
+        self.monitor
+            .actualizar(|estado| estado.fase = Fase::EsperaCiclo);
+        self.esperar(ms, Orden::Abortar).await
+    }
+
+    /// Lleva la selectora a `salida`, espera su tiempo de cambio si tuvo que moverse
+    /// y comprueba que quedó en posición antes de que se abra el dosificador.
+    async fn seleccionar<L: Seleccionar>(
+        &self,
+        selectora: &mut L,
+        salida: u32,
+        reporte: &mut Reporte,
+    ) -> Result<(), Fin> {
+        if selectora.get_posicion() != salida {
+            eventos::emitir(
+                Evento::new(
+                    Severidad::Info,
+                    "Selectora",
+                    format!("Moviendo a la salida {}", salida),
+                )
+                .id(selectora.get_id()),
             );
-            sleep(self.racion.get_ciclo_espera());
+            if let Err(error) = selectora.set_posicion(salida) {
+                self.alarma(
+                    reporte,
+                    Alarma::ErrorSelectora {
+                        selectora: selectora.get_id(),
+                        salida,
+                        error,
+                    },
+                );
+                return Err(Fin::ErrorSelectora);
+            }
+            self.esperar(selectora.get_espera(), Orden::Abortar).await?;
         }
+
+        let posicion = selectora.get_posicion();
+        if posicion != salida {
+            self.alarma(
+                reporte,
+                Alarma::PosicionIncorrecta {
+                    selectora: selectora.get_id(),
+                    salida,
+                    posicion,
+                },
+            );
+            return Err(Fin::ErrorSelectora);
+        }
+        Ok(())
+    }
+
+    /// Suma lo entregado en un pulso al reporte y lo publica en el `Monitor`.
+    fn registrar_pulso(&self, reporte: &mut Reporte, pulso: u32, entregado: Gramos) {
+        if let Some(ciclo) = reporte.ciclos.last_mut() {
+            ciclo.pulsos.push(entregado);
+        }
+        reporte.entregado += entregado;
+        let total = reporte.entregado;
+        self.monitor.actualizar(|estado| {
+            estado.fase = Fase::Pulso;
+            estado.pulso = pulso;
+            estado.entregado = total;
+        });
+    }
+
+    /// Cierra el dosificador y marca el término del ciclo en curso en el reporte.
+    fn cerrar_dosificador<D: Dosificar>(&self, doser: &mut D, reporte: &mut Reporte) {
+        doser.set_estado(false).print();
+        if let Some(ciclo) = reporte.ciclos.last_mut() {
+            ciclo.termino = Tiempo(self.reloj.ahora());
+        }
+    }
+
+    /// Levanta una alarma: la anuncia y la agrega al reporte.
+    fn alarma(&self, reporte: &mut Reporte, alarma: Alarma) {
+        let (origen, id, mensaje) = match &alarma {
+            Alarma::SiloSinAlimento {
+                silo,
+                solicitado,
+                entregado,
+            } => (
+                "Silo",
+                *silo,
+                format!(
+                    "Sin alimento, se entregaron {} de {} del pulso",
+                    entregado, solicitado
+                ),
+            ),
+            Alarma::ErrorSilo { silo, error } => {
+                ("Silo", *silo, format!("Error al entregar: {:?}", error))
+            }
+            Alarma::SalidaSinSelectora { ciclo, salida } => (
+                "Ciclo",
+                *ciclo,
+                format!(
+                    "Debe entregarse en la salida {} pero no hay selectora",
+                    salida
+                ),
+            ),
+            Alarma::ErrorSelectora {
+                selectora,
+                salida,
+                error,
+            } => (
+                "Selectora",
+                *selectora,
+                format!("No se pudo mover a la salida {}: {:?}", salida, error),
+            ),
+            Alarma::PosicionIncorrecta {
+                selectora,
+                salida,
+                posicion,
+            } => (
+                "Selectora",
+                *selectora,
+                format!("Quedó en la salida {} en vez de la {}", posicion, salida),
+            ),
+            Alarma::SopladorNoListo { soplador, espera } => (
+                "Soplador",
+                *soplador,
+                format!("No quedó listo en {}", espera),
+            ),
+        };
+        eventos::emitir(
+            Evento::new(Severidad::Error, origen, format!("Alarma: {}", mensaje)).id(id),
+        );
+        reporte.alarmas.push(alarma);
+    }
+
+    /// Revisa el `Control` entre pulsos: retiene el programa mientras esté pausado
+    /// y retorna el `Fin` correspondiente si se ordenó detenerlo.
+    async fn punto_de_control(&self) -> Result<(), Fin> {
+        loop {
+            match self.control.get_orden() {
+                Orden::Continuar => return Ok(()),
+                Orden::Abortar => return Err(Fin::Abortado),
+                Orden::ParadaEmergencia => return Err(Fin::ParadaEmergencia),
+                Orden::Pausar => {
+                    self.monitor
+                        .actualizar(|estado| estado.fase = Fase::Pausado);
+                    self.reloj.esperar(PASO_CONTROL.0).await;
+                }
+            }
+        }
+    }
+
+    /// Espera `ms` en tramos de `PASO_CONTROL`, revisando el `Control` en cada tramo.
+    ///
+    /// Con `Orden::Abortar` la espera se interrumpe tanto al abortar como ante una parada
+    /// de emergencia; con `Orden::ParadaEmergencia` solo esta última la interrumpe.
+    async fn esperar(&self, ms: Milisegundos, interrumpe: Orden) -> Result<(), Fin> {
+        let mut restante = ms;
+
+        while restante > Milisegundos(0) {
+            let tramo = restante.min(PASO_CONTROL);
+            self.reloj.esperar(tramo.0).await;
+            restante -= tramo;
+
+            match self.control.get_orden() {
+                Orden::ParadaEmergencia => return Err(Fin::ParadaEmergencia),
+                Orden::Abortar if interrumpe == Orden::Abortar => return Err(Fin::Abortado),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lleva el hardware a un estado definido: primero cierra el dosificador y luego apaga el soplador.
+    fn detener<S: Soplar, D: Dosificar>(
+        &self,
+        soplador: &mut S,
+        doser: &mut D,
+        reporte: &mut Reporte,
+    ) {
+        if doser.get_estado() {
+            self.cerrar_dosificador(doser, reporte);
+        } else {
+            doser.set_estado(false).print();
+        }
+        soplador.set_estado(false).print();
+        reporte.soplador_apagado(Tiempo(self.reloj.ahora()));
+    }
+
+    /// Apaga el soplador si está encendido; su rampa de bajada continúa sin bloquear el programa.
+    fn apagar_soplador<S: Soplar>(&self, soplador: &mut S, reporte: &mut Reporte) {
+        if soplador.get_estado() {
+            soplador.set_estado(false);
+            reporte.soplador_apagado(Tiempo(self.reloj.ahora()));
+        }
+    }
+}
+
+/// `parte` de `total` aplicada a `valor`, con `parte` limitada a `total`. Es 0 si `total` es 0.
+fn fraccion(valor: u32, parte: u64, total: u32) -> u32 {
+    match total {
+        0 => 0,
+        _ => (u64::from(valor) * parte.min(u64::from(total)) / u64::from(total)) as u32,
     }
 }
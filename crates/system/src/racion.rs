@@ -1,25 +1,43 @@
+use crate::barrido::Barrido;
 use crate::ciclo::Ciclo;
+use crate::errors::CicloError;
+use hardware::traits::Dosificar;
+
 use types::rango::{Rango, RangoError};
+use types::unidades::{Gramos, GramosPorSegundo, Milisegundos};
 use uuid::Uuid;
 
 /// Espera mínima de 1 minutos
-pub const ESPERA_MIN: u32 = 60 * 1000 * 1;
+pub const ESPERA_MIN: Milisegundos = Milisegundos::minutos(1);
 
 /// Espera máxima de 5 horas
-pub const ESPERA_MAX: u32 = 60 * 1000 * 60 * 5;
+pub const ESPERA_MAX: Milisegundos = Milisegundos::minutos(60 * 5);
+
+/// El `Rango` de la espera entre ciclos, en `ESPERA_MIN`.
+fn espera_inicial() -> Rango<Milisegundos> {
+    Rango::new(ESPERA_MIN, ESPERA_MAX, ESPERA_MIN).unwrap()
+}
 
 /// Representa un **Racion** que contiene una lista de ciclos y un tiempo de espera para la siguiente ración.
 ///
 /// La estructura `Racion` tiene los siguientes campos:
 /// - **ciclos**: Un vector de referencias a ciclos, que representan las ciclos asociadas con el racion.
-/// - **ciclo_espera**: El tiempo en segundos de espera antes de la siguiente ración.
+/// - **ciclo_espera**: El tiempo de espera antes del siguiente ciclo.
+/// - **salida**: La salida de la selectora para los ciclos que no indican una.
+/// - **barrido**: El barrido de la línea para los ciclos que no indican uno.
 /// - **id**: Un identificador único para cada instancia del racion.
 pub struct Racion<'a> {
     /// Lista de referencias a las ciclos asociadas con el racion.
     ciclos: Vec<&'a Ciclo>,
 
-    /// Tiempo de espera antes de la siguiente ración en segundos.
-    ciclo_espera: Rango,
+    /// Tiempo de espera antes del siguiente ciclo.
+    ciclo_espera: Rango<Milisegundos>,
+
+    /// Salida de la selectora por defecto para los ciclos de la ración.
+    salida: Option<u32>,
+
+    /// Barrido de la línea por defecto para los ciclos de la ración.
+    barrido: Option<Barrido>,
 
     /// El identificador único del racion.
     id: Uuid,
@@ -40,15 +58,56 @@ impl<'a> Racion<'a> {
     ///! let ciclos = vec![&racion1, &racion2]; // Suponiendo que `racion1` y `racion2` son instancias de `Ciclo`.
     ///! let racion = Racion::new(ciclos);
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn new(ciclos: Vec<&'a Ciclo>) -> Self {
         Self {
             ciclos,
-            ciclo_espera: Rango::new(ESPERA_MIN, ESPERA_MAX, ESPERA_MIN).unwrap(),
+            ciclo_espera: espera_inicial(),
+            salida: None,
+            barrido: None,
             id: Uuid::new_v4(),
         }
     }
 }
 
+impl Racion<'_> {
+    /// Crea los ciclos de una ración definida por masa: reparte `gramos` en `ciclos`
+    /// ciclos iguales con `Ciclo::por_masa`. Los gramos que no se reparten en partes
+    /// iguales se suman al primer ciclo.
+    ///
+    /// Como la ración guarda referencias, los ciclos se crean aparte y luego se enlazan.
+    ///
+    /// # Errores:
+    /// Los mismos de `Ciclo::por_masa`; `ciclos` igual a 0 es `CicloError::CantidadFueraDeRango`.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! let ciclos =
+    ///!     Racion::ciclos_por_masa(Gramos(300000), 3, &doser, Some(GramosPorSegundo(500))).unwrap();
+    ///! let racion = Racion::new(ciclos.iter().collect());
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn ciclos_por_masa<D: Dosificar>(
+        gramos: Gramos,
+        ciclos: u32,
+        doser: &D,
+        caudal_max: Option<GramosPorSegundo>,
+    ) -> Result<Vec<Ciclo>, CicloError> {
+        if ciclos == 0 {
+            return Err(CicloError::CantidadFueraDeRango);
+        }
+
+        let parte = Gramos(gramos.0 / ciclos);
+        let resto = Gramos(gramos.0 % ciclos);
+        (0..ciclos)
+            .map(|i| {
+                let masa = if i == 0 { parte + resto } else { parte };
+                Ciclo::por_masa(masa, doser, caudal_max)
+            })
+            .collect()
+    }
+}
+
 impl<'a> Racion<'a> {
     /// Obtiene la lista de ciclos asociadas con el racion.
     ///
@@ -60,6 +119,7 @@ impl<'a> Racion<'a> {
     ///! let ciclos = racion.get_ciclos();
     ///! assert_eq!(ciclos.len(), 2); // Suponiendo que el racion tiene 2 ciclos asociadas.
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_ciclos(&self) -> &Vec<&'a Ciclo> {
         &self.ciclos
     }
@@ -67,18 +127,22 @@ impl<'a> Racion<'a> {
     /// Establece el tiempo de espera para la siguiente ración.
     ///
     /// # Parámetros:
-    /// - `n`: El tiempo de espera en segundos.
+    /// - `n`: El tiempo de espera.
     ///
     /// # Retorna:
     /// Una referencia mutable al objeto `Racion` para permitir el encadenamiento de llamadas (method chaining).
     ///
     /// # Ejemplo:
     ///! ```
-    ///! racion.set_ciclo_espera(30); // Establece un tiempo de espera de 30 segundos.
-    ///! assert_eq!(racion.get_ciclo_espera(), 30); // Verifica que el tiempo de espera sea ahora 30 segundos.
+    ///! racion.set_ciclo_espera(Milisegundos::minutos(30)); // Establece un tiempo de espera de 30 minutos.
+    ///! assert_eq!(racion.get_ciclo_espera(), Milisegundos::minutos(30));
     ///! ```
-    pub fn set_ciclo_espera(&mut self, n: u32) -> Result<(), crate::errors::RacionError> {
-        match self.ciclo_espera.set(n, "[Racion]") {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_ciclo_espera(&mut self, n: Milisegundos) -> Result<(), crate::errors::RacionError> {
+        match self
+            .ciclo_espera
+            .set_campo(n, "Racion", self.id, "ciclo_espera")
+        {
             Ok(()) => Ok(()),
             Err(RangoError::FueraDeRango) => Err(crate::errors::RacionError::EsperaFueraDeRango),
         }
@@ -86,18 +150,53 @@ impl<'a> Racion<'a> {
 
     /// Obtiene el tiempo de espera actual para el siguiente ciclo.
     ///
-    /// # Retorna:
-    /// El tiempo de espera en segundos (de tipo `u32`).
-    ///
     /// # Ejemplo:
     ///! ```
     ///! let tiempo_espera = racion.get_ciclo_espera();
-    ///! assert_eq!(tiempo_espera, 30); // El tiempo de espera debería ser 30 segundos.
+    ///! assert_eq!(tiempo_espera, Milisegundos::minutos(30));
     ///! ```
-    pub fn get_ciclo_espera(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get_ciclo_espera(&self) -> Milisegundos {
         self.ciclo_espera.get()
     }
 
+    /// Establece la salida de la selectora para los ciclos que no indican una.
+    ///
+    /// # Ejemplo:
+    ///! ```
+    ///! racion.set_salida(Some(2));
+    ///! assert_eq!(racion.get_salida(), Some(2));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_salida(&mut self, n: Option<u32>) {
+        self.salida = n;
+    }
+
+    /// Obtiene la salida por defecto de la ración.
+    pub fn get_salida(&self) -> Option<u32> {
+        self.salida
+    }
+
+    /// Obtiene la salida en la que se entrega `ciclo`: la del ciclo o, si no tiene, la de la ración.
+    pub fn get_salida_de(&self, ciclo: &Ciclo) -> Option<u32> {
+        ciclo.get_salida().or(self.salida)
+    }
+
+    /// Establece el barrido de la línea para los ciclos que no indican uno.
+    pub fn set_barrido(&mut self, n: Option<Barrido>) {
+        self.barrido = n;
+    }
+
+    /// Obtiene el barrido por defecto de la ración.
+    pub fn get_barrido(&self) -> Option<&Barrido> {
+        self.barrido.as_ref()
+    }
+
+    /// Obtiene el barrido que se ejecuta tras `ciclo`: el del ciclo o, si no tiene, el de la ración.
+    pub fn get_barrido_de<'b>(&'b self, ciclo: &'b Ciclo) -> Option<&'b Barrido> {
+        ciclo.get_barrido().or(self.barrido.as_ref())
+    }
+
     /// Obtiene el identificador único (UUID) del racion.
     ///
     /// # Retorna:
@@ -108,7 +207,123 @@ impl<'a> Racion<'a> {
     ///! let id = racion.get_id();
     ///! println!("El ID del racion es: {}", id);
     ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
     pub fn get_id(&self) -> Uuid {
         self.id
     }
 }
+
+/// Una ración dueña de sus ciclos, para cargar raciones serializadas.
+///
+/// `Racion` guarda referencias a ciclos que viven en otra parte, por lo que no se puede
+/// deserializar directamente. Una `Racion` se serializa con sus ciclos completos y ese
+/// mismo formato se carga como `RacionGuardada`, de la que se obtiene la `Racion`.
+///
+/// # Ejemplo:
+///! ```
+///! let texto = serde_json::to_string(&racion)?;
+///! let guardada: RacionGuardada = serde_json::from_str(&texto)?;
+///! let racion = guardada.get_racion();
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[cfg(feature = "serde")]
+#[derive(PartialEq, Debug)]
+pub struct RacionGuardada {
+    ciclos: Vec<Ciclo>,
+    ciclo_espera: Rango<Milisegundos>,
+    salida: Option<u32>,
+    barrido: Option<Barrido>,
+    id: Uuid,
+}
+
+#[cfg(feature = "serde")]
+impl RacionGuardada {
+    pub fn get_ciclos(&self) -> &Vec<Ciclo> {
+        &self.ciclos
+    }
+
+    /// Arma la `Racion` con los ciclos guardados. Conserva el `id` de la ración original.
+    pub fn get_racion(&self) -> Racion<'_> {
+        Racion {
+            ciclos: self.ciclos.iter().collect(),
+            ciclo_espera: self.ciclo_espera,
+            salida: self.salida,
+            barrido: self.barrido.clone(),
+            id: self.id,
+        }
+    }
+}
+
+/// La espera entre ciclos se vuelve a validar al deserializar, igual que los valores
+/// de cada `Ciclo`.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename = "Racion")]
+    struct Vista<'a> {
+        ciclos: Vec<&'a Ciclo>,
+        ciclo_espera: Milisegundos,
+        salida: Option<u32>,
+        barrido: Option<&'a Barrido>,
+        id: Uuid,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Racion", deny_unknown_fields)]
+    struct Datos {
+        ciclos: Vec<Ciclo>,
+        ciclo_espera: Milisegundos,
+        #[serde(default)]
+        salida: Option<u32>,
+        #[serde(default)]
+        barrido: Option<Barrido>,
+        #[serde(default = "Uuid::new_v4")]
+        id: Uuid,
+    }
+
+    impl Serialize for Racion<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Vista {
+                ciclos: self.ciclos.clone(),
+                ciclo_espera: self.get_ciclo_espera(),
+                salida: self.salida,
+                barrido: self.barrido.as_ref(),
+                id: self.id,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl Serialize for RacionGuardada {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.get_racion().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RacionGuardada {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::deserialize(deserializer)?;
+            let mut ciclo_espera = espera_inicial();
+            ciclo_espera
+                .set_campo(datos.ciclo_espera, "Racion", datos.id, "ciclo_espera")
+                .map_err(|_| {
+                    D::Error::custom(format!(
+                        "ciclo_espera {} fuera de rango ({}..={})",
+                        datos.ciclo_espera, ESPERA_MIN, ESPERA_MAX
+                    ))
+                })?;
+            Ok(RacionGuardada {
+                ciclos: datos.ciclos,
+                ciclo_espera,
+                salida: datos.salida,
+                barrido: datos.barrido,
+                id: datos.id,
+            })
+        }
+    }
+}
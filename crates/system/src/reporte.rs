@@ -0,0 +1,180 @@
+use hardware::errors::{SelectoraError, SiloError};
+use types::unidades::{Gramos, Milisegundos, Tiempo};
+use uuid::Uuid;
+
+use crate::control::Fin;
+
+/// Alarma levantada durante la ejecución de un `Programa`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Alarma {
+    /// El silo se quedó sin alimento a mitad de un pulso.
+    /// Se entregó lo que quedaba (`entregado`) de lo `solicitado` para ese pulso.
+    SiloSinAlimento {
+        silo: Uuid,
+        solicitado: Gramos,
+        entregado: Gramos,
+    },
+    /// El silo rechazó una entrega por un motivo distinto a la falta de alimento.
+    ErrorSilo { silo: Uuid, error: SiloError },
+    /// El ciclo debe entregarse en una `salida` pero el programa no tiene selectora.
+    SalidaSinSelectora { ciclo: Uuid, salida: u32 },
+    /// La selectora rechazó moverse a la `salida` pedida.
+    ErrorSelectora {
+        selectora: Uuid,
+        salida: u32,
+        error: SelectoraError,
+    },
+    /// Tras la espera de cambio, la selectora no quedó en la `salida` pedida.
+    PosicionIncorrecta {
+        selectora: Uuid,
+        salida: u32,
+        posicion: u32,
+    },
+    /// El soplador no quedó listo (encendido y a su potencia) dentro de `espera`.
+    SopladorNoListo {
+        soplador: Uuid,
+        espera: Milisegundos,
+    },
+}
+
+/// Detalle de la ejecución de un `Ciclo` dentro de una ración.
+///
+/// # Campos:
+/// - `ciclo`: El identificador del ciclo ejecutado.
+/// - `salida`: La salida de la selectora en la que se entregó, si se usó una.
+/// - `pulsos`: Gramos entregados en cada pulso ejecutado, en orden.
+/// - `inicio`: Instante en que comenzó el ciclo según el reloj del programa.
+/// - `termino`: Instante en que terminó el ciclo según el reloj del programa.
+/// - `barrido`: Lo que duró el barrido de la línea al terminar el ciclo.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ReporteCiclo {
+    pub ciclo: Uuid,
+    pub salida: Option<u32>,
+    pub pulsos: Vec<Gramos>,
+    pub inicio: Tiempo,
+    pub termino: Tiempo,
+    pub barrido: Tiempo,
+}
+
+impl ReporteCiclo {
+    pub fn new(ciclo: Uuid, salida: Option<u32>, inicio: Tiempo) -> Self {
+        Self {
+            ciclo,
+            salida,
+            pulsos: Vec::new(),
+            inicio,
+            termino: inicio,
+            barrido: Tiempo(0),
+        }
+    }
+
+    /// Cantidad de pulsos ejecutados en el ciclo.
+    pub fn get_pulsos(&self) -> u32 {
+        self.pulsos.len() as u32
+    }
+
+    /// Gramos entregados en el ciclo.
+    pub fn get_entregado(&self) -> Gramos {
+        self.pulsos.iter().copied().sum()
+    }
+}
+
+/// Resultado de ejecutar una `Racion` con un `Programa`.
+///
+/// Los instantes y duraciones se expresan en `Tiempo` (desde la época Unix con el reloj
+/// del sistema) y las cantidades de alimento en `Gramos`.
+///
+/// # Campos:
+/// - `racion`: El identificador de la ración ejecutada.
+/// - `fin`: El motivo por el que terminó la ejecución.
+/// - `solicitado`: Gramos que la ración debía entregar en total.
+/// - `entregado`: Gramos efectivamente entregados.
+/// - `inicio`, `termino`: Instantes de comienzo y término de la ración.
+/// - `silo_antes`, `silo_despues`: Gramos en el silo antes y después de la ración.
+/// - `soplador_encendido`: Tiempo que el soplador estuvo encendido.
+/// - `ciclos`: Detalle de cada ciclo ejecutado.
+/// - `alarmas`: Alarmas y errores levantados durante la ejecución.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Reporte {
+    pub racion: Uuid,
+    pub fin: Fin,
+    pub solicitado: Gramos,
+    pub entregado: Gramos,
+    pub inicio: Tiempo,
+    pub termino: Tiempo,
+    pub silo_antes: Gramos,
+    pub silo_despues: Gramos,
+    pub soplador_encendido: Tiempo,
+    pub ciclos: Vec<ReporteCiclo>,
+    pub alarmas: Vec<Alarma>,
+
+    /// Instante desde el que el soplador está encendido, si lo está.
+    soplador_desde: Option<Tiempo>,
+}
+
+impl Reporte {
+    pub fn new(racion: Uuid, solicitado: Gramos, inicio: Tiempo, silo_antes: Gramos) -> Self {
+        Self {
+            racion,
+            fin: Fin::Completado,
+            solicitado,
+            entregado: Gramos(0),
+            inicio,
+            termino: inicio,
+            silo_antes,
+            silo_despues: silo_antes,
+            soplador_encendido: Tiempo(0),
+            ciclos: Vec::new(),
+            alarmas: Vec::new(),
+            soplador_desde: None,
+        }
+    }
+
+    /// Indica si la ración se entregó por completo.
+    pub fn is_completo(&self) -> bool {
+        self.fin == Fin::Completado && self.entregado >= self.solicitado
+    }
+
+    /// Gramos de la ración que quedaron sin entregar.
+    pub fn get_faltante(&self) -> Gramos {
+        self.solicitado.saturating_sub(self.entregado)
+    }
+
+    /// Gramos entregados en la `salida` de la selectora, sumando todos sus ciclos.
+    pub fn get_entregado_en(&self, salida: u32) -> Gramos {
+        self.ciclos
+            .iter()
+            .filter(|ciclo| ciclo.salida == Some(salida))
+            .map(|ciclo| ciclo.get_entregado())
+            .sum()
+    }
+
+    /// Duración total de la ración.
+    pub fn get_duracion(&self) -> Tiempo {
+        self.termino.saturating_sub(self.inicio)
+    }
+
+    /// Registra el encendido del soplador. No tiene efecto si ya estaba encendido.
+    pub(crate) fn soplador_encendido(&mut self, ahora: Tiempo) {
+        if self.soplador_desde.is_none() {
+            self.soplador_desde = Some(ahora);
+        }
+    }
+
+    /// Registra el apagado del soplador y acumula el tiempo que estuvo encendido.
+    pub(crate) fn soplador_apagado(&mut self, ahora: Tiempo) {
+        if let Some(desde) = self.soplador_desde.take() {
+            self.soplador_encendido += ahora.saturating_sub(desde);
+        }
+    }
+
+    /// Cierra el reporte en el instante `ahora`, con el nivel final del silo.
+    pub(crate) fn cerrar(&mut self, ahora: Tiempo, silo_despues: Gramos) {
+        if let Some(desde) = self.soplador_desde {
+            self.soplador_encendido += ahora.saturating_sub(desde);
+            self.soplador_desde = Some(ahora);
+        }
+        self.termino = ahora;
+        self.silo_despues = silo_despues;
+    }
+}
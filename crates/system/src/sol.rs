@@ -0,0 +1,113 @@
+use crate::errors::AgendaError;
+
+/// Milisegundos en un día.
+const DIA_MS: f64 = 86_400_000.0;
+
+/// Día juliano de la época Unix.
+const JULIANO_UNIX: f64 = 2_440_587.5;
+
+/// Día juliano de la época J2000.
+const JULIANO_2000: f64 = 2_451_545.0;
+
+/// Altura del sol en grados al amanecer y atardecer, corregida por refracción y el radio del disco.
+const ALTURA_HORIZONTE: f64 = -0.833;
+
+/// Inclinación del eje terrestre en grados.
+const OBLICUIDAD: f64 = 23.4397;
+
+/// Luz de un día en una ubicación.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Luz {
+    /// Instantes de amanecer y atardecer, en milisegundos desde la época Unix.
+    Dia { amanecer: u64, atardecer: u64 },
+    /// El sol no se pone en todo el día.
+    DiaPolar,
+    /// El sol no sale en todo el día.
+    NochePolar,
+}
+
+impl Luz {
+    /// Indica si hay luz en el `instante`, en milisegundos desde la época Unix.
+    pub fn contiene(&self, instante: u64) -> bool {
+        match *self {
+            Luz::Dia {
+                amanecer,
+                atardecer,
+            } => amanecer <= instante && instante <= atardecer,
+            Luz::DiaPolar => true,
+            Luz::NochePolar => false,
+        }
+    }
+}
+
+/// Ubicación geográfica de un centro de cultivo, en grados decimales.
+///
+/// Latitudes al sur del ecuador y longitudes al oeste de Greenwich son negativas.
+///
+/// # Ejemplo:
+///! ```
+///! let puerto_montt = Ubicacion::new(-41.47, -72.94)?;
+///! let luz = puerto_montt.get_luz(reloj.ahora());
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Ubicacion {
+    latitud: f64,
+    longitud: f64,
+}
+
+impl Ubicacion {
+    /// # Errores:
+    /// - `AgendaError::UbicacionInvalida`: La latitud no está entre -90 y 90 o la longitud entre -180 y 180.
+    pub fn new(latitud: f64, longitud: f64) -> Result<Self, AgendaError> {
+        if !(-90.0..=90.0).contains(&latitud) || !(-180.0..=180.0).contains(&longitud) {
+            return Err(AgendaError::UbicacionInvalida);
+        }
+        Ok(Self { latitud, longitud })
+    }
+
+    pub fn get_latitud(&self) -> f64 {
+        self.latitud
+    }
+
+    pub fn get_longitud(&self) -> f64 {
+        self.longitud
+    }
+
+    /// Calcula el amanecer y el atardecer del día que contiene el `instante`.
+    ///
+    /// Usa la ecuación del amanecer con la ecuación del tiempo, con un error de
+    /// alrededor de un minuto fuera de las zonas polares. Conviene pasar un instante
+    /// cercano al mediodía local para que el día calculado sea el día local.
+    pub fn get_luz(&self, instante: u64) -> Luz {
+        let juliano = instante as f64 / DIA_MS + JULIANO_UNIX;
+        let n = (juliano - JULIANO_2000 + 0.0008).round();
+
+        // Mediodía solar medio en la longitud de la ubicación
+        let medio = n - self.longitud / 360.0;
+        let anomalia = (357.5291 + 0.985_600_28 * medio).rem_euclid(360.0);
+        let m = anomalia.to_radians();
+        let centro = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+        let eclip = (anomalia + centro + 180.0 + 102.9372).rem_euclid(360.0);
+        let l = eclip.to_radians();
+        let transito = JULIANO_2000 + medio + 0.0053 * m.sin() - 0.0069 * (2.0 * l).sin();
+
+        let declinacion = (l.sin() * OBLICUIDAD.to_radians().sin()).asin();
+        let latitud = self.latitud.to_radians();
+        let coseno = (ALTURA_HORIZONTE.to_radians().sin() - latitud.sin() * declinacion.sin())
+            / (latitud.cos() * declinacion.cos());
+
+        if coseno < -1.0 {
+            return Luz::DiaPolar;
+        }
+        if coseno > 1.0 {
+            return Luz::NochePolar;
+        }
+        let semiarco = coseno.acos().to_degrees() / 360.0;
+        let instante = |juliano: f64| ((juliano - JULIANO_UNIX) * DIA_MS).round().max(0.0) as u64;
+        Luz::Dia {
+            amanecer: instante(transito - semiarco),
+            atardecer: instante(transito + semiarco),
+        }
+    }
+}
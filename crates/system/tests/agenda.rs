@@ -0,0 +1,406 @@
+use std::sync::Arc;
+
+use hardware::dosificador::Dosificador;
+use hardware::silo::Silo;
+use hardware::soplador::Soplador;
+use system::agenda::{Agenda, Ajuste, Hora, RegistroAgenda, Resultado, Ventana, DIA};
+use system::ciclo::Ciclo;
+use system::control::Fin;
+use system::errors::AgendaError;
+use system::estado::Fase;
+use system::racion::Racion;
+use system::sol::{Luz, Ubicacion};
+use types::unidades::{Kilogramos, Tiempo};
+use utils::executor::{bloquear, unir};
+use utils::reloj::{Espera, RelojManual, RelojRef};
+
+const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
+const HORA: u64 = 60 * 60 * 1000;
+
+/// Medianoche del 21 de junio de 2024 en Puerto Montt (UTC-4).
+const MEDIANOCHE_INVIERNO: u64 = 1_718_942_400_000;
+
+#[cfg(test)]
+mod agenda {
+    use super::*;
+    use types::unidades::KgPorSegundo;
+
+    fn hora(h: u32, m: u32) -> Hora {
+        Hora::new(h, m).unwrap()
+    }
+
+    fn crear_ciclo(pulsos: u32) -> Ciclo {
+        let mut ciclo = Ciclo::new();
+        ciclo
+            .set_pulsos(pulsos)
+            .expect("Ha intentado insertar pulsos fuera del rango permitido");
+        ciclo
+    }
+
+    fn crear_hardware(reloj: &RelojRef) -> (Soplador, Dosificador, Silo) {
+        let mut soplador = Soplador::new();
+        soplador.set_reloj(reloj.clone());
+        let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+        silo.set_alimento(CAPACIDAD_SILOS).unwrap();
+        (soplador, Dosificador::new(KgPorSegundo(2)).unwrap(), silo)
+    }
+
+    mod hora {
+        use super::*;
+
+        #[test]
+        fn test_valores() {
+            let h = hora(10, 30);
+            assert_eq!(h.get_hora(), 10);
+            assert_eq!(h.get_minuto(), 30);
+            assert_eq!(h.get_ms(), 10 * 3_600_000 + 30 * 60_000);
+            assert_eq!(h.to_string(), "10:30");
+            assert!(hora(7, 0) < hora(14, 0));
+        }
+
+        #[test]
+        fn test_fuera_de_rango() {
+            assert_eq!(Hora::new(24, 0), Err(AgendaError::HoraInvalida));
+            assert_eq!(Hora::new(7, 60), Err(AgendaError::HoraInvalida));
+            assert!(Hora::new(23, 59).is_ok());
+        }
+
+        #[test]
+        fn test_ventana() {
+            let ventana = Ventana::new(hora(6, 0), hora(18, 0)).unwrap();
+            assert!(ventana.contiene(hora(6, 0)));
+            assert!(ventana.contiene(hora(17, 59)));
+            assert!(!ventana.contiene(hora(18, 0)));
+            assert!(!ventana.contiene(hora(5, 59)));
+
+            assert_eq!(
+                Ventana::new(hora(18, 0), hora(6, 0)),
+                Err(AgendaError::VentanaInvalida)
+            );
+            assert_eq!(
+                Ventana::new(hora(6, 0), hora(6, 0)),
+                Err(AgendaError::VentanaInvalida)
+            );
+        }
+    }
+
+    mod siguiente {
+        use super::*;
+
+        #[test]
+        fn test_agenda_vacia() {
+            assert_eq!(Agenda::new().get_siguiente(0), None);
+        }
+
+        #[test]
+        fn test_orden_y_cambio_de_dia() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let mut agenda = Agenda::new();
+            agenda
+                .agregar(hora(14, 0), &racion)
+                .agregar(hora(7, 0), &racion)
+                .agregar(hora(10, 30), &racion);
+
+            let horas: Vec<Hora> = agenda.get_horarios().iter().map(|h| h.get_hora()).collect();
+            assert_eq!(horas, vec![hora(7, 0), hora(10, 30), hora(14, 0)]);
+
+            let dia = 3 * DIA;
+            assert_eq!(agenda.get_siguiente(dia), Some((dia + 7 * HORA, 0)));
+            assert_eq!(
+                agenda.get_siguiente(dia + 7 * HORA),
+                Some((dia + 7 * HORA, 0))
+            );
+            assert_eq!(
+                agenda.get_siguiente(dia + 8 * HORA),
+                Some((dia + 10 * HORA + 30 * 60_000, 1))
+            );
+            // Pasada la última se pasa al día siguiente
+            assert_eq!(
+                agenda.get_siguiente(dia + 15 * HORA),
+                Some((dia + DIA + 7 * HORA, 0))
+            );
+        }
+
+        #[test]
+        fn test_zona_horaria() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let mut agenda = Agenda::new();
+            agenda.agregar(hora(7, 0), &racion);
+
+            // UTC-3: las 07:00 locales son las 10:00 UTC
+            agenda.set_zona_horaria(-3 * 60).unwrap();
+            let dia = 3 * DIA;
+            assert_eq!(agenda.get_siguiente(dia), Some((dia + 10 * HORA, 0)));
+            assert_eq!(agenda.get_hora(dia + 10 * HORA), hora(7, 0));
+            // A las 02:00 UTC aún es el día anterior en la hora local
+            assert_eq!(agenda.get_hora(dia + 2 * HORA), hora(23, 0));
+
+            assert_eq!(
+                agenda.set_zona_horaria(15 * 60),
+                Err(AgendaError::ZonaHorariaInvalida)
+            );
+            assert_eq!(agenda.get_zona_horaria(), -3 * 60);
+        }
+    }
+
+    mod ejecucion {
+        use super::*;
+        use types::unidades::Milisegundos;
+
+        #[test]
+        fn test_dia_completo() {
+            let c1 = crear_ciclo(2);
+            let manana = Racion::new(vec![&c1]);
+            let tarde = Racion::new(vec![&c1, &c1]);
+
+            let inicio = 10 * DIA;
+            let reloj: RelojRef = Arc::new(RelojManual::new(inicio));
+            let (mut soplador, mut doser, mut silo) = crear_hardware(&reloj);
+
+            let mut agenda = Agenda::new();
+            agenda
+                .agregar(hora(7, 0), &manana)
+                .agregar(hora(10, 30), &manana)
+                .agregar(hora(14, 0), &tarde)
+                .agregar(hora(20, 0), &manana)
+                .agregar_ventana(Ventana::new(hora(6, 0), hora(18, 0)).unwrap())
+                .set_reloj(reloj.clone());
+
+            let registros: Vec<RegistroAgenda> =
+                agenda.iniciar(inicio + DIA - 1, &mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(registros.len(), 4);
+            let programados: Vec<u64> = registros.iter().map(|r| r.programado - inicio).collect();
+            assert_eq!(
+                programados,
+                vec![7 * HORA, 10 * HORA + 30 * 60_000, 14 * HORA, 20 * HORA]
+            );
+
+            for registro in &registros[..3] {
+                let Resultado::Ejecutado(reporte) = &registro.resultado else {
+                    panic!("No se ejecutó la ración de las {}", registro.hora);
+                };
+                assert_eq!(reporte.fin, Fin::Completado);
+                assert_eq!(reporte.inicio, Tiempo(registro.programado));
+            }
+            assert_eq!(registros[3].resultado, Resultado::FueraDeVentana);
+            assert!(reloj.ahora() >= inicio + 20 * HORA);
+            assert!(!soplador.get_estado());
+        }
+
+        #[test]
+        fn test_no_ejecuta_despues_del_limite() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(8 * HORA));
+            let (mut soplador, mut doser, mut silo) = crear_hardware(&reloj);
+
+            let mut agenda = Agenda::new();
+            agenda.agregar(hora(7, 0), &racion).set_reloj(reloj.clone());
+
+            // Las 07:00 ya pasaron: la siguiente es al otro día, después del límite
+            let registros = agenda.iniciar(DIA, &mut soplador, &mut doser, &mut silo);
+            assert!(registros.is_empty());
+            assert_eq!(reloj.ahora(), 8 * HORA);
+        }
+
+        #[test]
+        fn test_raciones_a_la_misma_hora_y_atraso() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let (mut soplador, mut doser, mut silo) = crear_hardware(&reloj);
+
+            // Cada ración toma algo más de un minuto por el descanso final: la segunda
+            // comienza con ese atraso y la tercera con el doble, sobre la tolerancia
+            let mut agenda = Agenda::new();
+            agenda
+                .agregar(hora(7, 0), &racion)
+                .agregar(hora(7, 0), &racion)
+                .agregar(hora(7, 0), &racion)
+                .set_tolerancia(Milisegundos(90_000))
+                .set_reloj(reloj.clone());
+
+            let registros = agenda.iniciar(8 * HORA, &mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(registros.len(), 3);
+            let Resultado::Ejecutado(primera) = &registros[0].resultado else {
+                panic!("No se ejecutó la primera ración");
+            };
+            let Resultado::Ejecutado(segunda) = &registros[1].resultado else {
+                panic!("No se ejecutó la segunda ración de las 07:00");
+            };
+            assert!(segunda.inicio >= primera.termino);
+            assert_eq!(registros[1].programado, 7 * HORA);
+            assert_eq!(registros[2].resultado, Resultado::Atrasado);
+        }
+
+        #[test]
+        fn test_control_de_la_racion_en_curso() {
+            let c1 = crear_ciclo(10);
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let (mut soplador, mut doser, mut silo) = crear_hardware(&reloj);
+
+            let mut agenda = Agenda::new();
+            agenda.agregar(hora(7, 0), &racion).set_reloj(reloj.clone());
+            let monitor = agenda.get_monitor();
+            let control = agenda.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::Pulso {
+                    Espera::ceder().await;
+                }
+                control.parada_emergencia();
+            };
+
+            let (registros, _) = bloquear(unir(
+                agenda.iniciar_async(8 * HORA, &mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(registros.len(), 1);
+            let Resultado::Ejecutado(reporte) = &registros[0].resultado else {
+                panic!("No se ejecutó la ración");
+            };
+            assert_eq!(reporte.fin, Fin::ParadaEmergencia);
+            assert_eq!(monitor.get().fase, Fase::Finalizado);
+        }
+    }
+    mod luz {
+        use super::*;
+
+        fn crear_agenda<'a>(racion: &'a Racion<'a>, ajuste: Ajuste) -> Agenda<'a> {
+            let mut agenda = Agenda::new();
+            agenda
+                .agregar(hora(6, 0), racion)
+                .agregar(hora(12, 0), racion)
+                .agregar(hora(19, 0), racion)
+                .set_ubicacion(Some(Ubicacion::new(-41.47, -72.94).unwrap()))
+                .set_ajuste(ajuste);
+            agenda.set_zona_horaria(-4 * 60).unwrap();
+            agenda
+        }
+
+        fn instantes(agenda: &Agenda) -> Vec<u64> {
+            (0..3)
+                .scan(MEDIANOCHE_INVIERNO, |desde, _| {
+                    let (instante, _) = agenda.get_siguiente(*desde)?;
+                    *desde = instante + 1;
+                    Some(instante)
+                })
+                .collect()
+        }
+
+        fn luz(agenda: &Agenda) -> (u64, u64) {
+            match agenda.get_luz(MEDIANOCHE_INVIERNO) {
+                Some(Luz::Dia {
+                    amanecer,
+                    atardecer,
+                }) => (amanecer, atardecer),
+                otra => panic!("Se esperaba un día con amanecer y atardecer: {:?}", otra),
+            }
+        }
+
+        #[test]
+        fn test_luz_del_dia_local() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let agenda = crear_agenda(&racion, Ajuste::Omitir);
+
+            // En invierno amanece cerca de las 08:18 y atardece cerca de las 17:28
+            let (amanecer, atardecer) = luz(&agenda);
+            assert_eq!(agenda.get_hora(amanecer).get_hora(), 8);
+            assert_eq!(agenda.get_hora(atardecer).get_hora(), 17);
+            assert_eq!(Agenda::new().get_luz(MEDIANOCHE_INVIERNO), None);
+        }
+
+        #[test]
+        fn test_omitir_fuera_de_la_luz() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let mut agenda = crear_agenda(&racion, Ajuste::Omitir);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(MEDIANOCHE_INVIERNO));
+            agenda.set_reloj(reloj.clone());
+            let (mut soplador, mut doser, mut silo) = crear_hardware(&reloj);
+
+            let registros = agenda.iniciar(
+                MEDIANOCHE_INVIERNO + DIA - 1,
+                &mut soplador,
+                &mut doser,
+                &mut silo,
+            );
+
+            assert_eq!(
+                instantes(&agenda),
+                vec![
+                    MEDIANOCHE_INVIERNO + 6 * HORA,
+                    MEDIANOCHE_INVIERNO + 12 * HORA,
+                    MEDIANOCHE_INVIERNO + 19 * HORA
+                ]
+            );
+            assert_eq!(registros.len(), 3);
+            assert_eq!(registros[0].resultado, Resultado::FueraDeVentana);
+            assert!(matches!(registros[1].resultado, Resultado::Ejecutado(_)));
+            assert_eq!(registros[2].resultado, Resultado::FueraDeVentana);
+        }
+
+        #[test]
+        fn test_recortar_a_la_luz() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let agenda = crear_agenda(&racion, Ajuste::Recortar);
+            let (amanecer, atardecer) = luz(&agenda);
+
+            assert_eq!(
+                instantes(&agenda),
+                vec![amanecer, MEDIANOCHE_INVIERNO + 12 * HORA, atardecer]
+            );
+        }
+
+        #[test]
+        fn test_escalar_a_la_luz() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let referencia = Ventana::new(hora(6, 0), hora(18, 0)).unwrap();
+            let agenda = crear_agenda(&racion, Ajuste::Escalar(referencia));
+            let (amanecer, atardecer) = luz(&agenda);
+
+            // Las 12:00 están a mitad de la referencia y pasan a mitad de la luz del día;
+            // las 19:00 quedan fuera de la referencia y pasan al atardecer
+            assert_eq!(
+                instantes(&agenda),
+                vec![amanecer, amanecer + (atardecer - amanecer) / 2, atardecer]
+            );
+        }
+
+        #[test]
+        fn test_noche_polar() {
+            let c1 = crear_ciclo(1);
+            let racion = Racion::new(vec![&c1]);
+            let mut agenda = crear_agenda(&racion, Ajuste::Recortar);
+            agenda.set_ubicacion(Some(Ubicacion::new(-75.0, -72.94).unwrap()));
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(MEDIANOCHE_INVIERNO));
+            agenda.set_reloj(reloj.clone());
+            let (mut soplador, mut doser, mut silo) = crear_hardware(&reloj);
+
+            let registros = agenda.iniciar(
+                MEDIANOCHE_INVIERNO + DIA - 1,
+                &mut soplador,
+                &mut doser,
+                &mut silo,
+            );
+            assert_eq!(registros.len(), 3);
+            assert!(registros
+                .iter()
+                .all(|r| r.resultado == Resultado::FueraDeVentana));
+        }
+    }
+}
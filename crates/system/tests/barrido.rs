@@ -0,0 +1,56 @@
+use system::barrido::{Barrido, LARGO_MAX, MARGEN_MAX, VELOCIDAD_MIN};
+use system::errors::BarridoError;
+
+#[cfg(test)]
+mod barrido {
+    use super::*;
+    use types::unidades::{Metros, MetrosPorSegundo, Milisegundos, Porcentaje};
+
+    #[test]
+    fn test_valores_iniciales() {
+        let barrido = Barrido::new();
+        assert_eq!(barrido.get_potencia(), Porcentaje(100));
+        assert_eq!(barrido.get_largo(), Metros(1));
+        assert_eq!(barrido.get_velocidad(), MetrosPorSegundo(20));
+        assert_eq!(barrido.get_margen(), 50);
+    }
+
+    #[test]
+    fn test_duracion_segun_largo() {
+        let mut barrido = Barrido::new();
+        barrido.set_largo(Metros(400)).unwrap();
+        barrido.set_velocidad(MetrosPorSegundo(20)).unwrap();
+
+        barrido.set_margen(0).unwrap();
+        assert_eq!(barrido.get_duracion(), Milisegundos(20000));
+        barrido.set_margen(50).unwrap();
+        assert_eq!(barrido.get_duracion(), Milisegundos(30000));
+
+        // La línea más larga al pellet más lento con el mayor margen cabe en u32
+        barrido.set_largo(LARGO_MAX).unwrap();
+        barrido.set_velocidad(VELOCIDAD_MIN).unwrap();
+        barrido.set_margen(MARGEN_MAX).unwrap();
+        assert_eq!(barrido.get_duracion(), Milisegundos(2_400_000));
+    }
+
+    #[test]
+    fn test_fuera_de_rango() {
+        let mut barrido = Barrido::new();
+        assert_eq!(
+            barrido.set_potencia(Porcentaje(0)),
+            Err(BarridoError::PotenciaFueraDeRango)
+        );
+        assert_eq!(
+            barrido.set_largo(Metros(0)),
+            Err(BarridoError::LargoFueraDeRango)
+        );
+        assert_eq!(
+            barrido.set_velocidad(MetrosPorSegundo(VELOCIDAD_MIN.0 - 1)),
+            Err(BarridoError::VelocidadFueraDeRango)
+        );
+        assert_eq!(
+            barrido.set_margen(MARGEN_MAX + 1),
+            Err(BarridoError::MargenFueraDeRango)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use hardware::dosificador::Dosificador;
 use system::ciclo::Ciclo;
 
 // #[cfg(test)]
@@ -138,6 +139,7 @@ mod ciclo {
 
     mod set_pulso_duracion {
         use system::ciclo::{DURACION_MAX, DURACION_MIN};
+        use types::unidades::Milisegundos;
 
         use super::*;
 
@@ -174,7 +176,7 @@ mod ciclo {
                 DURACION_MIN
             );
 
-            let response = ciclo.set_pulso_duracion(DURACION_MIN - 1);
+            let response = ciclo.set_pulso_duracion(DURACION_MIN - Milisegundos(1));
 
             assert_eq!(
                 response,
@@ -194,7 +196,7 @@ mod ciclo {
                 DURACION_MIN
             );
 
-            let response = ciclo.set_pulso_duracion(DURACION_MAX + 1);
+            let response = ciclo.set_pulso_duracion(DURACION_MAX + Milisegundos(1));
 
             assert_eq!(
                 response,
@@ -206,6 +208,7 @@ mod ciclo {
 
     mod set_pulso_espera {
         use system::ciclo::{ESPERA_MAX, ESPERA_MIN};
+        use types::unidades::Milisegundos;
 
         use super::*;
 
@@ -213,21 +216,33 @@ mod ciclo {
         fn test_establece_duracion() {
             let mut ciclo = Ciclo::new();
 
-            assert_eq!(ciclo.get_pulso_espera(), 1000, "Los pulsos no son mil");
+            assert_eq!(
+                ciclo.get_pulso_espera(),
+                Milisegundos(1000),
+                "Los pulsos no son mil"
+            );
 
             ciclo
-                .set_pulso_espera(10000)
+                .set_pulso_espera(Milisegundos(10000))
                 .expect("Ha intentado establecer una espera fuera del rango");
-            assert_eq!(ciclo.get_pulso_espera(), 10000, "Los pulsos no son 10000");
+            assert_eq!(
+                ciclo.get_pulso_espera(),
+                Milisegundos(10000),
+                "Los pulsos no son 10000"
+            );
         }
 
         #[test]
         fn test_bajo_el_rango() {
             let mut ciclo = Ciclo::new();
 
-            assert_eq!(ciclo.get_pulso_espera(), 1000, "La espera no es mil");
+            assert_eq!(
+                ciclo.get_pulso_espera(),
+                Milisegundos(1000),
+                "La espera no es mil"
+            );
 
-            let response = ciclo.set_pulso_espera(999);
+            let response = ciclo.set_pulso_espera(Milisegundos(999));
 
             assert_eq!(
                 response,
@@ -242,7 +257,7 @@ mod ciclo {
 
             assert_eq!(ciclo.get_pulso_espera(), ESPERA_MIN, "La espera no es mil");
 
-            let response = ciclo.set_pulso_espera(ESPERA_MAX + 1);
+            let response = ciclo.set_pulso_espera(ESPERA_MAX + Milisegundos(1));
 
             assert_eq!(
                 response,
@@ -267,8 +282,8 @@ mod ciclo {
         let b = Ciclo::new().get_pulso_espera();
 
         assert!(
-            type_name_of_val(&b).contains("u32"),
-            "La espera no es de tipo u32"
+            type_name_of_val(&b).contains("Milisegundos"),
+            "La espera no es de tipo Milisegundos"
         );
     }
 
@@ -277,8 +292,8 @@ mod ciclo {
         let b = Ciclo::new().get_pulso_duracion();
 
         assert!(
-            type_name_of_val(&b).contains("u32"),
-            "La duración no es de tipo u32"
+            type_name_of_val(&b).contains("Milisegundos"),
+            "La duración no es de tipo Milisegundos"
         );
     }
 
@@ -286,6 +301,7 @@ mod ciclo {
         use super::{assert_return_type, Ciclo};
         use system::ciclo::CicloData;
         use types::rango::RangoData;
+        use types::unidades::Milisegundos;
 
         mod tipos {
             use super::*;
@@ -305,7 +321,7 @@ mod ciclo {
 
                 let b = ciclo.get_all();
 
-                assert_return_type::<RangoData>(&b.duracion);
+                assert_return_type::<RangoData<Milisegundos>>(&b.duracion);
             }
 
             #[test]
@@ -314,7 +330,7 @@ mod ciclo {
 
                 let b = ciclo.get_all();
 
-                assert_return_type::<RangoData>(&b.espera);
+                assert_return_type::<RangoData<Milisegundos>>(&b.espera);
             }
 
             #[test]
@@ -343,8 +359,8 @@ mod ciclo {
 
                 let b = ciclo.get_all();
 
-                assert_return_type::<u32>(&b.duracion.min);
-                assert_return_type::<u32>(&b.duracion.max);
+                assert_return_type::<Milisegundos>(&b.duracion.min);
+                assert_return_type::<Milisegundos>(&b.duracion.max);
             }
 
             #[test]
@@ -353,8 +369,8 @@ mod ciclo {
 
                 let b = ciclo.get_all();
 
-                assert_return_type::<u32>(&b.espera.min);
-                assert_return_type::<u32>(&b.espera.max);
+                assert_return_type::<Milisegundos>(&b.espera.min);
+                assert_return_type::<Milisegundos>(&b.espera.max);
             }
         }
 
@@ -413,4 +429,212 @@ mod ciclo {
             }
         }
     }
+
+    mod pulsos_entregados {
+        use super::*;
+        use types::unidades::{Gramos, GramosPorSegundo, KgPorSegundo, Milisegundos};
+
+        const ENTREGA: KgPorSegundo = KgPorSegundo(2);
+
+        fn crear_dosificador() -> Dosificador {
+            Dosificador::new(ENTREGA).unwrap()
+        }
+
+        fn crear_ciclo(pulsos: u32, duracion: Milisegundos) -> Ciclo {
+            let mut ciclo = Ciclo::new();
+            ciclo
+                .set_pulsos(pulsos)
+                .expect("Ha intentado establecer una cantidad de pulsos fuera del rango");
+            ciclo
+                .set_pulso_duracion(duracion)
+                .expect("Ha intentado establecer una duracion fuera del rango");
+            ciclo
+        }
+
+        #[test]
+        fn test_sin_purga_por_defecto() {
+            let ciclo = Ciclo::new();
+
+            assert!(!ciclo.get_pulso_purga(), "La purga está activa por defecto");
+            assert_eq!(ciclo.get_total_pulsos(), 0);
+        }
+
+        #[test]
+        fn test_pulsos_exactos() {
+            let ciclo = crear_ciclo(50, Milisegundos(5000));
+
+            assert_eq!(
+                ciclo.get_total_pulsos(),
+                50,
+                "El ciclo no entrega 50 pulsos"
+            );
+            // 50 pulsos de 5 s a 2 kg/s
+            assert_eq!(
+                ciclo.calcular_entrega(&crear_dosificador()),
+                Ok(Gramos(500_000))
+            );
+        }
+
+        #[test]
+        fn test_pulso_de_purga() {
+            let mut ciclo = crear_ciclo(50, Milisegundos(5000));
+            ciclo.set_pulso_purga(true);
+
+            assert_eq!(ciclo.get_pulsos(), 50, "La purga modificó los pulsos");
+            assert_eq!(ciclo.get_total_pulsos(), 51);
+            assert_eq!(
+                ciclo.calcular_entrega(&crear_dosificador()),
+                Ok(Gramos(510_000))
+            );
+        }
+
+        #[test]
+        fn test_cero_pulsos_no_entrega() {
+            let ciclo = crear_ciclo(0, Milisegundos(5000));
+
+            assert_eq!(ciclo.calcular_entrega(&crear_dosificador()), Ok(Gramos(0)));
+        }
+
+        #[test]
+        fn test_pulsos_fraccionarios() {
+            let ciclo = crear_ciclo(10, Milisegundos(1500));
+
+            // 10 pulsos de 1,5 s a 2 kg/s: no se trunca a segundos enteros
+            assert_eq!(
+                ciclo.calcular_entrega(&crear_dosificador()),
+                Ok(Gramos(30_000))
+            );
+            // 10 pulsos de 1,5 s a 1,5 kg/s
+            assert_eq!(
+                ciclo.calcular_entrega(&Dosificador::new_gramos(GramosPorSegundo(1500)).unwrap()),
+                Ok(Gramos(22_500))
+            );
+        }
+
+        #[test]
+        fn test_entrega_desbordada() {
+            use system::ciclo::{DURACION_MAX, PULSOS_MAX};
+
+            let mut ciclo = crear_ciclo(PULSOS_MAX, DURACION_MAX);
+            let doser = Dosificador::new_gramos(GramosPorSegundo(50_000)).unwrap();
+
+            // 10000 pulsos de 10 s a 50 kg/s son 5000 t, más que u32::MAX gramos
+            assert_eq!(
+                ciclo.calcular_entrega(&doser),
+                Err(CicloError::EntregaDesbordada)
+            );
+            ciclo.set_pulso_purga(true);
+            assert_eq!(
+                ciclo.calcular_entrega(&doser),
+                Err(CicloError::EntregaDesbordada)
+            );
+        }
+    }
+
+    mod por_masa {
+        use super::*;
+        use types::unidades::{Gramos, GramosPorSegundo, KgPorSegundo, Milisegundos};
+
+        use hardware::calibracion::Curva;
+        use system::ciclo::{DURACION_MAX, DURACION_MIN, ESPERA_MAX, ESPERA_MIN, PULSOS_MAX};
+        use system::errors::CicloError;
+
+        #[test]
+        fn test_masa_exacta() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            let ciclo = Ciclo::por_masa(Gramos(100_000), &doser, None).unwrap();
+
+            // 5 pulsos de 10 s a 2 kg/s
+            assert_eq!(ciclo.get_pulsos(), 5);
+            assert_eq!(ciclo.get_pulso_duracion(), DURACION_MAX);
+            assert_eq!(ciclo.get_pulso_espera(), ESPERA_MIN);
+            assert_eq!(ciclo.calcular_entrega(&doser).unwrap(), Gramos(100_000));
+        }
+
+        #[test]
+        fn test_redondea_hacia_arriba() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            let ciclo = Ciclo::por_masa(Gramos(125_000), &doser, None).unwrap();
+
+            assert_eq!(ciclo.get_pulsos(), 7);
+            assert_eq!(ciclo.get_pulso_duracion(), Milisegundos(8929));
+            let entrega = ciclo.calcular_entrega(&doser).unwrap();
+            assert!(entrega >= Gramos(125_000), "Se entregó menos de lo pedido");
+            assert!(
+                entrega - Gramos(125_000) < Gramos(ciclo.get_pulsos() * 2),
+                "El exceso es mayor a un ms por pulso"
+            );
+        }
+
+        #[test]
+        fn test_usa_la_curva_calibrada() {
+            let mut doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            doser.set_curva(Some(Curva::new(GramosPorSegundo(1800), -150)));
+            let ciclo = Ciclo::por_masa(Gramos(50_000), &doser, None).unwrap();
+
+            assert!(ciclo.calcular_entrega(&doser).unwrap() >= Gramos(50_000));
+            assert!(ciclo.get_pulso_duracion() >= DURACION_MIN);
+            assert!(ciclo.get_pulso_duracion() <= DURACION_MAX);
+        }
+
+        #[test]
+        fn test_caudal_maximo() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            // 100 kg a no más de 1 kg/s: cada pulso de 20 kg necesita 20 s entre inicios
+            let ciclo =
+                Ciclo::por_masa(Gramos(100_000), &doser, Some(GramosPorSegundo(1000))).unwrap();
+
+            let gramos = doser.calcular_gramos(ciclo.get_pulso_duracion());
+            let periodo = ciclo.get_pulso_duracion() + ciclo.get_pulso_espera();
+            assert!(ciclo.get_pulso_espera() <= ESPERA_MAX);
+            assert!(
+                u64::from(gramos.0) * 1000 / u64::from(periodo.0) <= 1000,
+                "El caudal promedio supera el máximo"
+            );
+            assert!(ciclo.calcular_entrega(&doser).unwrap() >= Gramos(100_000));
+        }
+
+        #[test]
+        fn test_caudal_reparte_en_mas_pulsos() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            // Con pulsos de 20 kg la espera necesaria supera ESPERA_MAX
+            let ciclo =
+                Ciclo::por_masa(Gramos(100_000), &doser, Some(GramosPorSegundo(500))).unwrap();
+
+            assert!(ciclo.get_pulsos() > 5, "No se repartió en más pulsos");
+            assert!(ciclo.get_pulso_espera() <= ESPERA_MAX);
+        }
+
+        #[test]
+        fn test_fuera_de_rango() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+
+            // Menos que un pulso de DURACION_MIN
+            assert_eq!(
+                Ciclo::por_masa(Gramos(1000), &doser, None),
+                Err(CicloError::MasaFueraDeRango)
+            );
+            // Más que PULSOS_MAX pulsos de DURACION_MAX
+            assert_eq!(
+                Ciclo::por_masa(Gramos(PULSOS_MAX * 20_000 + 1), &doser, None),
+                Err(CicloError::MasaFueraDeRango)
+            );
+            assert_eq!(
+                Ciclo::por_masa(Gramos(100_000), &doser, Some(GramosPorSegundo(0))),
+                Err(CicloError::CaudalFueraDeRango)
+            );
+            assert_eq!(
+                Ciclo::por_masa(Gramos(100_000), &doser, Some(GramosPorSegundo(1))),
+                Err(CicloError::CaudalFueraDeRango)
+            );
+        }
+
+        #[test]
+        fn test_masa_cero() {
+            let ciclo =
+                Ciclo::por_masa(Gramos(0), &Dosificador::new(KgPorSegundo(2)).unwrap(), None)
+                    .unwrap();
+            assert_eq!(ciclo.get_total_pulsos(), 0);
+        }
+    }
 }
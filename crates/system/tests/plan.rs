@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use hardware::dosificador::Dosificador;
+use hardware::errors::SopladorError;
+use hardware::logs::{Print, Registro};
+use hardware::selectora::Selectora;
+use hardware::silo::Silo;
+use hardware::traits::Soplar;
+use system::ciclo::Ciclo;
+use system::control::Fin;
+use system::plan::{Jaula, Modo, Plan, Turno};
+use system::racion::Racion;
+use types::unidades::{Kilogramos, Porcentaje, Tiempo};
+use utils::reloj::{RelojManual, RelojRef};
+use uuid::Uuid;
+
+const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
+
+/// Soplador de prueba que cuenta cuántas veces se enciende.
+struct SopladorContador {
+    estado: bool,
+    encendidos: u32,
+}
+
+impl Print for SopladorContador {
+    fn texto(&self) -> String {
+        String::from("[SopladorContador]")
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("SopladorContador")
+    }
+}
+
+impl Soplar for SopladorContador {
+    fn set_estado(&mut self, n: bool) -> &mut Self {
+        if n {
+            self.encendidos += 1;
+        }
+        self.estado = n;
+        self
+    }
+
+    fn set_potencia(&mut self, _n: Porcentaje) -> Result<(), SopladorError> {
+        Ok(())
+    }
+
+    fn get_estado(&self) -> bool {
+        self.estado
+    }
+
+    fn get_potencia(&self) -> Porcentaje {
+        Porcentaje(0)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Uuid::nil()
+    }
+}
+
+#[cfg(test)]
+mod plan {
+    use super::*;
+
+    fn crear_ciclo(pulsos: u32) -> Ciclo {
+        let mut ciclo = Ciclo::new();
+        ciclo
+            .set_pulsos(pulsos)
+            .expect("Ha intentado insertar pulsos fuera del rango permitido");
+        ciclo
+    }
+
+    fn salidas(turnos: &[Turno]) -> Vec<u32> {
+        turnos.iter().map(|turno| turno.salida).collect()
+    }
+
+    mod secuencia {
+        use super::*;
+
+        #[test]
+        fn test_rotativo() {
+            let c1 = crear_ciclo(2);
+            let dos = Racion::new(vec![&c1, &c1]);
+            let uno = Racion::new(vec![&c1]);
+
+            let mut plan = Plan::new(Modo::Rotativo);
+            plan.agregar(Jaula::new("J-A", 4, &dos))
+                .agregar(Jaula::new("J-B", 1, &dos))
+                .agregar(Jaula::new("J-C", 2, &uno));
+
+            // Parte en la salida actual y recorre en orden ascendente, volviendo a la 1
+            let turnos = plan.get_secuencia(2);
+            assert_eq!(salidas(&turnos), vec![2, 4, 1, 4, 1]);
+            assert_eq!(
+                turnos[0],
+                Turno {
+                    jaula: 2,
+                    ciclo: 0,
+                    salida: 2
+                }
+            );
+            assert_eq!(turnos[3].ciclo, 1);
+            assert_eq!(Plan::contar_movimientos(&turnos, 2), 4);
+        }
+
+        #[test]
+        fn test_prioridad() {
+            let c1 = crear_ciclo(2);
+            let racion = Racion::new(vec![&c1, &c1, &c1]);
+
+            let mut urgente = Jaula::new("J-A", 5, &racion);
+            urgente.set_prioridad(10);
+            let mut plan = Plan::new(Modo::Prioridad);
+            plan.agregar(Jaula::new("J-B", 0, &racion))
+                .agregar(urgente)
+                .agregar(Jaula::new("J-C", 3, &racion));
+
+            let turnos = plan.get_secuencia(0);
+            assert_eq!(salidas(&turnos), vec![5, 5, 5, 0, 0, 0, 3, 3, 3]);
+            assert_eq!(
+                Plan::contar_movimientos(&turnos, 0),
+                3,
+                "La selectora se movió más de una vez por jaula"
+            );
+        }
+
+        #[test]
+        fn test_jaulas_en_la_misma_salida() {
+            let c1 = crear_ciclo(2);
+            let racion = Racion::new(vec![&c1]);
+
+            let mut plan = Plan::new(Modo::Rotativo);
+            plan.agregar(Jaula::new("J-A", 2, &racion))
+                .agregar(Jaula::new("J-B", 0, &racion))
+                .agregar(Jaula::new("J-C", 2, &racion));
+
+            let turnos = plan.get_secuencia(0);
+            assert_eq!(salidas(&turnos), vec![0, 2, 2]);
+            assert_eq!(Plan::contar_movimientos(&turnos, 0), 1);
+        }
+
+        #[test]
+        fn test_plan_vacio() {
+            let plan = Plan::new(Modo::Rotativo);
+            assert!(plan.get_secuencia(0).is_empty());
+        }
+    }
+
+    mod ejecucion {
+        use super::*;
+        use types::unidades::KgPorSegundo;
+
+        #[test]
+        fn test_alimenta_cada_jaula() {
+            let c1 = crear_ciclo(2);
+            let c2 = crear_ciclo(3);
+            let racion_a = Racion::new(vec![&c1, &c2]);
+            let racion_b = Racion::new(vec![&c2]);
+
+            let mut plan = Plan::new(Modo::Rotativo);
+            plan.agregar(Jaula::new("J-A", 1, &racion_a))
+                .agregar(Jaula::new("J-B", 3, &racion_b));
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = SopladorContador {
+                estado: false,
+                encendidos: 0,
+            };
+            let mut doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(CAPACIDAD_SILOS).unwrap();
+            let mut selectora = Selectora::new(5);
+
+            // El descanso de J-A transcurre con el soplador encendido
+            let reporte = plan
+                .get_programa(selectora.get_posicion())
+                .set_reloj(reloj.clone())
+                .set_mantener_encendido(racion_a.get_ciclo_espera())
+                .iniciar_en(&mut soplador, &mut doser, &mut silo, &mut selectora);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert_eq!(reporte.racion, plan.get_id());
+            assert_eq!(
+                reporte.get_entregado_en(1),
+                c1.calcular_entrega(&doser).unwrap() + c2.calcular_entrega(&doser).unwrap()
+            );
+            assert_eq!(
+                reporte.get_entregado_en(3),
+                c2.calcular_entrega(&doser).unwrap()
+            );
+            assert_eq!(
+                soplador.encendidos, 1,
+                "El soplador se reinició entre jaulas"
+            );
+            assert_eq!(selectora.get_posicion(), 1);
+
+            // El descanso de J-A se cuenta desde el término de su primer ciclo
+            let [primero, _, segundo] = &reporte.ciclos[..] else {
+                panic!("Se esperaban 3 ciclos");
+            };
+            assert!(
+                segundo.inicio >= primero.termino + Tiempo::from(racion_a.get_ciclo_espera()),
+                "No se respetó el descanso de la jaula"
+            );
+            // Sin descanso al final del plan
+            assert!(reporte.termino < segundo.termino + Tiempo::from(racion_a.get_ciclo_espera()));
+            assert_eq!(reporte.termino, Tiempo(reloj.ahora()));
+        }
+    }
+}
@@ -1,3 +1,207 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use std::sync::Mutex;
+
+use hardware::calibracion::Curva;
+use hardware::dosificador::Dosificador;
+use hardware::errors::{SelectoraError, SopladorError};
+use hardware::logs::{Print, Registro};
+use hardware::selectora::Selectora;
+use hardware::silo::Silo;
+use hardware::soplador::Soplador;
+use hardware::traits::{Dosificar, Seleccionar, Soplar};
+use system::barrido::Barrido;
+use system::ciclo::Ciclo;
+use system::control::{Fin, Orden};
+use system::estado::Fase;
+use system::programa::{Programa, ESPERA_SOPLADOR_MAX};
+use system::racion::Racion;
+use system::reporte::Alarma;
+use types::rango::RangoData;
+use types::unidades::{
+    Gramos, GramosPorSegundo, KgPorSegundo, Kilogramos, Metros, MetrosPorSegundo, Milisegundos,
+    Porcentaje, Tiempo,
+};
+use utils::executor::{bloquear, unir};
+use utils::reloj::{Espera, Reloj, RelojAcelerado, RelojManual, RelojRef};
+use uuid::Uuid;
+
+const CAPACIDAD_SILOS: Kilogramos = Kilogramos(24000);
+const ENTREGA: KgPorSegundo = KgPorSegundo(2);
+const ESPERA_SELECTORA: Milisegundos = Milisegundos(20000);
+
+/// Registro compartido de las acciones que reciben los dobles de prueba.
+type Bitacora = Arc<Mutex<Vec<&'static str>>>;
+
+/// Soplador de prueba que anota cada cambio de estado en la bitácora.
+struct SopladorFalso {
+    estado: bool,
+    bitacora: Bitacora,
+}
+
+impl Print for SopladorFalso {
+    fn texto(&self) -> String {
+        String::from("[SopladorFalso]")
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("SopladorFalso")
+    }
+}
+
+impl Soplar for SopladorFalso {
+    fn set_estado(&mut self, n: bool) -> &mut Self {
+        self.bitacora
+            .lock()
+            .unwrap()
+            .push(if n { "soplador:on" } else { "soplador:off" });
+        self.estado = n;
+        self
+    }
+
+    fn set_potencia(&mut self, _n: Porcentaje) -> Result<(), SopladorError> {
+        Ok(())
+    }
+
+    fn get_estado(&self) -> bool {
+        self.estado
+    }
+
+    fn get_potencia(&self) -> Porcentaje {
+        Porcentaje(0)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Uuid::nil()
+    }
+}
+
+/// Soplador de prueba que enciende pero nunca termina su rampa.
+struct SopladorTrabado {
+    estado: bool,
+}
+
+impl Print for SopladorTrabado {
+    fn texto(&self) -> String {
+        String::from("[SopladorTrabado]")
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("SopladorTrabado")
+    }
+}
+
+impl Soplar for SopladorTrabado {
+    fn set_estado(&mut self, n: bool) -> &mut Self {
+        self.estado = n;
+        self
+    }
+
+    fn set_potencia(&mut self, _n: Porcentaje) -> Result<(), SopladorError> {
+        Ok(())
+    }
+
+    fn get_estado(&self) -> bool {
+        self.estado
+    }
+
+    fn get_potencia(&self) -> Porcentaje {
+        Porcentaje(0)
+    }
+
+    fn get_id(&self) -> Uuid {
+        Uuid::nil()
+    }
+
+    fn is_listo(&self) -> bool {
+        false
+    }
+}
+
+/// Dosificador de prueba que anota cada apertura y cierre en la bitácora.
+struct DosificadorFalso {
+    estado: bool,
+    bitacora: Bitacora,
+}
+
+impl Print for DosificadorFalso {
+    fn texto(&self) -> String {
+        String::from("[DosificadorFalso]")
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("DosificadorFalso")
+    }
+}
+
+impl Dosificar for DosificadorFalso {
+    fn set_estado(&mut self, n: bool) -> &mut Self {
+        self.bitacora
+            .lock()
+            .unwrap()
+            .push(if n { "doser:on" } else { "doser:off" });
+        self.estado = n;
+        self
+    }
+
+    fn get_entrega(&self) -> KgPorSegundo {
+        ENTREGA
+    }
+
+    fn get_estado(&self) -> bool {
+        self.estado
+    }
+
+    fn get_id(&self) -> Uuid {
+        Uuid::nil()
+    }
+}
+
+/// Selectora de prueba que anota cada movimiento en la bitácora.
+/// Si está `atascada` acepta la orden pero no cambia de posición.
+struct SelectoraFalsa {
+    posicion: u32,
+    atascada: bool,
+    bitacora: Bitacora,
+}
+
+impl Print for SelectoraFalsa {
+    fn texto(&self) -> String {
+        String::from("[SelectoraFalsa]")
+    }
+
+    fn registro(&self) -> Registro {
+        Registro::new("SelectoraFalsa")
+    }
+}
+
+impl Seleccionar for SelectoraFalsa {
+    fn set_posicion(&mut self, n: u32) -> Result<(), SelectoraError> {
+        self.bitacora.lock().unwrap().push("selectora");
+        if !self.atascada {
+            self.posicion = n;
+        }
+        Ok(())
+    }
+
+    fn get_posicion(&self) -> u32 {
+        self.posicion
+    }
+
+    fn get_posiciones(&self) -> RangoData {
+        RangoData { min: 0, max: 5 }
+    }
+
+    fn get_espera(&self) -> Milisegundos {
+        ESPERA_SELECTORA
+    }
+
+    fn get_id(&self) -> Uuid {
+        Uuid::nil()
+    }
+}
+
 // #[cfg(test)]
 // mod avisos {
 //     // Si no hay comentarios en ignore comentar este modulo
@@ -15,4 +219,1172 @@
 // }
 
 #[cfg(test)]
-mod programa {}
+mod programa {
+    use super::*;
+
+    // Función auxiliar para crear un ciclo con valores predeterminados
+    fn crear_ciclo(pulsos: u32, duracion: Milisegundos, espera: Milisegundos) -> Ciclo {
+        let mut ciclo = Ciclo::new();
+        ciclo
+            .set_pulsos(pulsos)
+            .expect("Ha intentado insertar pulsos fuera del rango permitido");
+        ciclo
+            .set_pulso_duracion(duracion)
+            .expect("Ha intentado establecer una duracion fuera del rango permitido");
+        ciclo
+            .set_pulso_espera(espera)
+            .expect("Ha intentado establecer una espera fuera del rango permitido");
+        ciclo
+    }
+
+    fn crear_silo() -> Silo {
+        let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+        silo.set_alimento(CAPACIDAD_SILOS)
+            .expect("Ha intentado ingresar mas alimento que la capacidad del silo");
+        silo
+    }
+
+    fn crear_falsos() -> (Bitacora, SopladorFalso, DosificadorFalso) {
+        let bitacora: Bitacora = Arc::new(Mutex::new(Vec::new()));
+        let soplador = SopladorFalso {
+            estado: false,
+            bitacora: bitacora.clone(),
+        };
+        let doser = DosificadorFalso {
+            estado: false,
+            bitacora: bitacora.clone(),
+        };
+        (bitacora, soplador, doser)
+    }
+
+    mod reloj {
+        use super::*;
+
+        #[test]
+        fn test_racion_completa_en_tiempo_virtual() {
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
+
+            let mut racion = Racion::new(vec![&c1, &c2, &c1, &c1]);
+            racion
+                .set_ciclo_espera(Milisegundos(60000))
+                .expect("Ha intentado establecer una espera fuera de rango");
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let inicio = Instant::now();
+            Programa::new(&racion).set_reloj(reloj.clone()).iniciar(
+                &mut soplador,
+                &mut doser,
+                &mut silo,
+            );
+
+            assert!(
+                inicio.elapsed().as_millis() < 1000,
+                "La ración ha esperado en tiempo real"
+            );
+            // Al menos los pulsos de cada ciclo y la espera entre ciclos
+            let minimo = 3 * 50 * (5000 + 8000) + 20 * (3000 + 4000) + 4 * 60000;
+            assert!(reloj.ahora() >= minimo, "El tiempo virtual no ha avanzado");
+            assert!(
+                silo.get_alimento() < CAPACIDAD_SILOS,
+                "No se ha entregado alimento"
+            );
+        }
+
+        #[test]
+        fn test_encendido_usa_el_reloj() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+
+            soplador.set_reloj(reloj.clone()).set_estado(true);
+
+            // El encendido no bloquea: el soplador queda listo al terminar la rampa
+            assert_eq!(reloj.ahora(), 0);
+            assert!(!soplador.is_listo());
+            reloj.avanzar(u64::from(hardware::soplador::TIEMPO_DE_ENCENDIDO.0));
+            assert!(soplador.is_listo());
+        }
+
+        #[test]
+        fn test_espera_que_el_soplador_quede_listo() {
+            let c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj);
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert!(
+                reporte.ciclos[0].termino
+                    >= reporte.ciclos[0].inicio
+                        + Tiempo::from(hardware::soplador::TIEMPO_DE_ENCENDIDO)
+                        + Tiempo(1000),
+                "Se abrió el dosificador antes de que el soplador quedara listo"
+            );
+        }
+
+        #[test]
+        fn test_soplador_que_no_queda_listo() {
+            let c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = SopladorTrabado { estado: false };
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj.clone());
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::ErrorSoplador);
+            assert_eq!(reporte.entregado, Gramos(0));
+            assert_eq!(reloj.ahora(), u64::from(ESPERA_SOPLADOR_MAX.0));
+            assert!(matches!(
+                reporte.alarmas[..],
+                [Alarma::SopladorNoListo { .. }]
+            ));
+            assert!(!soplador.get_estado(), "El soplador quedó encendido");
+        }
+    }
+
+    mod asincrono {
+        use super::*;
+
+        #[test]
+        fn test_monitor_durante_la_ejecucion() {
+            let c1 = crear_ciclo(3, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1, &c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj);
+            let monitor = programa.get_monitor();
+            assert_eq!(monitor.get().fase, Fase::Detenido);
+
+            let observador = async {
+                let mut fases = Vec::new();
+                let mut ciclos = Vec::new();
+                while monitor.get().fase != Fase::Finalizado {
+                    fases.push(monitor.get().fase);
+                    ciclos.push(monitor.get().ciclo);
+                    Espera::ceder().await;
+                }
+                (fases, ciclos)
+            };
+
+            let (_, (fases, ciclos)) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                observador,
+            ));
+
+            assert!(fases.contains(&Fase::Pulso), "No se observó ningún pulso");
+            assert!(fases.contains(&Fase::EsperaPulso));
+            assert!(fases.contains(&Fase::EsperaCiclo));
+            assert!(ciclos.contains(&1), "No se observó el segundo ciclo");
+            assert_eq!(
+                monitor.get().entregado,
+                Gramos::try_from(CAPACIDAD_SILOS).unwrap() - silo.get_alimento_gramos(),
+                "El monitor no refleja lo entregado"
+            );
+        }
+
+        #[test]
+        fn test_lineas_concurrentes() {
+            let c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let mut racion = Racion::new(vec![&c1]);
+            // Cinco minutos que con el reloj acelerado son 300 ms reales
+            racion
+                .set_ciclo_espera(Milisegundos(300000))
+                .expect("Ha intentado establecer una espera fuera de rango");
+
+            let reloj: RelojRef = Arc::new(RelojAcelerado::new(1000));
+
+            let mut soplador_1 = Soplador::new();
+            soplador_1.set_reloj(reloj.clone());
+            let mut doser_1 = Dosificador::new(ENTREGA).unwrap();
+            let mut silo_1 = crear_silo();
+            let mut linea_1 = Programa::new(&racion);
+            linea_1.set_reloj(reloj.clone());
+
+            let mut soplador_2 = Soplador::new();
+            soplador_2.set_reloj(reloj.clone());
+            let mut doser_2 = Dosificador::new(ENTREGA).unwrap();
+            let mut silo_2 = crear_silo();
+            let mut linea_2 = Programa::new(&racion);
+            linea_2.set_reloj(reloj);
+
+            let inicio = Instant::now();
+            bloquear(unir(
+                linea_1.iniciar_async(&mut soplador_1, &mut doser_1, &mut silo_1),
+                linea_2.iniciar_async(&mut soplador_2, &mut doser_2, &mut silo_2),
+            ));
+
+            assert!(
+                inicio.elapsed().as_millis() < 550,
+                "Las líneas no se han ejecutado de forma concurrente"
+            );
+            assert!(silo_1.get_alimento() < CAPACIDAD_SILOS);
+            assert!(silo_2.get_alimento() < CAPACIDAD_SILOS);
+        }
+    }
+
+    mod control {
+        use super::*;
+
+        #[test]
+        fn test_parada_emergencia_durante_un_pulso() {
+            let c1 = crear_ciclo(10, Milisegundos(10000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::Pulso {
+                    Espera::ceder().await;
+                }
+                control.parada_emergencia();
+            };
+
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::ParadaEmergencia);
+            assert!(!doser.get_estado(), "El dosificador quedó abierto");
+            assert!(!soplador.get_estado(), "El soplador quedó encendido");
+            assert_eq!(
+                bitacora.lock().unwrap().as_slice(),
+                ["soplador:on", "doser:on", "doser:off", "soplador:off"],
+                "El orden de detención no es dosificador y luego soplador"
+            );
+            assert_eq!(monitor.get().fase, Fase::Finalizado);
+        }
+
+        #[test]
+        fn test_parada_emergencia_descuenta_solo_lo_entregado() {
+            let c1 = crear_ciclo(10, Milisegundos(10000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::Pulso {
+                    Espera::ceder().await;
+                }
+                control.parada_emergencia();
+            };
+
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::ParadaEmergencia);
+            assert!(
+                reporte.entregado < doser.calcular_gramos(Milisegundos(10000)),
+                "Se descontó el pulso completo"
+            );
+            assert_eq!(reporte.silo_antes - reporte.silo_despues, reporte.entregado);
+            assert_eq!(
+                silo.get_alimento_gramos(),
+                Gramos::try_from(CAPACIDAD_SILOS).unwrap() - reporte.entregado
+            );
+        }
+
+        #[test]
+        fn test_abortar_termina_el_pulso_en_curso() {
+            let c1 = crear_ciclo(10, Milisegundos(10000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1, &c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::Pulso {
+                    Espera::ceder().await;
+                }
+                control.abortar();
+            };
+
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::Abortado);
+            assert_eq!(monitor.get().pulso, 0, "Se entregaron pulsos tras abortar");
+            assert_eq!(
+                bitacora.lock().unwrap().as_slice(),
+                [
+                    "soplador:on",
+                    "doser:on",
+                    "doser:off",
+                    "doser:off",
+                    "soplador:off"
+                ]
+            );
+        }
+
+        #[test]
+        fn test_rearmar_despues_de_abortar() {
+            let c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let control = programa.get_control();
+
+            control.abortar();
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+            assert_eq!(reporte.fin, Fin::Abortado);
+
+            // La orden se mantiene hasta rearmar
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+            assert_eq!(reporte.fin, Fin::Abortado);
+
+            control.rearmar();
+            assert_eq!(control.get_orden(), Orden::Continuar);
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+            assert_eq!(reporte.fin, Fin::Completado);
+        }
+
+        #[test]
+        fn test_rearmar_despues_de_parada_emergencia() {
+            let c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let control = programa.get_control();
+
+            control.parada_emergencia();
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+            assert_eq!(reporte.fin, Fin::ParadaEmergencia);
+
+            control.rearmar();
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+            assert_eq!(reporte.fin, Fin::Completado);
+        }
+
+        #[test]
+        fn test_pausar_y_reanudar() {
+            let c1 = crear_ciclo(3, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let reloj = Arc::new(RelojManual::new(0));
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj.clone());
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::EsperaPulso {
+                    Espera::ceder().await;
+                }
+                control.pausar();
+                while monitor.get().fase != Fase::Pausado {
+                    Espera::ceder().await;
+                }
+                let pausado_en = monitor.get().pulso;
+                // Una hora en pausa
+                let hasta = reloj.ahora() + 60 * 60 * 1000;
+                while reloj.ahora() < hasta {
+                    assert_eq!(
+                        monitor.get().pulso,
+                        pausado_en,
+                        "Se entregó un pulso en pausa"
+                    );
+                    Espera::ceder().await;
+                }
+                assert_eq!(
+                    bitacora.lock().unwrap().last(),
+                    Some(&"doser:off"),
+                    "El dosificador quedó abierto en pausa"
+                );
+                control.reanudar();
+            };
+
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::Completado);
+        }
+    }
+
+    mod silo_vacio {
+        use super::*;
+
+        #[test]
+        fn test_entrega_parcial_y_detiene() {
+            // 4 kg por pulso (2 kg/s durante 2 s)
+            let c1 = crear_ciclo(10, Milisegundos(2000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(Kilogramos(10))
+                .expect("Ha intentado ingresar mas alimento que la capacidad del silo");
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::SinAlimento);
+            assert_eq!(
+                reporte.entregado,
+                Gramos(10_000),
+                "No se entregó el pulso parcial"
+            );
+            assert_eq!(reporte.get_faltante(), reporte.solicitado - Gramos(10_000));
+            assert!(!reporte.is_completo());
+            assert_eq!(silo.get_alimento(), Kilogramos(0));
+            assert_eq!(
+                reporte.alarmas,
+                vec![Alarma::SiloSinAlimento {
+                    silo: silo.get_id(),
+                    solicitado: Gramos(4000),
+                    entregado: Gramos(2000),
+                }]
+            );
+            assert!(!doser.get_estado(), "El dosificador quedó abierto");
+            assert!(!soplador.get_estado(), "El soplador quedó encendido");
+            assert_eq!(
+                bitacora.lock().unwrap().last(),
+                Some(&"soplador:off"),
+                "El soplador no se apagó después del dosificador"
+            );
+        }
+
+        #[test]
+        fn test_alarma_con_parada_en_el_pulso_parcial() {
+            // 20 kg por pulso (2 kg/s durante 10 s): los 10 kg del silo salen en 5 s
+            let c1 = crear_ciclo(10, Milisegundos(10000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+            silo.set_alimento(Kilogramos(10))
+                .expect("Ha intentado ingresar mas alimento que la capacidad del silo");
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::Pulso {
+                    Espera::ceder().await;
+                }
+                control.parada_emergencia();
+            };
+
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::ParadaEmergencia);
+            assert!(reporte.entregado < Gramos(10_000));
+            assert_eq!(
+                silo.get_alimento_gramos(),
+                Gramos(10_000) - reporte.entregado
+            );
+            assert_eq!(
+                reporte.alarmas,
+                vec![Alarma::SiloSinAlimento {
+                    silo: silo.get_id(),
+                    solicitado: Gramos(20_000),
+                    entregado: reporte.entregado,
+                }]
+            );
+        }
+
+        #[test]
+        fn test_silo_vacio_no_entrega() {
+            let c1 = crear_ciclo(10, Milisegundos(2000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = Silo::new(CAPACIDAD_SILOS).unwrap();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::SinAlimento);
+            assert_eq!(reporte.entregado, Gramos(0));
+            assert_eq!(reporte.alarmas.len(), 1);
+        }
+
+        #[test]
+        fn test_racion_completa() {
+            let c1 = crear_ciclo(10, Milisegundos(2000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert!(reporte.is_completo());
+            assert!(reporte.alarmas.is_empty());
+            assert_eq!(
+                reporte.entregado,
+                Gramos::try_from(CAPACIDAD_SILOS).unwrap() - silo.get_alimento_gramos()
+            );
+        }
+    }
+
+    mod reporte {
+        use super::*;
+
+        #[test]
+        fn test_detalle_por_ciclo() {
+            // 4 kg por pulso (2 kg/s durante 2 s)
+            let c1 = crear_ciclo(3, Milisegundos(2000), Milisegundos(1000));
+            let c2 = crear_ciclo(5, Milisegundos(2000), Milisegundos(3000));
+            let racion = Racion::new(vec![&c1, &c2]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(1000));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj.clone());
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.racion, racion.get_id());
+            assert_eq!(reporte.ciclos.len(), 2);
+            assert_eq!(reporte.ciclos[0].ciclo, c1.get_id());
+            assert_eq!(reporte.ciclos[1].ciclo, c2.get_id());
+            assert!(
+                reporte
+                    .ciclos
+                    .iter()
+                    .flat_map(|c| &c.pulsos)
+                    .all(|g| *g == Gramos(4000)),
+                "Los gramos por pulso no coinciden"
+            );
+            assert_eq!(
+                reporte
+                    .ciclos
+                    .iter()
+                    .map(|c| c.get_entregado())
+                    .sum::<Gramos>(),
+                reporte.entregado
+            );
+            assert_eq!(reporte.entregado, reporte.solicitado);
+
+            assert_eq!(
+                reporte.silo_antes,
+                Gramos::try_from(CAPACIDAD_SILOS).unwrap()
+            );
+            assert_eq!(reporte.silo_despues, silo.get_alimento_gramos());
+            assert_eq!(reporte.silo_antes - reporte.silo_despues, reporte.entregado);
+
+            assert_eq!(reporte.inicio, Tiempo(1000));
+            assert_eq!(reporte.termino, Tiempo(reloj.ahora()));
+            assert!(reporte.ciclos[0].inicio >= reporte.inicio);
+            assert!(reporte.ciclos[0].termino > reporte.ciclos[0].inicio);
+            assert!(reporte.ciclos[1].inicio > reporte.ciclos[0].termino);
+            assert!(reporte.ciclos[1].termino <= reporte.termino);
+
+            // El soplador se apaga durante los descansos y al terminar
+            let en_ciclos: Tiempo = reporte.ciclos.iter().map(|c| c.termino - c.inicio).sum();
+            assert!(reporte.soplador_encendido >= en_ciclos);
+            assert!(
+                reporte.soplador_encendido
+                    <= reporte.get_duracion()
+                        - Tiempo::from(racion.get_ciclo_espera())
+                        - Tiempo::from(racion.get_ciclo_espera())
+            );
+            assert!(!soplador.get_estado(), "El soplador quedó encendido");
+        }
+
+        #[test]
+        fn test_pulsos_exactos() {
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let mut c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
+            c2.set_pulso_purga(true);
+            let racion = Racion::new(vec![&c1, &c2]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.ciclos[0].get_pulsos(), 50);
+            assert_eq!(reporte.ciclos[1].get_pulsos(), 21, "No se entregó la purga");
+            assert_eq!(
+                reporte.ciclos[0].get_entregado(),
+                c1.calcular_entrega(&doser).unwrap()
+            );
+            assert_eq!(
+                reporte.ciclos[1].get_entregado(),
+                c2.calcular_entrega(&doser).unwrap()
+            );
+            assert_eq!(reporte.entregado, Gramos(500_000 + 126_000));
+            assert_eq!(silo.get_alimento(), CAPACIDAD_SILOS - Kilogramos(626));
+        }
+
+        #[test]
+        fn test_pulsos_en_gramos() {
+            // 1,5 kg/s durante 1,5 s: 2,25 kg por pulso
+            let c1 = crear_ciclo(4, Milisegundos(1500), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new_gramos(GramosPorSegundo(1500)).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj);
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert_eq!(reporte.ciclos[0].pulsos, vec![Gramos(2250); 4]);
+            assert_eq!(reporte.entregado, Gramos(9000));
+            assert_eq!(
+                silo.get_alimento_gramos(),
+                Gramos::try_from(CAPACIDAD_SILOS).unwrap() - Gramos(9000),
+                "El silo no descontó los gramos entregados"
+            );
+        }
+
+        #[test]
+        fn test_usa_la_curva_calibrada() {
+            let c1 = crear_ciclo(3, Milisegundos(5000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            doser.set_curva(Some(Curva::new(GramosPorSegundo(1800), -150)));
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj);
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(
+                reporte.solicitado,
+                Gramos(3 * 8850),
+                "No se usó la curva calibrada"
+            );
+            assert_eq!(reporte.ciclos[0].pulsos, vec![Gramos(8850); 3]);
+            assert_eq!(
+                silo.get_alimento_gramos(),
+                Gramos::try_from(CAPACIDAD_SILOS).unwrap() - Gramos(3 * 8850)
+            );
+        }
+
+        #[test]
+        fn test_soplador_apagado_en_parada() {
+            let c1 = crear_ciclo(10, Milisegundos(10000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj.clone());
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::EsperaPulso {
+                    Espera::ceder().await;
+                }
+                control.abortar();
+            };
+
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::Abortado);
+            assert_eq!(reporte.ciclos[0].get_pulsos(), 1);
+            assert_eq!(reporte.ciclos[0].pulsos, vec![Gramos(20_000)]);
+            assert!(!soplador.get_estado());
+            assert!(reporte.soplador_encendido <= reporte.get_duracion());
+            assert!(reporte.soplador_encendido > Tiempo(0));
+        }
+    }
+
+    mod selectora {
+        use super::*;
+
+        fn crear_selectora(bitacora: &Bitacora, posicion: u32, atascada: bool) -> SelectoraFalsa {
+            SelectoraFalsa {
+                posicion,
+                atascada,
+                bitacora: bitacora.clone(),
+            }
+        }
+
+        #[test]
+        fn test_mueve_antes_de_dosificar() {
+            let mut c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            c1.set_salida(Some(2));
+            let mut c2 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            c2.set_salida(Some(4));
+            let racion = Racion::new(vec![&c1, &c2]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut selectora = crear_selectora(&bitacora, 0, false);
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar_en(&mut soplador, &mut doser, &mut silo, &mut selectora);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert_eq!(selectora.get_posicion(), 4);
+            assert_eq!(reporte.ciclos[0].salida, Some(2));
+            assert_eq!(reporte.ciclos[1].salida, Some(4));
+            assert!(
+                reporte.ciclos[0].inicio >= Tiempo::from(ESPERA_SELECTORA),
+                "No se respetó la espera de la selectora"
+            );
+
+            let bitacora = bitacora.lock().unwrap();
+            let primera_apertura = bitacora.iter().position(|a| *a == "doser:on").unwrap();
+            assert_eq!(bitacora[0], "selectora");
+            assert!(primera_apertura > 0);
+            assert_eq!(bitacora.iter().filter(|a| **a == "selectora").count(), 2);
+        }
+
+        #[test]
+        fn test_no_espera_si_ya_esta_en_posicion() {
+            let mut c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            c1.set_salida(Some(3));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut selectora = crear_selectora(&bitacora, 3, false);
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar_en(&mut soplador, &mut doser, &mut silo, &mut selectora);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert!(reporte.ciclos[0].inicio < Tiempo::from(ESPERA_SELECTORA));
+            assert!(!bitacora.lock().unwrap().contains(&"selectora"));
+        }
+
+        #[test]
+        fn test_salida_de_la_racion() {
+            let c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let mut c2 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            c2.set_salida(Some(5));
+            let mut racion = Racion::new(vec![&c1, &c2]);
+            racion.set_salida(Some(1));
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut selectora = crear_selectora(&bitacora, 0, false);
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar_en(&mut soplador, &mut doser, &mut silo, &mut selectora);
+
+            assert_eq!(reporte.ciclos[0].salida, Some(1));
+            assert_eq!(reporte.ciclos[1].salida, Some(5));
+        }
+
+        #[test]
+        fn test_posicion_incorrecta() {
+            let mut c1 = crear_ciclo(3, Milisegundos(1000), Milisegundos(1000));
+            c1.set_salida(Some(2));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut selectora = crear_selectora(&bitacora, 0, true);
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar_en(&mut soplador, &mut doser, &mut silo, &mut selectora);
+
+            assert_eq!(reporte.fin, Fin::ErrorSelectora);
+            assert_eq!(
+                reporte.alarmas,
+                vec![Alarma::PosicionIncorrecta {
+                    selectora: Uuid::nil(),
+                    salida: 2,
+                    posicion: 0,
+                }]
+            );
+            assert_eq!(reporte.entregado, Gramos(0));
+            assert!(
+                !bitacora.lock().unwrap().contains(&"doser:on"),
+                "Se abrió el dosificador en la salida equivocada"
+            );
+            assert!(!soplador.get_estado());
+        }
+
+        #[test]
+        fn test_salida_fuera_de_rango() {
+            let mut c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            c1.set_salida(Some(7));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut selectora = Selectora::new(5);
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar_en(&mut soplador, &mut doser, &mut silo, &mut selectora);
+
+            assert_eq!(reporte.fin, Fin::ErrorSelectora);
+            assert_eq!(
+                reporte.alarmas,
+                vec![Alarma::ErrorSelectora {
+                    selectora: selectora.get_id(),
+                    salida: 7,
+                    error: SelectoraError::FueraDeRango,
+                }]
+            );
+        }
+
+        #[test]
+        fn test_salida_sin_selectora() {
+            let mut c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            c1.set_salida(Some(2));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::ErrorSelectora);
+            assert_eq!(
+                reporte.alarmas,
+                vec![Alarma::SalidaSinSelectora {
+                    ciclo: c1.get_id(),
+                    salida: 2,
+                }]
+            );
+            assert!(!bitacora.lock().unwrap().contains(&"doser:on"));
+        }
+    }
+
+    mod barrido {
+        use super::*;
+
+        /// Barrido de 30 s: 400 m a 20 m/s con 50% de margen.
+        fn crear_barrido(potencia: Porcentaje) -> Barrido {
+            let mut barrido = Barrido::new();
+            barrido.set_potencia(potencia).unwrap();
+            barrido.set_largo(Metros(400)).unwrap();
+            barrido.set_velocidad(MetrosPorSegundo(20)).unwrap();
+            barrido.set_margen(50).unwrap();
+            barrido
+        }
+
+        fn crear_soplador(reloj: &RelojRef) -> Soplador {
+            let mut soplador = Soplador::new();
+            soplador.set_reloj(reloj.clone());
+            soplador.set_potencia(Porcentaje(60)).unwrap();
+            soplador
+        }
+
+        #[test]
+        fn test_barre_al_terminar_cada_ciclo() {
+            let mut c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(5000));
+            c1.set_barrido(Some(crear_barrido(Porcentaje(90))));
+            let racion = Racion::new(vec![&c1, &c1]);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj);
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj);
+            let monitor = programa.get_monitor();
+
+            let observador = async {
+                let mut barridos = 0;
+                while monitor.get().fase != Fase::Finalizado {
+                    if monitor.get().fase == Fase::Barrido {
+                        barridos += 1;
+                    }
+                    Espera::ceder().await;
+                }
+                barridos
+            };
+            let (reporte, barridos) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                observador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert!(barridos > 0, "No se observó la fase de barrido");
+            assert_eq!(reporte.ciclos[0].barrido, Tiempo(30000));
+            assert_eq!(reporte.ciclos[1].barrido, Tiempo(30000));
+            assert_eq!(
+                soplador.get_potencia(),
+                Porcentaje(60),
+                "No se restauró la potencia"
+            );
+
+            // El barrido reemplaza la espera tras el último pulso
+            assert!(
+                reporte.ciclos[1].inicio
+                    >= reporte.ciclos[0].termino
+                        + Tiempo(30000)
+                        + Tiempo::from(racion.get_ciclo_espera())
+            );
+        }
+
+        #[test]
+        fn test_barrido_de_la_racion() {
+            let c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let mut c2 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let mut corto = crear_barrido(Porcentaje(90));
+            corto.set_largo(Metros(100)).unwrap();
+            c2.set_barrido(Some(corto));
+            let mut racion = Racion::new(vec![&c1, &c2]);
+            racion.set_barrido(Some(crear_barrido(Porcentaje(90))));
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut soplador = crear_soplador(&reloj);
+            let mut doser = Dosificador::new(ENTREGA).unwrap();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(reloj);
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.ciclos[0].barrido, Tiempo(30000));
+            assert_eq!(reporte.ciclos[1].barrido, Tiempo(7500));
+        }
+
+        #[test]
+        fn test_sin_barrido_por_defecto() {
+            let c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.ciclos[0].barrido, Tiempo(0));
+        }
+
+        #[test]
+        fn test_barre_al_abortar() {
+            let mut c1 = crear_ciclo(10, Milisegundos(1000), Milisegundos(5000));
+            c1.set_barrido(Some(crear_barrido(Porcentaje(90))));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::EsperaPulso {
+                    Espera::ceder().await;
+                }
+                control.abortar();
+            };
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::Abortado);
+            assert_eq!(
+                reporte.ciclos[0].barrido,
+                Tiempo(30000),
+                "No se barrió la línea"
+            );
+            assert_eq!(
+                bitacora.lock().unwrap().last(),
+                Some(&"soplador:off"),
+                "El soplador no se apagó después del barrido"
+            );
+        }
+
+        #[test]
+        fn test_sin_barrido_en_emergencia() {
+            let mut c1 = crear_ciclo(10, Milisegundos(10000), Milisegundos(1000));
+            c1.set_barrido(Some(crear_barrido(Porcentaje(90))));
+            let racion = Racion::new(vec![&c1]);
+            let (_, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let monitor = programa.get_monitor();
+            let control = programa.get_control();
+
+            let operador = async {
+                while monitor.get().fase != Fase::Pulso {
+                    Espera::ceder().await;
+                }
+                control.parada_emergencia();
+            };
+            let (reporte, _) = bloquear(unir(
+                programa.iniciar_async(&mut soplador, &mut doser, &mut silo),
+                operador,
+            ));
+
+            assert_eq!(reporte.fin, Fin::ParadaEmergencia);
+            assert_eq!(reporte.ciclos[0].barrido, Tiempo(0));
+        }
+    }
+    mod apagado {
+        use super::*;
+
+        fn contar(bitacora: &Bitacora, accion: &str) -> usize {
+            bitacora
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|a| **a == accion)
+                .count()
+        }
+
+        /// Prueba que el soplador se apaga tras cada ciclo y se vuelve a encender en el siguiente
+        #[test]
+        fn test_apaga_en_el_descanso() {
+            let c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1, &c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert_eq!(contar(&bitacora, "soplador:on"), 2);
+            assert_eq!(contar(&bitacora, "soplador:off"), 2);
+            assert!(!soplador.get_estado());
+            assert!(
+                reporte.soplador_encendido
+                    < reporte.get_duracion() - Tiempo::from(racion.get_ciclo_espera())
+            );
+        }
+
+        /// Prueba que el soplador se apaga recién después del barrido
+        #[test]
+        fn test_apaga_despues_del_barrido() {
+            let mut c1 = crear_ciclo(1, Milisegundos(1000), Milisegundos(1000));
+            let mut barrido = Barrido::new();
+            barrido.set_largo(Metros(100)).unwrap();
+            c1.set_barrido(Some(barrido));
+            let racion = Racion::new(vec![&c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa.set_reloj(Arc::new(RelojManual::new(0)));
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(
+                bitacora.lock().unwrap().as_slice(),
+                ["soplador:on", "doser:on", "doser:off", "soplador:off"]
+            );
+            assert_eq!(
+                reporte.soplador_encendido,
+                reporte.ciclos[0].termino - reporte.ciclos[0].inicio + reporte.ciclos[0].barrido
+            );
+        }
+
+        /// Prueba que con `set_mantener_encendido` el soplador sigue encendido en descansos cortos
+        #[test]
+        fn test_mantener_encendido() {
+            let c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1, &c1, &c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa
+                .set_reloj(Arc::new(RelojManual::new(0)))
+                .set_mantener_encendido(racion.get_ciclo_espera());
+            let reporte = programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert_eq!(contar(&bitacora, "soplador:on"), 1);
+            assert_eq!(bitacora.lock().unwrap().last(), Some(&"soplador:off"));
+            assert!(!soplador.get_estado(), "El soplador quedó encendido");
+        }
+
+        /// Prueba que un descanso más largo que el permitido apaga el soplador
+        #[test]
+        fn test_descanso_mas_largo_que_el_permitido() {
+            let c1 = crear_ciclo(2, Milisegundos(1000), Milisegundos(1000));
+            let racion = Racion::new(vec![&c1, &c1]);
+            let (bitacora, mut soplador, mut doser) = crear_falsos();
+            let mut silo = crear_silo();
+
+            let mut programa = Programa::new(&racion);
+            programa
+                .set_reloj(Arc::new(RelojManual::new(0)))
+                .set_mantener_encendido(Milisegundos(racion.get_ciclo_espera().0 / 2));
+            programa.iniciar(&mut soplador, &mut doser, &mut silo);
+
+            assert_eq!(contar(&bitacora, "soplador:on"), 2);
+        }
+    }
+}
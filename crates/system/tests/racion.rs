@@ -18,9 +18,10 @@
 mod racion {
     use system::racion::Racion;
     use system::{ciclo::Ciclo, racion::ESPERA_MAX, racion::ESPERA_MIN};
+    use types::unidades::Milisegundos;
 
     // Función auxiliar para crear un ciclo con valores predeterminados
-    fn crear_ciclo(pulsos: u32, duracion: u32, espera: u32) -> Ciclo {
+    fn crear_ciclo(pulsos: u32, duracion: Milisegundos, espera: Milisegundos) -> Ciclo {
         let mut ciclo = Ciclo::new();
         ciclo
             .set_pulsos(pulsos)
@@ -39,8 +40,8 @@ mod racion {
 
         #[test]
         fn test_valores_iniciales() {
-            let c1 = crear_ciclo(50, 5000, 8000);
-            let c2 = crear_ciclo(20, 3000, 4000);
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
             let vector = vec![&c1, &c2, &c1, &c1, &c2];
             let vector2 = vector.clone();
@@ -62,14 +63,14 @@ mod racion {
         }
 
         mod id {
-            use super::{crear_ciclo, Racion};
+            use super::{crear_ciclo, Milisegundos, Racion};
             use uuid::Uuid;
 
             /// Prueba que la id sea unica
             #[test]
             fn test_unicidad() {
-                let c1 = crear_ciclo(50, 5000, 8000);
-                let c2 = crear_ciclo(20, 3000, 4000);
+                let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+                let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
                 let v1 = vec![&c1, &c2, &c1, &c1, &c2];
                 let v2 = v1.clone();
@@ -83,8 +84,8 @@ mod racion {
             /// Prueba que la id sea un UUID válido
             #[test]
             fn test_validez() {
-                let c1 = crear_ciclo(50, 5000, 8000);
-                let c2 = crear_ciclo(20, 3000, 4000);
+                let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+                let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
                 let v1 = vec![&c1, &c2, &c1, &c1, &c2];
 
@@ -96,8 +97,8 @@ mod racion {
 
             #[test]
             fn tets_is_v4() {
-                let c1 = crear_ciclo(50, 5000, 8000);
-                let c2 = crear_ciclo(20, 3000, 4000);
+                let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+                let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
                 let v1 = vec![&c1, &c2, &c1, &c1, &c2];
 
@@ -114,12 +115,12 @@ mod racion {
     }
 
     mod set_ciclo_espera {
-        use super::{crear_ciclo, Racion, ESPERA_MAX, ESPERA_MIN};
+        use super::{crear_ciclo, Milisegundos, Racion, ESPERA_MAX, ESPERA_MIN};
 
         #[test]
         fn test_establece_valor() {
-            let c1 = crear_ciclo(50, 5000, 8000);
-            let c2 = crear_ciclo(20, 3000, 4000);
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
             let vector = vec![&c1, &c2, &c1, &c1, &c2];
 
@@ -134,28 +135,28 @@ mod racion {
             );
 
             racion
-                .set_ciclo_espera(ESPERA_MIN + 1)
+                .set_ciclo_espera(ESPERA_MIN + Milisegundos(1))
                 .expect("Ha intentado establecer una espera fuera de rango");
 
             assert_eq!(
                 racion.get_ciclo_espera(),
-                ESPERA_MIN + 1,
+                ESPERA_MIN + Milisegundos(1),
                 "La espera no es {}",
-                ESPERA_MIN + 1
+                ESPERA_MIN + Milisegundos(1)
             );
         }
 
         #[test]
         fn test_bajo_el_rango() {
-            let c1 = crear_ciclo(50, 5000, 8000);
-            let c2 = crear_ciclo(20, 3000, 4000);
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
             let vector = vec![&c1, &c2, &c1, &c1, &c2];
 
             // Crear la ración con los ciclos predefinidos
             let mut racion = Racion::new(vector);
 
-            let r = racion.set_ciclo_espera(ESPERA_MIN - 1);
+            let r = racion.set_ciclo_espera(ESPERA_MIN - Milisegundos(1));
 
             assert_eq!(
                 r,
@@ -166,14 +167,14 @@ mod racion {
 
         #[test]
         fn test_sobre_el_rango() {
-            let c1 = crear_ciclo(50, 5000, 8000);
-            let c2 = crear_ciclo(20, 3000, 4000);
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
 
             let vector = vec![&c1, &c2, &c1, &c1, &c2];
 
             // Crear la ración con los ciclos predefinidos
             let mut racion = Racion::new(vector);
-            let r = racion.set_ciclo_espera(ESPERA_MAX + 1);
+            let r = racion.set_ciclo_espera(ESPERA_MAX + Milisegundos(1));
 
             assert_eq!(
                 r,
@@ -182,4 +183,96 @@ mod racion {
             )
         }
     }
+
+    mod por_masa {
+        use super::*;
+        use types::unidades::{Gramos, KgPorSegundo};
+
+        use hardware::dosificador::Dosificador;
+        use system::errors::CicloError;
+
+        #[test]
+        fn test_reparte_en_ciclos() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            let ciclos = Racion::ciclos_por_masa(Gramos(300_001), 3, &doser, None).unwrap();
+            let racion = Racion::new(ciclos.iter().collect());
+
+            assert_eq!(racion.get_ciclos().len(), 3);
+            let total: Gramos = racion
+                .get_ciclos()
+                .iter()
+                .map(|ciclo| ciclo.calcular_entrega(&doser).unwrap())
+                .sum();
+            assert!(
+                total >= Gramos(300_001),
+                "La ración entrega menos de lo pedido"
+            );
+            assert!(ciclos[0].calcular_entrega(&doser).unwrap() >= Gramos(100_001));
+        }
+
+        #[test]
+        fn test_cero_ciclos() {
+            let doser = Dosificador::new(KgPorSegundo(2)).unwrap();
+            assert_eq!(
+                Racion::ciclos_por_masa(Gramos(300_000), 0, &doser, None).err(),
+                Some(CicloError::CantidadFueraDeRango)
+            );
+        }
+    }
+
+    mod print {
+        use super::*;
+        use system::logs::{Print, Valor};
+        use system::programa::Programa;
+
+        #[test]
+        fn test_registro_incluye_ciclos() {
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let c2 = crear_ciclo(20, Milisegundos(3000), Milisegundos(4000));
+            let racion = Racion::new(vec![&c1, &c2]);
+
+            let registro = racion.registro();
+
+            assert_eq!(registro.origen, "Racion");
+            assert_eq!(registro.id, Some(racion.get_id()));
+            match registro.get("ciclos") {
+                Some(Valor::Lista(ciclos)) => {
+                    assert_eq!(ciclos.len(), 2);
+                    assert_eq!(ciclos[0], Valor::Registro(c1.registro()));
+                    assert_eq!(ciclos[1], Valor::Registro(c2.registro()));
+                }
+                otro => panic!("Se esperaba la lista de ciclos, se obtuvo {:?}", otro),
+            }
+        }
+
+        #[test]
+        fn test_ciclo() {
+            let ciclo = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+
+            let registro = ciclo.registro();
+
+            assert_eq!(registro.get("pulsos"), Some(&Valor::Entero(50)));
+            assert_eq!(registro.get("pulso_duracion"), Some(&Valor::Entero(5000)));
+            assert_eq!(registro.get("pulso_espera"), Some(&Valor::Entero(8000)));
+            assert_eq!(registro.get("barrido"), Some(&Valor::Nulo));
+            assert!(ciclo.print().contains("[50 P][5000ms DP][8000ms EP]"));
+        }
+
+        #[test]
+        fn test_programa() {
+            let c1 = crear_ciclo(50, Milisegundos(5000), Milisegundos(8000));
+            let racion = Racion::new(vec![&c1, &c1, &c1]);
+            let programa = Programa::new(&racion);
+
+            assert!(programa.print().contains("[3 ciclos][Detenido]"));
+
+            let registro = programa.registro();
+            assert_eq!(registro.id, Some(racion.get_id()));
+            assert_eq!(registro.get("fase"), Some(&Valor::from("Detenido")));
+            match registro.get("pasos") {
+                Some(Valor::Lista(pasos)) => assert_eq!(pasos.len(), 3),
+                otro => panic!("Se esperaba la lista de pasos, se obtuvo {:?}", otro),
+            }
+        }
+    }
 }
@@ -0,0 +1,97 @@
+#![cfg(feature = "serde")]
+
+use system::barrido::Barrido;
+use system::ciclo::Ciclo;
+use system::racion::{Racion, RacionGuardada};
+
+#[cfg(test)]
+mod serializacion {
+    use super::*;
+    use types::unidades::{Metros, Milisegundos};
+
+    fn crear_ciclo(pulsos: u32) -> Ciclo {
+        let mut ciclo = Ciclo::new();
+        ciclo.set_pulsos(pulsos).unwrap();
+        ciclo.set_pulso_duracion(Milisegundos(5000)).unwrap();
+        ciclo.set_salida(Some(2));
+        ciclo
+    }
+
+    mod ciclo {
+        use super::*;
+
+        #[test]
+        fn test_ida_y_vuelta() {
+            let mut ciclo = crear_ciclo(50);
+            let mut barrido = Barrido::new();
+            barrido.set_largo(Metros(400)).unwrap();
+            ciclo.set_pulso_purga(true).set_barrido(Some(barrido));
+
+            let texto = serde_json::to_string(&ciclo).unwrap();
+            let copia: Ciclo = serde_json::from_str(&texto).unwrap();
+            assert_eq!(copia, ciclo);
+        }
+
+        #[test]
+        fn test_fuera_de_rango() {
+            let texto = r#"{"pulsos": 20000, "pulso_duracion": 5000, "pulso_espera": 8000}"#;
+            let error = serde_json::from_str::<Ciclo>(texto).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("pulsos 20000 fuera de rango (0..=10000)"));
+
+            let texto = r#"{"pulsos": 5, "pulso_duracion": 500, "pulso_espera": 8000}"#;
+            assert!(serde_json::from_str::<Ciclo>(texto).is_err());
+        }
+
+        #[test]
+        fn test_barrido_fuera_de_rango() {
+            let texto = r#"{
+                "pulsos": 5, "pulso_duracion": 5000, "pulso_espera": 8000,
+                "barrido": {"potencia": 100, "largo": 400, "velocidad": 50, "margen": 50}
+            }"#;
+            let error = serde_json::from_str::<Ciclo>(texto).unwrap_err();
+            assert!(error.to_string().contains("velocidad 50m/s fuera de rango"));
+        }
+
+        #[test]
+        fn test_clave_desconocida() {
+            let texto = r#"{"pulsos": 5, "pulso_duracion": 5000, "pulso_espera": 8000, "x": 1}"#;
+            assert!(serde_json::from_str::<Ciclo>(texto).is_err());
+        }
+    }
+
+    mod racion {
+        use super::*;
+        use types::unidades::Milisegundos;
+
+        #[test]
+        fn test_ida_y_vuelta() {
+            let c1 = crear_ciclo(50);
+            let c2 = crear_ciclo(20);
+            let mut racion = Racion::new(vec![&c1, &c2, &c1]);
+            racion.set_ciclo_espera(Milisegundos(90000)).unwrap();
+            racion.set_salida(Some(1));
+
+            let texto = serde_json::to_string(&racion).unwrap();
+            let guardada: RacionGuardada = serde_json::from_str(&texto).unwrap();
+            assert_eq!(guardada.get_ciclos().len(), 3);
+
+            let copia = guardada.get_racion();
+            assert_eq!(copia.get_id(), racion.get_id());
+            assert_eq!(copia.get_ciclo_espera(), Milisegundos(90000));
+            assert_eq!(copia.get_salida(), Some(1));
+            assert_eq!(copia.get_ciclos()[1], &c2);
+            assert_eq!(serde_json::to_string(&guardada).unwrap(), texto);
+        }
+
+        #[test]
+        fn test_espera_fuera_de_rango() {
+            let texto = r#"{"ciclos": [], "ciclo_espera": 10}"#;
+            let error = serde_json::from_str::<RacionGuardada>(texto).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("ciclo_espera 10ms fuera de rango"));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use system::errors::AgendaError;
+use system::sol::{Luz, Ubicacion};
+
+/// Mediodía UTC del 21 de junio de 2024.
+const SOLSTICIO_JUNIO: u64 = 1_718_971_200_000;
+
+/// Mediodía UTC del 21 de diciembre de 2024.
+const SOLSTICIO_DICIEMBRE: u64 = 1_734_782_400_000;
+
+const MINUTO: u64 = 60 * 1000;
+
+#[cfg(test)]
+mod sol {
+    use super::*;
+
+    fn dia(luz: Luz) -> (u64, u64) {
+        match luz {
+            Luz::Dia {
+                amanecer,
+                atardecer,
+            } => (amanecer, atardecer),
+            otra => panic!("Se esperaba un día con amanecer y atardecer: {:?}", otra),
+        }
+    }
+
+    fn cerca(a: u64, b: u64, tolerancia: u64) -> bool {
+        a.abs_diff(b) <= tolerancia
+    }
+
+    #[test]
+    fn test_coordenadas_invalidas() {
+        assert_eq!(
+            Ubicacion::new(-91.0, 0.0),
+            Err(AgendaError::UbicacionInvalida)
+        );
+        assert_eq!(
+            Ubicacion::new(0.0, 180.5),
+            Err(AgendaError::UbicacionInvalida)
+        );
+        assert_eq!(
+            Ubicacion::new(f64::NAN, 0.0),
+            Err(AgendaError::UbicacionInvalida)
+        );
+        assert!(Ubicacion::new(-41.47, -72.94).is_ok());
+    }
+
+    /// Londres el 21 de junio de 2024: amanecer 03:43 UTC y atardecer 20:21 UTC
+    #[test]
+    fn test_londres() {
+        let londres = Ubicacion::new(51.5, -0.13).unwrap();
+        let (amanecer, atardecer) = dia(londres.get_luz(SOLSTICIO_JUNIO));
+
+        let medianoche = SOLSTICIO_JUNIO - 12 * 60 * MINUTO;
+        assert!(cerca(
+            amanecer,
+            medianoche + (3 * 60 + 43) * MINUTO,
+            2 * MINUTO
+        ));
+        assert!(cerca(
+            atardecer,
+            medianoche + (20 * 60 + 21) * MINUTO,
+            2 * MINUTO
+        ));
+    }
+
+    /// En el sur los días de invierno son mucho más cortos que los de verano
+    #[test]
+    fn test_largo_del_dia_en_el_sur() {
+        let puerto_montt = Ubicacion::new(-41.47, -72.94).unwrap();
+        let (amanecer, atardecer) = dia(puerto_montt.get_luz(SOLSTICIO_JUNIO));
+        let invierno = atardecer - amanecer;
+        let (amanecer, atardecer) = dia(puerto_montt.get_luz(SOLSTICIO_DICIEMBRE));
+        let verano = atardecer - amanecer;
+
+        // Cerca de 9 h 10 min en invierno y 15 h 10 min en verano
+        assert!(cerca(invierno, (9 * 60 + 10) * MINUTO, 10 * MINUTO));
+        assert!(cerca(verano, (15 * 60 + 10) * MINUTO, 10 * MINUTO));
+    }
+
+    #[test]
+    fn test_zonas_polares() {
+        let antartica = Ubicacion::new(-75.0, 0.0).unwrap();
+        assert_eq!(antartica.get_luz(SOLSTICIO_JUNIO), Luz::NochePolar);
+        assert_eq!(antartica.get_luz(SOLSTICIO_DICIEMBRE), Luz::DiaPolar);
+
+        assert!(Luz::DiaPolar.contiene(SOLSTICIO_DICIEMBRE));
+        assert!(!Luz::NochePolar.contiene(SOLSTICIO_JUNIO));
+    }
+
+    #[test]
+    fn test_contiene() {
+        let luz = Luz::Dia {
+            amanecer: 1000,
+            atardecer: 2000,
+        };
+        assert!(luz.contiene(1000));
+        assert!(luz.contiene(2000));
+        assert!(!luz.contiene(999));
+        assert!(!luz.contiene(2001));
+    }
+}
@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+
+/// Waker que despierta al hilo bloqueado en `bloquear`.
+struct Despertador(Thread);
+
+impl Wake for Despertador {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Ejecuta un `Future` hasta completarlo, bloqueando el hilo actual.
+///
+/// Es un executor mínimo para usar el programa asíncrono desde código síncrono;
+/// cualquier otro executor puede usarse en su lugar.
+///
+/// # Ejemplo:
+///! ```rust
+///! let resultado = bloquear(async { 1 + 1 });
+///! assert_eq!(resultado, 2);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub fn bloquear<F: Future>(futuro: F) -> F::Output {
+    let mut futuro = pin!(futuro);
+    let waker = Arc::new(Despertador(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match futuro.as_mut().poll(&mut cx) {
+            Poll::Ready(resultado) => return resultado,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// `Future` que ejecuta dos futures de forma concurrente y entrega ambos resultados.
+///
+/// # Ejemplo:
+///! ```rust
+///! let (a, b) = bloquear(unir(linea_1.iniciar_async(..), linea_2.iniciar_async(..)));
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct Unir<A: Future, B: Future> {
+    a: Pin<Box<A>>,
+    b: Pin<Box<B>>,
+    resultado_a: Option<A::Output>,
+    resultado_b: Option<B::Output>,
+}
+
+pub fn unir<A: Future, B: Future>(a: A, b: B) -> Unir<A, B> {
+    Unir {
+        a: Box::pin(a),
+        b: Box::pin(b),
+        resultado_a: None,
+        resultado_b: None,
+    }
+}
+
+impl<A: Future, B: Future> Future for Unir<A, B>
+where
+    A::Output: Unpin,
+    B::Output: Unpin,
+{
+    type Output = (A::Output, B::Output);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.resultado_a.is_none() {
+            if let Poll::Ready(resultado) = self.a.as_mut().poll(cx) {
+                self.resultado_a = Some(resultado);
+            }
+        }
+        if self.resultado_b.is_none() {
+            if let Poll::Ready(resultado) = self.b.as_mut().poll(cx) {
+                self.resultado_b = Some(resultado);
+            }
+        }
+
+        if self.resultado_a.is_some() && self.resultado_b.is_some() {
+            let a = self.resultado_a.take().unwrap();
+            let b = self.resultado_b.take().unwrap();
+            Poll::Ready((a, b))
+        } else {
+            Poll::Pending
+        }
+    }
+}
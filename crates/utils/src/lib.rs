@@ -1 +1,3 @@
+pub mod executor;
+pub mod reloj;
 pub mod utils;
@@ -0,0 +1,360 @@
+use std::cmp::{self, Reverse};
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::utils::sleep;
+
+/// El trait `Reloj` abstrae el paso del tiempo para todo lo que necesite esperar.
+///
+/// Permite reemplazar las esperas reales por tiempo virtual, de modo que una
+/// ración completa se pueda probar en milisegundos.
+///
+/// # Métodos:
+/// - `ahora`: Milisegundos transcurridos desde la época Unix según este reloj.
+/// - `dormir`: Espera `ms` milisegundos según este reloj, bloqueando el hilo.
+/// - `esperar`: Espera `ms` milisegundos sin bloquear el hilo, como un `Future`.
+pub trait Reloj: Send + Sync {
+    fn ahora(&self) -> u64;
+    fn dormir(&self, ms: u32);
+
+    /// Por defecto duerme con `dormir` y luego cede el control una vez, lo que es
+    /// correcto para relojes virtuales. Los relojes reales deben sobreescribirlo.
+    fn esperar(&self, ms: u32) -> Espera {
+        self.dormir(ms);
+        Espera::ceder()
+    }
+}
+
+/// Referencia compartida a un reloj, para que varios componentes usen el mismo tiempo.
+pub type RelojRef = Arc<dyn Reloj>;
+
+/// Milisegundos actuales desde la época Unix según el reloj del sistema.
+fn ahora_sistema() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Reloj del sistema: las esperas bloquean el hilo durante el tiempo real.
+///
+/// # Ejemplo:
+///! ```rust
+///! let reloj = RelojReal::new();
+///! reloj.dormir(2000);  // Pausa de 2 segundos
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct RelojReal;
+
+impl RelojReal {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RelojReal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reloj for RelojReal {
+    fn ahora(&self) -> u64 {
+        ahora_sistema()
+    }
+
+    fn dormir(&self, ms: u32) {
+        sleep(ms);
+    }
+
+    fn esperar(&self, ms: u32) -> Espera {
+        Espera::new(ms)
+    }
+}
+
+/// Reloj virtual controlado manualmente: las esperas no bloquean, solo avanzan el tiempo.
+///
+/// # Ejemplo:
+///! ```rust
+///! let reloj = RelojManual::new(0);
+///! reloj.dormir(60000);  // Retorna de inmediato
+///! assert_eq!(reloj.ahora(), 60000);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct RelojManual {
+    /// Milisegundos desde la época Unix.
+    ahora: AtomicU64,
+}
+
+impl RelojManual {
+    /// Crea un reloj virtual que comienza en `inicio` milisegundos desde la época Unix.
+    pub fn new(inicio: u64) -> Self {
+        Self {
+            ahora: AtomicU64::new(inicio),
+        }
+    }
+
+    /// Avanza el reloj `ms` milisegundos sin esperar.
+    pub fn avanzar(&self, ms: u64) {
+        self.ahora.fetch_add(ms, Ordering::SeqCst);
+    }
+
+    /// Fija el instante actual del reloj.
+    pub fn set_ahora(&self, ms: u64) {
+        self.ahora.store(ms, Ordering::SeqCst);
+    }
+}
+
+impl Reloj for RelojManual {
+    fn ahora(&self) -> u64 {
+        self.ahora.load(Ordering::SeqCst)
+    }
+
+    fn dormir(&self, ms: u32) {
+        self.avanzar(ms.into());
+    }
+}
+
+/// Reloj acelerado: el tiempo transcurre `factor` veces más rápido que el real.
+///
+/// Útil para simulaciones donde se quiere observar la ejecución sin esperar los tiempos reales.
+///
+/// # Ejemplo:
+///! ```rust
+///! let reloj = RelojAcelerado::new(1000);
+///! reloj.dormir(60000);  // Pausa real de 60 ms
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct RelojAcelerado {
+    factor: u32,
+    inicio: u64,
+    origen: Instant,
+}
+
+impl RelojAcelerado {
+    /// Crea un reloj acelerado que parte en la hora actual del sistema.
+    /// Un `factor` de 0 se trata como 1.
+    pub fn new(factor: u32) -> Self {
+        Self {
+            factor: factor.max(1),
+            inicio: ahora_sistema(),
+            origen: Instant::now(),
+        }
+    }
+
+    pub fn get_factor(&self) -> u32 {
+        self.factor
+    }
+}
+
+impl Reloj for RelojAcelerado {
+    fn ahora(&self) -> u64 {
+        let real = self.origen.elapsed().as_millis() as u64;
+        self.inicio + real * u64::from(self.factor)
+    }
+
+    fn dormir(&self, ms: u32) {
+        sleep(ms / self.factor);
+    }
+
+    fn esperar(&self, ms: u32) -> Espera {
+        Espera::new(ms / self.factor)
+    }
+}
+
+/// Crea una referencia compartida al reloj del sistema.
+pub fn real() -> RelojRef {
+    Arc::new(RelojReal::new())
+}
+
+/// `Future` que se completa tras una espera en tiempo real, sin depender de ningún runtime.
+///
+/// Todas las esperas comparten un único hilo temporizador que guarda los plazos en un
+/// montículo y despierta cada tarea al cumplirse el suyo, por lo que funciona con
+/// cualquier executor y varias líneas concurrentes no crean un hilo por espera.
+/// Una espera de 0 ms solo cede el control una vez.
+///
+/// # Ejemplo:
+///! ```rust
+///! bloquear(async {
+///!     Espera::new(2000).await;  // Pausa de 2 segundos sin bloquear el executor
+///! });
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct Espera {
+    /// Milisegundos reales a esperar.
+    ms: u32,
+
+    /// Estado compartido con el temporizador, desde que la espera se registra.
+    compartido: Option<Arc<Mutex<EstadoEspera>>>,
+
+    /// Indica si ya se cedió el control en una espera de 0 ms.
+    cedido: bool,
+}
+
+/// Estado que comparte una `Espera` con el temporizador: si terminó y a quién despertar.
+struct EstadoEspera {
+    terminada: bool,
+    waker: Option<Waker>,
+}
+
+impl Espera {
+    pub fn new(ms: u32) -> Self {
+        Self {
+            ms,
+            compartido: None,
+            cedido: false,
+        }
+    }
+
+    /// Crea una espera que solo cede el control al executor una vez.
+    pub fn ceder() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Future for Espera {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.ms == 0 {
+            if self.cedido {
+                return Poll::Ready(());
+            }
+            self.cedido = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        match &self.compartido {
+            Some(compartido) => {
+                let mut estado = compartido.lock().unwrap();
+                if estado.terminada {
+                    Poll::Ready(())
+                } else {
+                    estado.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+            None => {
+                let compartido = Arc::new(Mutex::new(EstadoEspera {
+                    terminada: false,
+                    waker: Some(cx.waker().clone()),
+                }));
+                let plazo = Instant::now() + Duration::from_millis(u64::from(self.ms));
+                temporizador().registrar(plazo, compartido.clone());
+                self.compartido = Some(compartido);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Un plazo pendiente en el temporizador. Se ordena por instante y, a igual instante,
+/// por orden de registro.
+struct Plazo {
+    instante: Instant,
+    orden: u64,
+    estado: Arc<Mutex<EstadoEspera>>,
+}
+
+impl PartialEq for Plazo {
+    fn eq(&self, otro: &Self) -> bool {
+        self.cmp(otro) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Plazo {}
+
+impl PartialOrd for Plazo {
+    fn partial_cmp(&self, otro: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(otro))
+    }
+}
+
+impl Ord for Plazo {
+    fn cmp(&self, otro: &Self) -> cmp::Ordering {
+        (self.instante, self.orden).cmp(&(otro.instante, otro.orden))
+    }
+}
+
+/// Hilo único que despierta las `Espera` en tiempo real del proceso.
+struct Temporizador {
+    plazos: Mutex<BinaryHeap<Reverse<Plazo>>>,
+    aviso: Condvar,
+    orden: AtomicU64,
+}
+
+/// El temporizador del proceso, que se crea junto con su hilo en la primera espera.
+fn temporizador() -> &'static Temporizador {
+    static TEMPORIZADOR: OnceLock<&'static Temporizador> = OnceLock::new();
+    TEMPORIZADOR.get_or_init(|| {
+        let temporizador: &'static Temporizador = Box::leak(Box::new(Temporizador {
+            plazos: Mutex::new(BinaryHeap::new()),
+            aviso: Condvar::new(),
+            orden: AtomicU64::new(0),
+        }));
+        thread::Builder::new()
+            .name("temporizador".into())
+            .spawn(move || temporizador.ejecutar())
+            .expect("No se pudo crear el hilo del temporizador");
+        temporizador
+    })
+}
+
+impl Temporizador {
+    fn registrar(&self, instante: Instant, estado: Arc<Mutex<EstadoEspera>>) {
+        let orden = self.orden.fetch_add(1, Ordering::Relaxed);
+        self.plazos.lock().unwrap().push(Reverse(Plazo {
+            instante,
+            orden,
+            estado,
+        }));
+        // El nuevo plazo puede ser anterior al que el hilo está esperando
+        self.aviso.notify_one();
+    }
+
+    fn ejecutar(&self) {
+        let mut plazos = self.plazos.lock().unwrap();
+        loop {
+            let ahora = Instant::now();
+            let mut cumplidos = Vec::new();
+            while plazos
+                .peek()
+                .is_some_and(|Reverse(plazo)| plazo.instante <= ahora)
+            {
+                if let Some(Reverse(plazo)) = plazos.pop() {
+                    cumplidos.push(plazo);
+                }
+            }
+
+            if !cumplidos.is_empty() {
+                // Se despierta sin el candado, para que las tareas puedan registrar otra espera
+                drop(plazos);
+                for plazo in cumplidos {
+                    let mut estado = plazo.estado.lock().unwrap();
+                    estado.terminada = true;
+                    if let Some(waker) = estado.waker.take() {
+                        waker.wake();
+                    }
+                }
+                plazos = self.plazos.lock().unwrap();
+                continue;
+            }
+
+            plazos = match plazos.peek() {
+                Some(Reverse(plazo)) => {
+                    let restante = plazo.instante.saturating_duration_since(ahora);
+                    self.aviso.wait_timeout(plazos, restante).unwrap().0
+                }
+                None => self.aviso.wait(plazos).unwrap(),
+            };
+        }
+    }
+}
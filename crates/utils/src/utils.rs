@@ -13,6 +13,7 @@ use std::time::Duration;
 ///! ```rust
 ///! lib::sleep(2000);  // Pausa de 2 segundos
 ///! ```
+#[allow(clippy::suspicious_doc_comments)]
 pub fn sleep(ms: u32) {
     thread::sleep(Duration::from_millis(ms.into()));
 }
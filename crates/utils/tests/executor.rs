@@ -0,0 +1,87 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use utils::executor::{bloquear, unir};
+use utils::reloj::Espera;
+
+#[cfg(test)]
+mod executor {
+    use super::*;
+
+    #[test]
+    fn test_bloquear_entrega_el_resultado() {
+        assert_eq!(bloquear(async { 1 + 1 }), 2);
+    }
+
+    #[test]
+    fn test_espera_real() {
+        let inicio = Instant::now();
+
+        bloquear(Espera::new(50));
+
+        assert!(
+            inicio.elapsed().as_millis() >= 50,
+            "La espera terminó antes"
+        );
+    }
+
+    #[test]
+    fn test_unir_es_concurrente() {
+        let inicio = Instant::now();
+
+        let (a, b) = bloquear(unir(
+            async {
+                Espera::new(200).await;
+                "a"
+            },
+            async {
+                Espera::new(200).await;
+                "b"
+            },
+        ));
+
+        assert_eq!((a, b), ("a", "b"));
+        assert!(
+            inicio.elapsed().as_millis() < 390,
+            "Las esperas se ejecutaron en serie"
+        );
+    }
+
+    #[test]
+    fn test_plazo_corto_registrado_despues() {
+        let orden = Arc::new(Mutex::new(Vec::new()));
+        let (orden_a, orden_b) = (orden.clone(), orden.clone());
+
+        bloquear(unir(
+            async move {
+                Espera::new(300).await;
+                orden_a.lock().unwrap().push("larga");
+            },
+            async move {
+                Espera::new(50).await;
+                orden_b.lock().unwrap().push("corta");
+            },
+        ));
+
+        assert_eq!(*orden.lock().unwrap(), vec!["corta", "larga"]);
+    }
+
+    #[test]
+    fn test_muchas_esperas_concurrentes() {
+        let inicio = Instant::now();
+
+        let mut todas: Pin<Box<dyn Future<Output = ()>>> = Box::pin(async {});
+        for _ in 0..200 {
+            todas = Box::pin(async move {
+                unir(todas, Espera::new(100)).await;
+            });
+        }
+        bloquear(todas);
+
+        let transcurrido = inicio.elapsed().as_millis();
+        assert!(transcurrido >= 100, "La espera terminó antes");
+        assert!(transcurrido < 1000, "Las esperas se ejecutaron en serie");
+    }
+}
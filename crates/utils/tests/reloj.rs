@@ -0,0 +1,74 @@
+use std::time::Instant;
+
+use utils::reloj::{Reloj, RelojAcelerado, RelojManual, RelojReal};
+
+#[cfg(test)]
+mod reloj {
+    use super::*;
+
+    mod manual {
+        use super::*;
+
+        #[test]
+        fn test_dormir_avanza_sin_bloquear() {
+            let reloj = RelojManual::new(0);
+            let inicio = Instant::now();
+
+            // Un día completo de esperas
+            reloj.dormir(24 * 60 * 60 * 1000);
+
+            assert_eq!(reloj.ahora(), 24 * 60 * 60 * 1000);
+            assert!(
+                inicio.elapsed().as_millis() < 100,
+                "El reloj manual ha bloqueado el hilo"
+            );
+        }
+
+        #[test]
+        fn test_avanzar_y_fijar() {
+            let reloj = RelojManual::new(1000);
+
+            reloj.avanzar(500);
+            assert_eq!(reloj.ahora(), 1500);
+
+            reloj.set_ahora(10);
+            assert_eq!(reloj.ahora(), 10);
+        }
+    }
+
+    mod acelerado {
+        use super::*;
+
+        #[test]
+        fn test_dormir_escala_la_espera() {
+            let reloj = RelojAcelerado::new(1000);
+            let antes = reloj.ahora();
+            let inicio = Instant::now();
+
+            reloj.dormir(50000);
+
+            let real = inicio.elapsed().as_millis();
+            assert!(real >= 50, "La espera real fue menor a la escalada");
+            assert!(real < 1000, "La espera no se ha acelerado");
+            assert!(
+                reloj.ahora() - antes >= 50000,
+                "El tiempo virtual no avanzó"
+            );
+        }
+
+        #[test]
+        fn test_factor_cero() {
+            assert_eq!(RelojAcelerado::new(0).get_factor(), 1);
+        }
+    }
+
+    mod real {
+        use super::*;
+
+        #[test]
+        fn test_ahora_es_epoca_unix() {
+            // 2020-01-01T00:00:00Z
+            assert!(RelojReal::new().ahora() > 1_577_836_800_000);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use hardware::calibracion::{Calibracion, Calibraciones, Curva, Muestra};
+use system::reporte::Reporte;
+use utils::reloj::RelojRef;
+
+use crate::config::Configuracion;
+use crate::errors::{ComandoError, ConfigError};
+use crate::estado::EstadoPlanta;
+
+/// Ejecuta la ración `racion`, o todas en orden alfabético si es `None`, partiendo del
+/// alimento guardado en `estado` y anotando en él cada entrega.
+///
+/// Con un `RelojManual` la ejecución es una simulación: el tiempo avanza sin esperas.
+///
+/// # Errores:
+/// - `ConfigError::RacionDesconocida`: No hay una ración con ese nombre.
+/// - `ConfigError::Valor`: El estado guardado no cabe en un silo de la configuración.
+pub fn ejecutar(
+    config: &mut Configuracion,
+    racion: Option<&str>,
+    reloj: RelojRef,
+    estado: &mut EstadoPlanta,
+) -> Result<Vec<(String, Reporte)>, ConfigError> {
+    let raciones: Vec<String> = match racion {
+        Some(racion) => vec![racion.to_string()],
+        None => config
+            .get_raciones()
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    };
+
+    estado.restaurar(config)?;
+    let mut reportes = Vec::new();
+    for racion in raciones {
+        let reporte = config.iniciar(&racion, reloj.clone())?;
+        estado.registrar(&racion, &reporte, config);
+        reportes.push((racion, reporte));
+    }
+    Ok(reportes)
+}
+
+/// Ajusta la curva del dosificador `dosificador` con el tipo de `alimento` a partir
+/// de las `muestras` pesadas y la guarda en `calibraciones`.
+///
+/// # Errores:
+/// - `ComandoError::DosificadorDesconocido`: La configuración no tiene ese dosificador.
+/// - `ComandoError::Calibracion`: Las muestras no permiten ajustar una curva.
+pub fn calibrar(
+    config: &Configuracion,
+    calibraciones: &mut Calibraciones,
+    dosificador: &str,
+    alimento: &str,
+    muestras: &[Muestra],
+) -> Result<Curva, ComandoError> {
+    if config.get_dosificador(dosificador).is_none() {
+        return Err(ComandoError::DosificadorDesconocido(
+            dosificador.to_string(),
+        ));
+    }
+
+    let mut calibracion = Calibracion::new();
+    for muestra in muestras {
+        calibracion.registrar(muestra.duracion, muestra.gramos)?;
+    }
+    let curva = calibracion.ajustar()?;
+    calibraciones.set_curva(dosificador, alimento, curva)?;
+    Ok(curva)
+}
@@ -0,0 +1,642 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use hardware::calibracion::Calibraciones;
+use hardware::dosificador::Dosificador;
+use hardware::rampa::{Perfil, Rampa, RAMPA_MAX, RAMPA_MIN};
+use hardware::selectora::{self, Selectora};
+use hardware::silo::{self, Silo};
+use hardware::soplador::{self, Soplador};
+use serde::Deserialize;
+use system::ciclo::{self, Ciclo};
+use system::programa::Programa;
+use system::racion::{self, Racion};
+use system::reporte::Reporte;
+use types::unidades::{Gramos, GramosPorSegundo, Kilogramos, Milisegundos, Porcentaje};
+use utils::reloj::RelojRef;
+
+use crate::errors::ConfigError;
+
+/// Formato de un archivo de configuración.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Formato {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl Formato {
+    /// Deduce el formato de la extensión de `ruta`.
+    ///
+    /// # Errores:
+    /// - `ConfigError::Formato`: La extensión no es `.toml`, `.yaml`, `.yml` ni `.json`.
+    pub fn desde_ruta<P: AsRef<Path>>(ruta: P) -> Result<Self, ConfigError> {
+        let extension = ruta
+            .as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "toml" => Ok(Formato::Toml),
+            "yaml" | "yml" => Ok(Formato::Yaml),
+            "json" => Ok(Formato::Json),
+            _ => Err(ConfigError::Formato(extension)),
+        }
+    }
+}
+
+// Estructura del archivo tal como se escribe. Cada cantidad se escribe como un número
+// en la unidad de su tipo: kilogramos para los silos, g/s para los dosificadores y
+// milisegundos para los tiempos.
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Archivo {
+    #[serde(default)]
+    silos: BTreeMap<String, ArchivoSilo>,
+    #[serde(default)]
+    dosificadores: BTreeMap<String, ArchivoDosificador>,
+    #[serde(default)]
+    sopladores: BTreeMap<String, ArchivoSoplador>,
+    #[serde(default)]
+    selectoras: BTreeMap<String, ArchivoSelectora>,
+    #[serde(default)]
+    ciclos: BTreeMap<String, ArchivoCiclo>,
+    #[serde(default)]
+    raciones: BTreeMap<String, ArchivoRacion>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoSilo {
+    capacidad: Kilogramos,
+    #[serde(default)]
+    alimento: Kilogramos,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoDosificador {
+    entrega: GramosPorSegundo,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoSoplador {
+    potencia: Option<Porcentaje>,
+    rampa: Option<ArchivoRampa>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoRampa {
+    perfil: Option<ArchivoPerfil>,
+    subida: Option<Milisegundos>,
+    bajada: Option<Milisegundos>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ArchivoPerfil {
+    Lineal,
+    CurvaS,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoSelectora {
+    salidas: u32,
+    espera: Option<Milisegundos>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoCiclo {
+    pulsos: Option<u32>,
+    pulso_duracion: Option<Milisegundos>,
+    pulso_espera: Option<Milisegundos>,
+    #[serde(default)]
+    pulso_purga: bool,
+    salida: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchivoRacion {
+    ciclos: Vec<String>,
+    ciclo_espera: Option<Milisegundos>,
+    salida: Option<u32>,
+    soplador: String,
+    dosificador: String,
+    silo: String,
+    selectora: Option<String>,
+    alimento: Option<String>,
+}
+
+/// Una ración de la configuración: los nombres de sus ciclos y del hardware que la entrega.
+#[derive(PartialEq, Debug, Clone)]
+pub struct DefinicionRacion {
+    ciclos: Vec<String>,
+    ciclo_espera: Option<Milisegundos>,
+    salida: Option<u32>,
+    soplador: String,
+    dosificador: String,
+    silo: String,
+    selectora: Option<String>,
+    alimento: Option<String>,
+}
+
+impl DefinicionRacion {
+    pub fn get_ciclos(&self) -> &Vec<String> {
+        &self.ciclos
+    }
+
+    pub fn get_soplador(&self) -> &str {
+        &self.soplador
+    }
+
+    pub fn get_dosificador(&self) -> &str {
+        &self.dosificador
+    }
+
+    pub fn get_silo(&self) -> &str {
+        &self.silo
+    }
+
+    pub fn get_selectora(&self) -> Option<&str> {
+        self.selectora.as_deref()
+    }
+
+    /// Tipo de alimento de la ración, con el que se busca la curva calibrada del dosificador.
+    pub fn get_alimento(&self) -> Option<&str> {
+        self.alimento.as_deref()
+    }
+}
+
+/// Configuración del binario: el hardware de la planta, los ciclos y las raciones.
+///
+/// Se carga desde un archivo TOML, YAML o JSON. Cada valor se valida con los mismos
+/// rangos que los setters de `Ciclo`, `Racion`, `Soplador` y `Silo`, y los errores
+/// indican la clave del archivo que los provoca.
+///
+/// # Ejemplo:
+///! ```toml
+///! [silos.silo-1]
+///! capacidad = 24000        # kg
+///! alimento = 24000         # kg
+///!
+///! [dosificadores.linea-1]
+///! entrega = 2000           # g/s
+///!
+///! [sopladores.soplador-1]
+///! potencia = 80
+///! rampa = { perfil = "curva_s", subida = 8000, bajada = 4000 }
+///!
+///! [ciclos.c1]
+///! pulsos = 50
+///! pulso_duracion = 5000    # ms
+///! pulso_espera = 8000      # ms
+///!
+///! [raciones.manana]
+///! ciclos = ["c1", "c1"]
+///! ciclo_espera = 60000     # ms
+///! soplador = "soplador-1"
+///! dosificador = "linea-1"
+///! silo = "silo-1"
+///! alimento = "pellet-4mm"  # opcional, para usar la curva calibrada
+///! ```
+///!
+///! ```
+///! let mut config = Configuracion::cargar("f1.toml")?;
+///! let reporte = config.iniciar("manana", reloj::real())?;
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub struct Configuracion {
+    silos: BTreeMap<String, Silo>,
+    dosificadores: BTreeMap<String, Dosificador>,
+    sopladores: BTreeMap<String, Soplador>,
+    selectoras: BTreeMap<String, Selectora>,
+    ciclos: BTreeMap<String, Ciclo>,
+    raciones: BTreeMap<String, DefinicionRacion>,
+    calibraciones: Calibraciones,
+}
+
+impl Configuracion {
+    /// Lee y valida la configuración de `ruta`, con el formato que indica su extensión.
+    pub fn cargar<P: AsRef<Path>>(ruta: P) -> Result<Self, ConfigError> {
+        let formato = Formato::desde_ruta(&ruta)?;
+        let texto = fs::read_to_string(ruta).map_err(|e| ConfigError::Archivo(e.kind()))?;
+        Self::leer(&texto, formato)
+    }
+
+    /// Interpreta y valida la configuración contenida en `texto`.
+    pub fn leer(texto: &str, formato: Formato) -> Result<Self, ConfigError> {
+        let archivo: Archivo = match formato {
+            Formato::Toml => {
+                serde_path_to_error::deserialize(toml::Deserializer::new(texto)).map_err(sintaxis)
+            }
+            Formato::Yaml => {
+                serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(texto))
+                    .map_err(sintaxis)
+            }
+            Formato::Json => {
+                serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(texto))
+                    .map_err(sintaxis)
+            }
+        }?;
+        Self::validar(archivo)
+    }
+
+    fn validar(archivo: Archivo) -> Result<Self, ConfigError> {
+        let mut config = Self {
+            silos: BTreeMap::new(),
+            dosificadores: BTreeMap::new(),
+            sopladores: BTreeMap::new(),
+            selectoras: BTreeMap::new(),
+            ciclos: BTreeMap::new(),
+            raciones: BTreeMap::new(),
+            calibraciones: Calibraciones::new(),
+        };
+
+        for (nombre, valor) in archivo.silos {
+            let clave = format!("silos.{}", nombre);
+            let mut silo = Silo::new(valor.capacidad).map_err(|_| {
+                fuera_de_rango(&clave, "capacidad", Kilogramos(0), silo::CAPACIDAD_MAX)
+            })?;
+            silo.set_alimento(valor.alimento)
+                .map_err(|_| fuera_de_rango(&clave, "alimento", Kilogramos(0), valor.capacidad))?;
+            config.silos.insert(nombre, silo);
+        }
+
+        for (nombre, valor) in archivo.dosificadores {
+            let doser = Dosificador::new_gramos(valor.entrega).map_err(|_| {
+                error(
+                    format!("dosificadores.{}.entrega", nombre),
+                    "debe ser mayor que 0",
+                )
+            })?;
+            config.dosificadores.insert(nombre, doser);
+        }
+
+        for (nombre, valor) in archivo.sopladores {
+            let clave = format!("sopladores.{}", nombre);
+            let mut soplador = Soplador::new();
+            if let Some(potencia) = valor.potencia {
+                soplador.set_potencia(potencia).map_err(|_| {
+                    fuera_de_rango(
+                        &clave,
+                        "potencia",
+                        soplador::POTENCIA_MIN,
+                        soplador::POTENCIA_MAX,
+                    )
+                })?;
+            }
+            if let Some(archivo) = valor.rampa {
+                let mut rampa = Rampa::new();
+                if let Some(perfil) = archivo.perfil {
+                    rampa.set_perfil(match perfil {
+                        ArchivoPerfil::Lineal => Perfil::Lineal,
+                        ArchivoPerfil::CurvaS => Perfil::CurvaS,
+                    });
+                }
+                let clave = format!("{}.rampa", clave);
+                if let Some(subida) = archivo.subida {
+                    rampa
+                        .set_subida(subida)
+                        .map_err(|_| fuera_de_rango(&clave, "subida", RAMPA_MIN, RAMPA_MAX))?;
+                }
+                if let Some(bajada) = archivo.bajada {
+                    rampa
+                        .set_bajada(bajada)
+                        .map_err(|_| fuera_de_rango(&clave, "bajada", RAMPA_MIN, RAMPA_MAX))?;
+                }
+                soplador.set_rampa(rampa);
+            }
+            config.sopladores.insert(nombre, soplador);
+        }
+
+        for (nombre, valor) in archivo.selectoras {
+            if valor.salidas == 0 {
+                return Err(error(
+                    format!("selectoras.{}.salidas", nombre),
+                    "debe ser mayor que 0",
+                ));
+            }
+            let mut selectora = Selectora::new(valor.salidas - 1);
+            if let Some(espera) = valor.espera {
+                selectora.set_espera(espera).map_err(|_| {
+                    fuera_de_rango(
+                        &format!("selectoras.{}", nombre),
+                        "espera",
+                        selectora::ESPERA_MIN,
+                        selectora::ESPERA_MAX,
+                    )
+                })?;
+            }
+            config.selectoras.insert(nombre, selectora);
+        }
+
+        for (nombre, valor) in archivo.ciclos {
+            let clave = format!("ciclos.{}", nombre);
+            let mut ciclo = Ciclo::new();
+            if let Some(pulsos) = valor.pulsos {
+                ciclo.set_pulsos(pulsos).map_err(|_| {
+                    fuera_de_rango(&clave, "pulsos", ciclo::PULSOS_MIN, ciclo::PULSOS_MAX)
+                })?;
+            }
+            if let Some(duracion) = valor.pulso_duracion {
+                ciclo.set_pulso_duracion(duracion).map_err(|_| {
+                    fuera_de_rango(
+                        &clave,
+                        "pulso_duracion",
+                        ciclo::DURACION_MIN,
+                        ciclo::DURACION_MAX,
+                    )
+                })?;
+            }
+            if let Some(espera) = valor.pulso_espera {
+                ciclo.set_pulso_espera(espera).map_err(|_| {
+                    fuera_de_rango(&clave, "pulso_espera", ciclo::ESPERA_MIN, ciclo::ESPERA_MAX)
+                })?;
+            }
+            ciclo.set_pulso_purga(valor.pulso_purga);
+            ciclo.set_salida(valor.salida);
+            config.ciclos.insert(nombre, ciclo);
+        }
+
+        for (nombre, valor) in archivo.raciones {
+            let definicion = config.validar_racion(&format!("raciones.{}", nombre), valor)?;
+            config.raciones.insert(nombre, definicion);
+        }
+
+        Ok(config)
+    }
+
+    /// Comprueba que la ración haga referencia a ciclos y hardware existentes y que
+    /// sus salidas existan en su selectora.
+    fn validar_racion(
+        &self,
+        clave: &str,
+        valor: ArchivoRacion,
+    ) -> Result<DefinicionRacion, ConfigError> {
+        if valor.ciclos.is_empty() {
+            return Err(error(
+                format!("{}.ciclos", clave),
+                "debe tener al menos un ciclo",
+            ));
+        }
+        if let Some(espera) = valor.ciclo_espera {
+            Racion::new(vec![]).set_ciclo_espera(espera).map_err(|_| {
+                fuera_de_rango(
+                    clave,
+                    "ciclo_espera",
+                    racion::ESPERA_MIN,
+                    racion::ESPERA_MAX,
+                )
+            })?;
+        }
+
+        referencia(&self.sopladores, clave, "soplador", &valor.soplador)?;
+        referencia(
+            &self.dosificadores,
+            clave,
+            "dosificador",
+            &valor.dosificador,
+        )?;
+        referencia(&self.silos, clave, "silo", &valor.silo)?;
+        let maxima = match &valor.selectora {
+            Some(nombre) => {
+                referencia(&self.selectoras, clave, "selectora", nombre)?;
+                Some(self.selectoras[nombre].get_posiciones().max)
+            }
+            None => None,
+        };
+
+        let salida_valida = |clave: String, salida: u32| match maxima {
+            Some(maxima) if salida > maxima => Err(fuera_de_rango(&clave, "salida", 0, maxima)),
+            Some(_) => Ok(()),
+            None => Err(error(
+                format!("{}.salida", clave),
+                "requiere que la ración tenga una selectora",
+            )),
+        };
+        if let Some(salida) = valor.salida {
+            salida_valida(clave.to_string(), salida)?;
+        }
+        for (i, nombre) in valor.ciclos.iter().enumerate() {
+            let Some(ciclo) = self.ciclos.get(nombre) else {
+                return Err(error(
+                    format!("{}.ciclos[{}]", clave, i),
+                    format!("no existe el ciclo '{}'", nombre),
+                ));
+            };
+            if let Some(salida) = ciclo.get_salida() {
+                salida_valida(format!("ciclos.{}", nombre), salida)?;
+            }
+        }
+
+        // Ni un ciclo ni la ración completa pueden entregar más de lo que cabe en un silo
+        let doser = &self.dosificadores[&valor.dosificador];
+        let mut total = 0u64;
+        for (i, nombre) in valor.ciclos.iter().enumerate() {
+            let entrega = self.ciclos[nombre].calcular_entrega(doser).map_err(|_| {
+                error(
+                    format!("{}.ciclos[{}]", clave, i),
+                    format!(
+                        "el ciclo '{}' entrega más de {} con el dosificador '{}'",
+                        nombre,
+                        Gramos(u32::MAX),
+                        valor.dosificador
+                    ),
+                )
+            })?;
+            total += u64::from(entrega.0);
+        }
+        if total > u64::from(u32::MAX) {
+            return Err(error(
+                format!("{}.ciclos", clave),
+                format!("la ración entrega más de {}", Gramos(u32::MAX)),
+            ));
+        }
+
+        Ok(DefinicionRacion {
+            ciclos: valor.ciclos,
+            ciclo_espera: valor.ciclo_espera,
+            salida: valor.salida,
+            soplador: valor.soplador,
+            dosificador: valor.dosificador,
+            silo: valor.silo,
+            selectora: valor.selectora,
+            alimento: valor.alimento,
+        })
+    }
+
+    pub fn get_silo(&self, nombre: &str) -> Option<&Silo> {
+        self.silos.get(nombre)
+    }
+
+    pub fn get_silo_mut(&mut self, nombre: &str) -> Option<&mut Silo> {
+        self.silos.get_mut(nombre)
+    }
+
+    /// Nombres de los silos, en orden alfabético.
+    pub fn get_silos(&self) -> Vec<&str> {
+        self.silos.keys().map(String::as_str).collect()
+    }
+
+    pub fn get_dosificador(&self, nombre: &str) -> Option<&Dosificador> {
+        self.dosificadores.get(nombre)
+    }
+
+    pub fn get_soplador(&self, nombre: &str) -> Option<&Soplador> {
+        self.sopladores.get(nombre)
+    }
+
+    pub fn get_selectora(&self, nombre: &str) -> Option<&Selectora> {
+        self.selectoras.get(nombre)
+    }
+
+    pub fn get_ciclo(&self, nombre: &str) -> Option<&Ciclo> {
+        self.ciclos.get(nombre)
+    }
+
+    pub fn get_definicion(&self, nombre: &str) -> Option<&DefinicionRacion> {
+        self.raciones.get(nombre)
+    }
+
+    /// Nombres de las raciones, en orden alfabético.
+    pub fn get_raciones(&self) -> Vec<&str> {
+        self.raciones.keys().map(String::as_str).collect()
+    }
+
+    /// Instala las curvas calibradas de los dosificadores. Cada ración usa la curva de
+    /// su dosificador con su `alimento`, o la entrega nominal si no tiene una.
+    pub fn set_calibraciones(&mut self, calibraciones: Calibraciones) {
+        self.calibraciones = calibraciones;
+    }
+
+    pub fn get_calibraciones(&self) -> &Calibraciones {
+        &self.calibraciones
+    }
+
+    /// Arma la `Racion` con el nombre dado a partir de los ciclos de la configuración.
+    pub fn get_racion(&self, nombre: &str) -> Option<Racion<'_>> {
+        let definicion = self.raciones.get(nombre)?;
+        Some(armar_racion(&self.ciclos, definicion))
+    }
+
+    /// Ejecuta la ración `nombre` en su hardware, midiendo el tiempo con `reloj`.
+    ///
+    /// # Errores:
+    /// - `ConfigError::RacionDesconocida`: No hay una ración con ese nombre.
+    pub fn iniciar(&mut self, nombre: &str, reloj: RelojRef) -> Result<Reporte, ConfigError> {
+        let definicion = self
+            .raciones
+            .get(nombre)
+            .ok_or_else(|| ConfigError::RacionDesconocida(nombre.to_string()))?;
+        let racion = armar_racion(&self.ciclos, definicion);
+
+        let soplador = self
+            .sopladores
+            .get_mut(&definicion.soplador)
+            .expect("El soplador se validó al cargar la configuración");
+        let doser = self
+            .dosificadores
+            .get_mut(&definicion.dosificador)
+            .expect("El dosificador se validó al cargar la configuración");
+        let silo = self
+            .silos
+            .get_mut(&definicion.silo)
+            .expect("El silo se validó al cargar la configuración");
+
+        match &definicion.alimento {
+            Some(alimento) => {
+                self.calibraciones
+                    .aplicar(&definicion.dosificador, alimento, doser);
+            }
+            None => {
+                doser.set_curva(None);
+            }
+        }
+
+        soplador.set_reloj(reloj.clone());
+        let mut programa = Programa::new(&racion);
+        programa.set_reloj(reloj);
+
+        let reporte = match &definicion.selectora {
+            Some(selectora) => {
+                let selectora = self
+                    .selectoras
+                    .get_mut(selectora)
+                    .expect("La selectora se validó al cargar la configuración");
+                programa.iniciar_en(soplador, doser, silo, selectora)
+            }
+            None => programa.iniciar(soplador, doser, silo),
+        };
+        Ok(reporte)
+    }
+}
+
+/// Arma la `Racion` de `definicion` con los ciclos declarados.
+fn armar_racion<'a>(
+    ciclos: &'a BTreeMap<String, Ciclo>,
+    definicion: &DefinicionRacion,
+) -> Racion<'a> {
+    let ciclos = definicion
+        .ciclos
+        .iter()
+        .map(|ciclo| &ciclos[ciclo])
+        .collect();
+
+    let mut racion = Racion::new(ciclos);
+    if let Some(espera) = definicion.ciclo_espera {
+        racion
+            .set_ciclo_espera(espera)
+            .expect("La espera se validó al cargar la configuración");
+    }
+    racion.set_salida(definicion.salida);
+    racion
+}
+
+/// Convierte un error de serde en un `ConfigError::Sintaxis` con la ruta de la clave.
+fn sintaxis<E: std::fmt::Display>(error: serde_path_to_error::Error<E>) -> ConfigError {
+    let clave = error.path().to_string();
+    ConfigError::Sintaxis {
+        clave,
+        mensaje: error.into_inner().to_string(),
+    }
+}
+
+fn error<C: Into<String>, M: Into<String>>(clave: C, mensaje: M) -> ConfigError {
+    ConfigError::Valor {
+        clave: clave.into(),
+        mensaje: mensaje.into(),
+    }
+}
+
+fn fuera_de_rango<T: Display>(clave: &str, campo: &str, min: T, max: T) -> ConfigError {
+    error(
+        format!("{}.{}", clave, campo),
+        format!("fuera de rango ({}..={})", min, max),
+    )
+}
+
+/// Comprueba que `nombre` exista entre los elementos declarados.
+fn referencia<T>(
+    elementos: &BTreeMap<String, T>,
+    clave: &str,
+    campo: &str,
+    nombre: &str,
+) -> Result<(), ConfigError> {
+    if elementos.contains_key(nombre) {
+        Ok(())
+    } else {
+        Err(error(
+            format!("{}.{}", clave, campo),
+            format!("no existe '{}'", nombre),
+        ))
+    }
+}
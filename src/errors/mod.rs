@@ -0,0 +1,100 @@
+use std::fmt;
+use std::io;
+
+use hardware::errors::CalibracionError;
+
+/// Error al cargar un archivo de configuración.
+///
+/// `clave` es la ruta dentro del archivo, por ejemplo `raciones.manana.ciclo_espera`
+/// o `raciones.manana.ciclos[2]`.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ConfigError {
+    /// No se pudo leer el archivo.
+    Archivo(io::ErrorKind),
+    /// La extensión del archivo no es `.toml`, `.yaml`, `.yml` ni `.json`.
+    Formato(String),
+    /// El archivo no se pudo interpretar: sintaxis inválida, un tipo incorrecto,
+    /// una clave desconocida o una clave obligatoria ausente.
+    Sintaxis { clave: String, mensaje: String },
+    /// El valor de `clave` está fuera de rango o hace referencia a algo que no existe.
+    Valor { clave: String, mensaje: String },
+    /// La configuración no tiene una ración con ese nombre.
+    RacionDesconocida(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Archivo(error) => write!(f, "No se pudo leer el archivo: {}", error),
+            ConfigError::Formato(extension) => {
+                write!(f, "Formato de configuración no soportado: '{}'", extension)
+            }
+            ConfigError::Sintaxis { clave, mensaje } | ConfigError::Valor { clave, mensaje } => {
+                write!(f, "{}: {}", clave, mensaje)
+            }
+            ConfigError::RacionDesconocida(nombre) => {
+                write!(f, "No existe la ración '{}'", nombre)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Error al ejecutar un subcomando de `f1`.
+#[derive(Debug, Clone)]
+pub enum ComandoError {
+    /// La configuración o el archivo de estado no son válidos.
+    Config(ConfigError),
+    /// La calibración no se pudo ajustar, leer o guardar.
+    Calibracion(CalibracionError),
+    /// La configuración no tiene un dosificador con ese nombre.
+    DosificadorDesconocido(String),
+}
+
+impl fmt::Display for ComandoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComandoError::Config(error) => write!(f, "{}", error),
+            ComandoError::Calibracion(error) => match error {
+                CalibracionError::MuestraInvalida => {
+                    write!(f, "Las muestras deben durar más de 0 ms")
+                }
+                CalibracionError::MuestrasInsuficientes => write!(
+                    f,
+                    "Se necesitan muestras de al menos dos duraciones distintas"
+                ),
+                CalibracionError::CurvaInvalida => {
+                    write!(f, "Las muestras no dan un caudal positivo")
+                }
+                CalibracionError::NombreInvalido => write!(
+                    f,
+                    "Los nombres no pueden estar vacíos ni contener ';' o saltos de línea"
+                ),
+                CalibracionError::Formato(linea) => {
+                    write!(f, "Archivo de calibraciones inválido en la línea {}", linea)
+                }
+                CalibracionError::Archivo(error) => {
+                    write!(f, "No se pudo acceder a las calibraciones: {}", error)
+                }
+            },
+            ComandoError::DosificadorDesconocido(nombre) => {
+                write!(f, "No existe el dosificador '{}'", nombre)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComandoError {}
+
+impl From<ConfigError> for ComandoError {
+    fn from(error: ConfigError) -> Self {
+        ComandoError::Config(error)
+    }
+}
+
+impl From<CalibracionError> for ComandoError {
+    fn from(error: CalibracionError) -> Self {
+        ComandoError::Calibracion(error)
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use system::reporte::Reporte;
+use types::unidades::{Gramos, Tiempo};
+
+use crate::config::Configuracion;
+use crate::errors::ConfigError;
+
+/// Resumen de la última entrega de una ración.
+///
+/// `inicio` y `termino` están en milisegundos desde la época Unix.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct UltimaEntrega {
+    pub inicio: Tiempo,
+    pub termino: Tiempo,
+    pub solicitado: Gramos,
+    pub entregado: Gramos,
+    pub fin: String,
+}
+
+/// Estado de la planta que se conserva entre ejecuciones de `f1`: el alimento que
+/// queda en cada silo y la última entrega de cada ración.
+///
+/// Se guarda como TOML. Un archivo inexistente equivale a un estado vacío, en el que
+/// los silos tienen el alimento de la configuración.
+///
+/// # Ejemplo:
+///! ```
+///! let mut estado = EstadoPlanta::cargar("f1.estado.toml")?;
+///! estado.restaurar(&mut config)?;
+///! let reporte = config.iniciar("manana", reloj::real())?;
+///! estado.registrar("manana", &reporte, &config);
+///! estado.guardar("f1.estado.toml")?;
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct EstadoPlanta {
+    /// Alimento de cada silo.
+    #[serde(default)]
+    silos: BTreeMap<String, Gramos>,
+    #[serde(default)]
+    raciones: BTreeMap<String, UltimaEntrega>,
+}
+
+impl EstadoPlanta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lee el estado guardado en `ruta`. Si el archivo no existe retorna un estado vacío.
+    pub fn cargar<P: AsRef<Path>>(ruta: P) -> Result<Self, ConfigError> {
+        let texto = match fs::read_to_string(ruta) {
+            Ok(texto) => texto,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(ConfigError::Archivo(e.kind())),
+        };
+        serde_path_to_error::deserialize(toml::Deserializer::new(&texto)).map_err(|error| {
+            ConfigError::Sintaxis {
+                clave: error.path().to_string(),
+                mensaje: error.into_inner().to_string(),
+            }
+        })
+    }
+
+    /// Escribe el estado en `ruta`, reemplazando su contenido.
+    pub fn guardar<P: AsRef<Path>>(&self, ruta: P) -> Result<(), ConfigError> {
+        let texto = toml::to_string(self).map_err(|error| ConfigError::Sintaxis {
+            clave: String::new(),
+            mensaje: error.to_string(),
+        })?;
+        fs::write(ruta, texto).map_err(|e| ConfigError::Archivo(e.kind()))
+    }
+
+    /// Carga en los silos de `config` el alimento guardado. Los silos que no aparecen
+    /// en el estado conservan el alimento de la configuración.
+    ///
+    /// # Errores:
+    /// - `ConfigError::Valor`: El alimento guardado supera la capacidad actual del silo.
+    pub fn restaurar(&self, config: &mut Configuracion) -> Result<(), ConfigError> {
+        for (nombre, gramos) in &self.silos {
+            let Some(silo) = config.get_silo_mut(nombre) else {
+                continue;
+            };
+            silo.set_alimento_gramos(*gramos)
+                .map_err(|_| ConfigError::Valor {
+                    clave: format!("silos.{}", nombre),
+                    mensaje: format!("el estado guardado ({}) supera la capacidad", gramos),
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Anota la entrega de `racion` y el alimento que queda en los silos de `config`.
+    pub fn registrar(&mut self, racion: &str, reporte: &Reporte, config: &Configuracion) {
+        self.raciones.insert(
+            racion.to_string(),
+            UltimaEntrega {
+                inicio: reporte.inicio,
+                termino: reporte.termino,
+                solicitado: reporte.solicitado,
+                entregado: reporte.entregado,
+                fin: format!("{:?}", reporte.fin),
+            },
+        );
+        for nombre in config.get_silos() {
+            let gramos = config
+                .get_silo(nombre)
+                .map(|silo| silo.get_alimento_gramos())
+                .unwrap_or_default();
+            self.silos.insert(nombre.to_string(), gramos);
+        }
+    }
+
+    /// Obtiene el alimento guardado del silo.
+    pub fn get_silo(&self, nombre: &str) -> Option<Gramos> {
+        self.silos.get(nombre).copied()
+    }
+
+    pub fn get_racion(&self, nombre: &str) -> Option<&UltimaEntrega> {
+        self.raciones.get(nombre)
+    }
+}
+
+impl fmt::Display for EstadoPlanta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.silos.is_empty() && self.raciones.is_empty() {
+            return writeln!(f, "Sin estado guardado");
+        }
+        for (nombre, gramos) in &self.silos {
+            writeln!(f, "[Silo][{}]: {}", nombre, gramos)?;
+        }
+        for (nombre, entrega) in &self.raciones {
+            writeln!(
+                f,
+                "[Racion][{}]: {} de {}, {}, {} (inicio {})",
+                nombre,
+                entrega.entregado,
+                entrega.solicitado,
+                entrega.fin,
+                entrega.termino.saturating_sub(entrega.inicio),
+                entrega.inicio.0
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod comandos;
+pub mod config;
+pub mod estado;
+
+pub mod errors;
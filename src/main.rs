@@ -1,42 +1,183 @@
-use hardware::dosificador::Dosificador;
-use hardware::silo::Silo;
-use hardware::soplador::Soplador;
-use system::ciclo::Ciclo;
-use system::programa::Programa;
-use system::racion::Racion;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
 
-fn main() {
-    let mut c1 = Ciclo::new();
-    c1.set_pulsos(50)
-        .expect("Ha intentado establer una cantidad de pulsos fuera del rango permitido");
-    c1.set_pulso_duracion(5000)
-        .expect("Ha intentado establecer una duracion de pulso fuera del rango permitido");
-    c1.set_pulso_espera(8000)
-        .expect("Ha intentado establecer una espera entre pulsos fuera del rango permitido");
+use clap::{Parser, Subcommand, ValueEnum};
+use f1::comandos;
+use f1::config::Configuracion;
+use f1::errors::ComandoError;
+use f1::estado::EstadoPlanta;
+use hardware::calibracion::{Calibraciones, Muestra};
+use system::logs::Print;
+use system::reporte::Reporte;
+use types::eventos::{self, Consola, Severidad};
+use types::unidades::{Gramos, Milisegundos};
+use utils::reloj::{self, RelojManual, RelojRef};
+
+/// Control de alimentación de la planta.
+#[derive(Parser)]
+#[command(name = "f1", version)]
+struct Cli {
+    /// Archivo de configuración (TOML, YAML o JSON).
+    #[arg(short, long, global = true, default_value = "f1.toml")]
+    config: PathBuf,
+
+    /// Archivo con el estado que se conserva entre ejecuciones.
+    #[arg(short, long, global = true, default_value = "f1.estado.toml")]
+    estado: PathBuf,
+
+    /// Archivo con las curvas calibradas de los dosificadores.
+    #[arg(long, global = true, default_value = "calibraciones.txt")]
+    calibraciones: PathBuf,
+
+    /// Severidad mínima de los eventos que se muestran.
+    #[arg(short, long, global = true, value_enum, default_value_t = Nivel::Info)]
+    nivel: Nivel,
+
+    #[command(subcommand)]
+    comando: Comando,
+}
+
+#[derive(Subcommand)]
+enum Comando {
+    /// Ejecuta una ración, o todas en orden alfabético, y guarda el estado.
+    Run { racion: Option<String> },
+    /// Comprueba la configuración contra los rangos permitidos.
+    Validate,
+    /// Ejecuta con tiempo virtual sin modificar el estado guardado.
+    Simulate { racion: Option<String> },
+    /// Muestra el estado guardado.
+    Status,
+    /// Ajusta la curva de un dosificador con pulsos de prueba pesados.
+    Calibrate {
+        dosificador: String,
+        alimento: String,
+        /// Pulsos de prueba como `ms:gramos`, por ejemplo `2000:3450`.
+        #[arg(required = true, value_parser = muestra)]
+        muestras: Vec<Muestra>,
+    },
+}
 
-    let mut c2 = Ciclo::new();
-    c2.set_pulsos(20)
-        .expect("Ha intentado establer una cantidad de pulsos fuera del rango permitido");
-    c2.set_pulso_duracion(3000)
-        .expect("Ha intentado establecer una duracion de pulso fuera del rango permitido");
-    c2.set_pulso_espera(4000)
-        .expect("Ha intentado establecer una espera entre pulsos fuera del rango permitido");
+fn muestra(texto: &str) -> Result<Muestra, String> {
+    let error = || format!("'{}' no tiene la forma ms:gramos", texto);
+    let (duracion, gramos) = texto.split_once(':').ok_or_else(error)?;
+    Ok(Muestra {
+        duracion: Milisegundos(duracion.trim().parse().map_err(|_| error())?),
+        gramos: Gramos(gramos.trim().parse().map_err(|_| error())?),
+    })
+}
 
-    let mut racion = Racion::new(vec![&c1, &c2, &c1, &c1]);
+/// Severidad mínima de los eventos que se muestran en la consola.
+#[derive(Clone, Copy, ValueEnum)]
+enum Nivel {
+    Depuracion,
+    Info,
+    Aviso,
+    Error,
+    /// No muestra eventos.
+    Silencio,
+}
 
-    let mut doser: Dosificador = Dosificador::new(2);
-    let mut soplador: Soplador = Soplador::new();
+impl Nivel {
+    /// `None` es el modo silencioso.
+    fn severidad(self) -> Option<Severidad> {
+        match self {
+            Nivel::Depuracion => Some(Severidad::Depuracion),
+            Nivel::Info => Some(Severidad::Info),
+            Nivel::Aviso => Some(Severidad::Aviso),
+            Nivel::Error => Some(Severidad::Error),
+            Nivel::Silencio => None,
+        }
+    }
+}
 
-    let mut silo: Silo = Silo::new(24000);
+fn main() {
+    let cli = Cli::parse();
+    match cli.nivel.severidad() {
+        Some(minima) => eventos::set_sumidero(Arc::new(Consola::new(minima))),
+        None => eventos::silenciar(),
+    }
 
-    silo.set_alimento(24000)
-        .expect("Ha intentado ingresar mas alimento que la capacidad del silo");
+    if let Err(error) = correr(cli) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
 
-    racion
-        .set_ciclo_espera(60000)
-        .expect("Ha intentado establecer una espera entre ciclos fuera del rango permitido");
+fn correr(cli: Cli) -> Result<(), ComandoError> {
+    match cli.comando {
+        Comando::Validate => {
+            let config = cargar(&cli)?;
+            println!(
+                "{}: configuración válida, {} raciones ({})",
+                cli.config.display(),
+                config.get_raciones().len(),
+                config.get_raciones().join(", ")
+            );
+        }
+        Comando::Run { ref racion } => {
+            let mut config = cargar(&cli)?;
+            let mut estado = EstadoPlanta::cargar(&cli.estado)?;
+            let resultado =
+                comandos::ejecutar(&mut config, racion.as_deref(), reloj::real(), &mut estado);
+            // El estado se guarda aunque falle una ración, para no perder las anteriores
+            estado.guardar(&cli.estado)?;
+            imprimir(&resultado?);
+        }
+        Comando::Simulate { ref racion } => {
+            let mut config = cargar(&cli)?;
+            let mut estado = EstadoPlanta::cargar(&cli.estado)?;
+            let inicio = reloj::real().ahora();
+            let virtual_: RelojRef = Arc::new(RelojManual::new(inicio));
+            let reloj = virtual_.clone();
+            eventos::set_reloj(Arc::new(move || reloj.ahora()));
+            let reportes = comandos::ejecutar(
+                &mut config,
+                racion.as_deref(),
+                virtual_.clone(),
+                &mut estado,
+            )?;
+            imprimir(&reportes);
+            println!(
+                "Simulación: {} ms de tiempo virtual",
+                virtual_.ahora() - inicio
+            );
+        }
+        Comando::Status => {
+            print!("{}", EstadoPlanta::cargar(&cli.estado)?);
+        }
+        Comando::Calibrate {
+            ref dosificador,
+            ref alimento,
+            ref muestras,
+        } => {
+            let config = cargar(&cli)?;
+            let mut calibraciones = Calibraciones::cargar(&cli.calibraciones)?;
+            let curva =
+                comandos::calibrar(&config, &mut calibraciones, dosificador, alimento, muestras)?;
+            calibraciones.guardar(&cli.calibraciones)?;
+            println!(
+                "[Dosificador][{}][{}]: {}, desfase {} g",
+                dosificador,
+                alimento,
+                curva.get_entrega(),
+                curva.get_desfase()
+            );
+        }
+    }
+    Ok(())
+}
 
-    let mut programa: Programa = Programa::new(&mut racion);
+/// Carga la configuración con las curvas calibradas de los dosificadores.
+fn cargar(cli: &Cli) -> Result<Configuracion, ComandoError> {
+    let mut config = Configuracion::cargar(&cli.config)?;
+    config.set_calibraciones(Calibraciones::cargar(&cli.calibraciones)?);
+    Ok(config)
+}
 
-    programa.iniciar(&mut soplador, &mut doser, &mut silo);
+fn imprimir(reportes: &[(String, Reporte)]) {
+    for (racion, reporte) in reportes {
+        println!("[Racion][{}]", racion);
+        reporte.print();
+    }
 }
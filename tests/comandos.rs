@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use f1::comandos::{calibrar, ejecutar};
+use f1::config::{Configuracion, Formato};
+use f1::errors::{ComandoError, ConfigError};
+use f1::estado::EstadoPlanta;
+use hardware::calibracion::{Calibraciones, Curva, Muestra};
+use hardware::errors::CalibracionError;
+use system::control::Fin;
+use types::unidades::{Gramos, Milisegundos, Tiempo};
+use utils::reloj::{Reloj, RelojManual, RelojRef};
+
+const CONFIG: &str = r#"
+silos:
+  silo-1: { capacidad: 24000, alimento: 24000 }
+dosificadores:
+  linea-1: { entrega: 2000 }
+sopladores:
+  soplador-1: {}
+ciclos:
+  c1: { pulsos: 5 }
+raciones:
+  manana:
+    ciclos: [c1]
+    soplador: soplador-1
+    dosificador: linea-1
+    silo: silo-1
+    alimento: pellet-4mm
+  tarde:
+    ciclos: [c1, c1]
+    soplador: soplador-1
+    dosificador: linea-1
+    silo: silo-1
+"#;
+
+#[cfg(test)]
+mod comandos {
+    use super::*;
+
+    fn config() -> Configuracion {
+        Configuracion::leer(CONFIG, Formato::Yaml).unwrap()
+    }
+
+    fn muestra(duracion: u32, gramos: u32) -> Muestra {
+        Muestra {
+            duracion: Milisegundos(duracion),
+            gramos: Gramos(gramos),
+        }
+    }
+
+    mod ejecutar {
+        use super::*;
+        use types::unidades::GramosPorSegundo;
+
+        #[test]
+        fn test_todas_las_raciones() {
+            let mut config = config();
+            let mut estado = EstadoPlanta::new();
+            let reloj = Arc::new(RelojManual::new(0));
+            let reloj_ref: RelojRef = reloj.clone();
+
+            let reportes = ejecutar(&mut config, None, reloj_ref, &mut estado).unwrap();
+
+            let nombres: Vec<&str> = reportes.iter().map(|(n, _)| n.as_str()).collect();
+            assert_eq!(nombres, vec!["manana", "tarde"]);
+            assert!(reportes.iter().all(|(_, r)| r.fin == Fin::Completado));
+            assert_eq!(Tiempo(reloj.ahora()), reportes[1].1.termino);
+
+            let entregado: Gramos = reportes.iter().map(|(_, r)| r.entregado).sum();
+            assert_eq!(
+                estado.get_silo("silo-1"),
+                Some(Gramos(24_000_000) - entregado)
+            );
+            assert!(estado.get_racion("tarde").is_some());
+        }
+
+        #[test]
+        fn test_parte_del_estado() {
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let mut estado = EstadoPlanta::new();
+            ejecutar(&mut config(), Some("tarde"), reloj.clone(), &mut estado).unwrap();
+            let antes = estado.get_silo("silo-1").unwrap();
+
+            // Una configuración recién cargada continúa desde el alimento guardado
+            let reportes = ejecutar(&mut config(), Some("tarde"), reloj, &mut estado).unwrap();
+            assert_eq!(
+                estado.get_silo("silo-1"),
+                Some(antes - reportes[0].1.entregado)
+            );
+        }
+
+        #[test]
+        fn test_racion_desconocida() {
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let error = ejecutar(
+                &mut config(),
+                Some("noche"),
+                reloj,
+                &mut EstadoPlanta::new(),
+            )
+            .unwrap_err();
+            assert_eq!(error, ConfigError::RacionDesconocida("noche".to_string()));
+        }
+
+        #[test]
+        fn test_usa_la_curva_calibrada() {
+            let mut calibraciones = Calibraciones::new();
+            calibraciones
+                .set_curva(
+                    "linea-1",
+                    "pellet-4mm",
+                    Curva::new(GramosPorSegundo(1000), 0),
+                )
+                .unwrap();
+            let mut config = config();
+            config.set_calibraciones(calibraciones);
+
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            let reportes = ejecutar(&mut config, None, reloj, &mut EstadoPlanta::new()).unwrap();
+
+            // "manana" usa la curva de 1000 g/s y "tarde" la entrega nominal de 2000 g/s
+            let manana = &reportes[0].1;
+            let tarde = &reportes[1].1;
+            assert_eq!(manana.entregado.0 * 4, tarde.entregado.0);
+        }
+    }
+
+    mod calibrar {
+        use super::*;
+        use types::unidades::GramosPorSegundo;
+
+        #[test]
+        fn test_ajusta_y_guarda() {
+            let mut calibraciones = Calibraciones::new();
+            let curva = calibrar(
+                &config(),
+                &mut calibraciones,
+                "linea-1",
+                "pellet-4mm",
+                &[
+                    muestra(2000, 3450),
+                    muestra(5000, 8850),
+                    muestra(8000, 14250),
+                ],
+            )
+            .unwrap();
+
+            assert_eq!(curva, Curva::new(GramosPorSegundo(1800), -150));
+            assert_eq!(
+                calibraciones.get_curva("linea-1", "pellet-4mm"),
+                Some(curva)
+            );
+        }
+
+        #[test]
+        fn test_dosificador_desconocido() {
+            let error = calibrar(
+                &config(),
+                &mut Calibraciones::new(),
+                "linea-9",
+                "pellet-4mm",
+                &[muestra(2000, 3450), muestra(5000, 8850)],
+            )
+            .unwrap_err();
+            assert!(matches!(error, ComandoError::DosificadorDesconocido(n) if n == "linea-9"));
+        }
+
+        #[test]
+        fn test_muestras_insuficientes() {
+            let mut calibraciones = Calibraciones::new();
+            let error = calibrar(
+                &config(),
+                &mut calibraciones,
+                "linea-1",
+                "pellet-4mm",
+                &[muestra(2000, 3450)],
+            )
+            .unwrap_err();
+            assert!(matches!(
+                error,
+                ComandoError::Calibracion(CalibracionError::MuestrasInsuficientes)
+            ));
+            assert!(calibraciones.is_empty());
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use std::sync::Arc;
+
+use f1::config::{Configuracion, Formato};
+use f1::errors::ConfigError;
+use hardware::rampa::Perfil;
+use system::control::Fin;
+use utils::reloj::{Reloj, RelojManual, RelojRef};
+
+const TOML: &str = r#"
+[silos.silo-1]
+capacidad = 24000
+alimento = 24000
+
+[dosificadores.linea-1]
+entrega = 2000
+
+[sopladores.soplador-1]
+potencia = 80
+rampa = { perfil = "curva_s", subida = 3000, bajada = 2000 }
+
+[selectoras.selectora-1]
+salidas = 4
+espera = 2000
+
+[ciclos.c1]
+pulsos = 5
+pulso_duracion = 1000
+pulso_espera = 1000
+salida = 1
+
+[ciclos.c2]
+pulsos = 2
+
+[raciones.manana]
+ciclos = ["c1", "c2"]
+ciclo_espera = 60000
+salida = 3
+soplador = "soplador-1"
+dosificador = "linea-1"
+silo = "silo-1"
+selectora = "selectora-1"
+"#;
+
+const YAML: &str = r#"
+silos:
+  silo-1: { capacidad: 24000, alimento: 24000 }
+dosificadores:
+  linea-1: { entrega: 2000 }
+sopladores:
+  soplador-1: {}
+ciclos:
+  c1: { pulsos: 5 }
+raciones:
+  tarde:
+    ciclos: [c1]
+    soplador: soplador-1
+    dosificador: linea-1
+    silo: silo-1
+"#;
+
+const JSON: &str = r#"{
+  "silos": { "silo-1": { "capacidad": 24000, "alimento": 24000 } },
+  "dosificadores": { "linea-1": { "entrega": 2000 } },
+  "sopladores": { "soplador-1": { "potencia": 60 } },
+  "ciclos": { "c1": { "pulsos": 5 } },
+  "raciones": {
+    "noche": {
+      "ciclos": ["c1", "c1"],
+      "soplador": "soplador-1",
+      "dosificador": "linea-1",
+      "silo": "silo-1"
+    }
+  }
+}"#;
+
+#[cfg(test)]
+mod config {
+    use super::*;
+
+    fn error_de(texto: &str, formato: Formato) -> ConfigError {
+        match Configuracion::leer(texto, formato) {
+            Ok(_) => panic!("La configuración debió ser rechazada"),
+            Err(error) => error,
+        }
+    }
+
+    fn clave_de(error: ConfigError) -> String {
+        match error {
+            ConfigError::Sintaxis { clave, .. } | ConfigError::Valor { clave, .. } => clave,
+            otro => panic!("Error inesperado: {:?}", otro),
+        }
+    }
+
+    mod formatos {
+        use super::*;
+        use types::unidades::{Kilogramos, Milisegundos, Porcentaje};
+
+        #[test]
+        fn test_toml() {
+            let config = Configuracion::leer(TOML, Formato::Toml).unwrap();
+
+            assert_eq!(
+                config.get_silo("silo-1").unwrap().get_alimento(),
+                Kilogramos(24000)
+            );
+            let soplador = config.get_soplador("soplador-1").unwrap();
+            assert_eq!(soplador.get_potencia(), Porcentaje(80));
+            assert_eq!(soplador.get_rampa().get_perfil(), Perfil::CurvaS);
+            assert_eq!(
+                config
+                    .get_selectora("selectora-1")
+                    .unwrap()
+                    .get_posiciones()
+                    .max,
+                3
+            );
+            assert_eq!(config.get_ciclo("c1").unwrap().get_salida(), Some(1));
+
+            let racion = config.get_racion("manana").unwrap();
+            assert_eq!(racion.get_ciclos().len(), 2);
+            assert_eq!(racion.get_ciclo_espera(), Milisegundos(60000));
+            assert_eq!(racion.get_salida(), Some(3));
+        }
+
+        #[test]
+        fn test_yaml() {
+            let config = Configuracion::leer(YAML, Formato::Yaml).unwrap();
+            assert_eq!(config.get_raciones(), vec!["tarde"]);
+            assert_eq!(
+                config.get_definicion("tarde").unwrap().get_selectora(),
+                None
+            );
+        }
+
+        #[test]
+        fn test_json() {
+            let config = Configuracion::leer(JSON, Formato::Json).unwrap();
+            assert_eq!(
+                config.get_soplador("soplador-1").unwrap().get_potencia(),
+                Porcentaje(60)
+            );
+            assert_eq!(config.get_racion("noche").unwrap().get_ciclos().len(), 2);
+        }
+
+        #[test]
+        fn test_desde_ruta() {
+            assert_eq!(Formato::desde_ruta("f1.toml"), Ok(Formato::Toml));
+            assert_eq!(Formato::desde_ruta("f1.yml"), Ok(Formato::Yaml));
+            assert_eq!(Formato::desde_ruta("f1.JSON"), Ok(Formato::Json));
+            assert_eq!(
+                Formato::desde_ruta("f1.ini"),
+                Err(ConfigError::Formato("ini".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_archivo_de_ejemplo() {
+            let ruta = concat!(env!("CARGO_MANIFEST_DIR"), "/f1.toml");
+            let config = Configuracion::cargar(ruta).unwrap();
+            assert_eq!(
+                config.get_racion("principal").unwrap().get_ciclos().len(),
+                4
+            );
+        }
+
+        #[test]
+        fn test_archivo_inexistente() {
+            assert!(matches!(
+                Configuracion::cargar("no-existe.toml"),
+                Err(ConfigError::Archivo(_))
+            ));
+        }
+    }
+
+    mod errores {
+        use super::*;
+
+        #[test]
+        fn test_fuera_de_rango() {
+            let texto = TOML.replace("pulsos = 5", "pulsos = 20000");
+            let error = error_de(&texto, Formato::Toml);
+            assert_eq!(
+                error,
+                ConfigError::Valor {
+                    clave: "ciclos.c1.pulsos".to_string(),
+                    mensaje: "fuera de rango (0..=10000)".to_string(),
+                }
+            );
+            assert_eq!(
+                error.to_string(),
+                "ciclos.c1.pulsos: fuera de rango (0..=10000)"
+            );
+
+            let texto = TOML.replace("ciclo_espera = 60000", "ciclo_espera = 10");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "raciones.manana.ciclo_espera"
+            );
+
+            let texto = TOML.replace("potencia = 80", "potencia = 150");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "sopladores.soplador-1.potencia"
+            );
+
+            let texto = TOML.replace("alimento = 24000", "alimento = 30000");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "silos.silo-1.alimento"
+            );
+        }
+
+        #[test]
+        fn test_esperas_fuera_de_rango() {
+            let texto = TOML.replace("subida = 3000", "subida = 60001");
+            assert_eq!(
+                error_de(&texto, Formato::Toml),
+                ConfigError::Valor {
+                    clave: "sopladores.soplador-1.rampa.subida".to_string(),
+                    mensaje: "fuera de rango (0ms..=60000ms)".to_string(),
+                }
+            );
+
+            let texto = TOML.replace("bajada = 2000", "bajada = 120000");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "sopladores.soplador-1.rampa.bajada"
+            );
+
+            let texto = TOML.replace("espera = 2000", "espera = 120001");
+            assert_eq!(
+                error_de(&texto, Formato::Toml),
+                ConfigError::Valor {
+                    clave: "selectoras.selectora-1.espera".to_string(),
+                    mensaje: "fuera de rango (0ms..=120000ms)".to_string(),
+                }
+            );
+        }
+
+        #[test]
+        fn test_capacidad_fuera_de_rango() {
+            let texto = TOML.replace("capacidad = 24000", "capacidad = 5000000");
+            assert_eq!(
+                error_de(&texto, Formato::Toml),
+                ConfigError::Valor {
+                    clave: "silos.silo-1.capacidad".to_string(),
+                    mensaje: "fuera de rango (0kg..=4294967kg)".to_string(),
+                }
+            );
+        }
+
+        #[test]
+        fn test_clave_desconocida() {
+            let texto = TOML.replace("pulso_espera = 1000", "pulso_pausa = 1000");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "ciclos.c1.pulso_pausa"
+            );
+
+            let texto = JSON.replace("\"potencia\"", "\"fuerza\"");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Json)),
+                "sopladores.soplador-1.fuerza"
+            );
+        }
+
+        #[test]
+        fn test_tipo_incorrecto() {
+            let texto = YAML.replace("pulsos: 5", "pulsos: cinco");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Yaml)),
+                "ciclos.c1.pulsos"
+            );
+        }
+
+        #[test]
+        fn test_referencias() {
+            let texto = TOML.replace("ciclos = [\"c1\", \"c2\"]", "ciclos = [\"c1\", \"c3\"]");
+            let error = error_de(&texto, Formato::Toml);
+            assert_eq!(
+                error.to_string(),
+                "raciones.manana.ciclos[1]: no existe el ciclo 'c3'"
+            );
+
+            let texto = TOML.replace("silo = \"silo-1\"", "silo = \"silo-2\"");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "raciones.manana.silo"
+            );
+        }
+
+        #[test]
+        fn test_salidas() {
+            let texto = TOML.replace("salida = 3", "salida = 4");
+            assert_eq!(
+                error_de(&texto, Formato::Toml).to_string(),
+                "raciones.manana.salida: fuera de rango (0..=3)"
+            );
+
+            let texto = TOML.replace("selectora = \"selectora-1\"\n", "");
+            assert_eq!(
+                clave_de(error_de(&texto, Formato::Toml)),
+                "raciones.manana.salida"
+            );
+        }
+
+        #[test]
+        fn test_entrega_desbordada() {
+            let texto = TOML
+                .replace("entrega = 2000", "entrega = 50000")
+                .replace("pulsos = 5", "pulsos = 10000")
+                .replace("pulso_duracion = 1000", "pulso_duracion = 10000");
+            assert_eq!(
+                error_de(&texto, Formato::Toml).to_string(),
+                "raciones.manana.ciclos[0]: el ciclo 'c1' entrega más de 4294967295g \
+                 con el dosificador 'linea-1'"
+            );
+
+            // Cada ciclo cabe, pero no la ración completa
+            let texto = TOML
+                .replace("entrega = 2000", "entrega = 50000")
+                .replace("pulsos = 5", "pulsos = 5000")
+                .replace("pulso_duracion = 1000", "pulso_duracion = 10000")
+                .replace("ciclos = [\"c1\", \"c2\"]", "ciclos = [\"c1\", \"c1\"]");
+            assert_eq!(
+                error_de(&texto, Formato::Toml).to_string(),
+                "raciones.manana.ciclos: la ración entrega más de 4294967295g"
+            );
+        }
+    }
+
+    mod ejecucion {
+        use super::*;
+        use types::unidades::Gramos;
+
+        #[test]
+        fn test_iniciar() {
+            let reloj = Arc::new(RelojManual::new(0));
+            let reloj_ref: RelojRef = reloj.clone();
+            let mut config = Configuracion::leer(YAML, Formato::Yaml).unwrap();
+
+            let reporte = config.iniciar("tarde", reloj_ref).unwrap();
+
+            assert_eq!(reporte.fin, Fin::Completado);
+            assert_eq!(reporte.entregado, reporte.solicitado);
+            assert!(reloj.ahora() > 0);
+            assert_eq!(
+                config.get_silo("silo-1").unwrap().get_alimento_gramos(),
+                Gramos(24000 * 1000) - reporte.entregado
+            );
+        }
+
+        #[test]
+        fn test_racion_desconocida() {
+            let mut config = Configuracion::leer(YAML, Formato::Yaml).unwrap();
+            let reloj: RelojRef = Arc::new(RelojManual::new(0));
+            assert_eq!(
+                config.iniciar("manana", reloj).err(),
+                Some(ConfigError::RacionDesconocida("manana".to_string()))
+            );
+        }
+    }
+}
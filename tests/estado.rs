@@ -0,0 +1,118 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use f1::config::{Configuracion, Formato};
+use f1::errors::ConfigError;
+use f1::estado::EstadoPlanta;
+use types::unidades::{Gramos, Tiempo};
+use utils::reloj::{RelojManual, RelojRef};
+use uuid::Uuid;
+
+const CONFIG: &str = r#"
+silos:
+  silo-1: { capacidad: 100, alimento: 100 }
+dosificadores:
+  linea-1: { entrega: 2000 }
+sopladores:
+  soplador-1: {}
+ciclos:
+  c1: { pulsos: 5 }
+raciones:
+  tarde:
+    ciclos: [c1]
+    soplador: soplador-1
+    dosificador: linea-1
+    silo: silo-1
+"#;
+
+#[cfg(test)]
+mod estado {
+    use super::*;
+
+    fn ruta_temporal() -> PathBuf {
+        env::temp_dir().join(format!("estado-{}.toml", Uuid::new_v4()))
+    }
+
+    fn config() -> Configuracion {
+        Configuracion::leer(CONFIG, Formato::Yaml).unwrap()
+    }
+
+    mod archivo {
+        use super::*;
+
+        #[test]
+        fn test_inexistente_es_vacio() {
+            let estado = EstadoPlanta::cargar(ruta_temporal()).unwrap();
+            assert_eq!(estado, EstadoPlanta::new());
+            assert_eq!(estado.to_string(), "Sin estado guardado\n");
+        }
+
+        #[test]
+        fn test_guardar_y_cargar() {
+            let mut config = config();
+            let reloj: RelojRef = Arc::new(RelojManual::new(1000));
+            let reporte = config.iniciar("tarde", reloj).unwrap();
+
+            let mut estado = EstadoPlanta::new();
+            estado.registrar("tarde", &reporte, &config);
+
+            let ruta = ruta_temporal();
+            estado.guardar(&ruta).unwrap();
+            let leido = EstadoPlanta::cargar(&ruta).unwrap();
+            fs::remove_file(&ruta).unwrap();
+
+            assert_eq!(leido, estado);
+            assert_eq!(
+                leido.get_silo("silo-1"),
+                Some(Gramos(100_000) - reporte.entregado)
+            );
+            let entrega = leido.get_racion("tarde").unwrap();
+            assert_eq!(entrega.inicio, Tiempo(1000));
+            assert_eq!(entrega.entregado, reporte.entregado);
+            assert_eq!(entrega.fin, "Completado");
+        }
+
+        #[test]
+        fn test_clave_desconocida() {
+            let ruta = ruta_temporal();
+            fs::write(&ruta, "[silos]\nsilo-1 = 10\n[otros]\n").unwrap();
+            let error = EstadoPlanta::cargar(&ruta).unwrap_err();
+            fs::remove_file(&ruta).unwrap();
+
+            assert!(matches!(error, ConfigError::Sintaxis { clave, .. } if clave == "otros"));
+        }
+    }
+
+    mod restaurar {
+        use super::*;
+
+        #[test]
+        fn test_silos() {
+            let ruta = ruta_temporal();
+            fs::write(&ruta, "[silos]\nsilo-1 = 40000\nsilo-viejo = 10\n").unwrap();
+            let estado = EstadoPlanta::cargar(&ruta).unwrap();
+            fs::remove_file(&ruta).unwrap();
+
+            let mut config = config();
+            estado.restaurar(&mut config).unwrap();
+            assert_eq!(
+                config.get_silo("silo-1").unwrap().get_alimento_gramos(),
+                Gramos(40000)
+            );
+        }
+
+        #[test]
+        fn test_supera_capacidad() {
+            let ruta = ruta_temporal();
+            fs::write(&ruta, "[silos]\nsilo-1 = 200000\n").unwrap();
+            let estado = EstadoPlanta::cargar(&ruta).unwrap();
+            fs::remove_file(&ruta).unwrap();
+
+            let mut config = config();
+            let error = estado.restaurar(&mut config).unwrap_err();
+            assert!(matches!(error, ConfigError::Valor { clave, .. } if clave == "silos.silo-1"));
+        }
+    }
+}
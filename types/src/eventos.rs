@@ -0,0 +1,236 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+/// Gravedad de un `Evento`, de menor a mayor.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Severidad {
+    /// Cambios de valores y pasos internos, útiles al diagnosticar.
+    Depuracion,
+    /// El avance normal de la operación.
+    Info,
+    /// Algo no salió como se pidió pero la operación continúa, por ejemplo un valor fuera de rango.
+    Aviso,
+    /// Una falla que detiene o compromete la operación.
+    Error,
+}
+
+/// Evento emitido por un dispositivo o componente.
+///
+/// # Campos:
+/// - `instante`: Milisegundos desde la época Unix en que se emitió, según `set_reloj`.
+/// - `severidad`: La gravedad del evento.
+/// - `origen`: El tipo de componente que lo emite, por ejemplo `"Silo"` o `"Ciclo"`.
+/// - `id`: El identificador del componente, si lo tiene.
+/// - `campo`: El valor que cambió, si el evento es un cambio de valor.
+/// - `anterior` y `nuevo`: El valor antes y después del cambio, como número; un tiempo
+///   en milisegundos.
+/// - `mensaje`: Descripción legible del evento.
+///
+/// # Ejemplo:
+///! ```
+///! eventos::emitir(
+///!     Evento::new(Severidad::Info, "Silo", "Llenado")
+///!         .id(silo.get_id())
+///!         .cambio("alimento", 0, 24000),
+///! );
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone)]
+pub struct Evento {
+    pub instante: u64,
+    pub severidad: Severidad,
+    pub origen: String,
+    pub id: Option<Uuid>,
+    pub campo: Option<String>,
+    pub anterior: Option<f64>,
+    pub nuevo: Option<f64>,
+    pub mensaje: String,
+}
+
+impl Evento {
+    /// Crea un evento sin `id` ni cambio de valor. El `instante` se asigna al emitirlo.
+    pub fn new<O: Into<String>, M: Into<String>>(
+        severidad: Severidad,
+        origen: O,
+        mensaje: M,
+    ) -> Self {
+        Self {
+            instante: 0,
+            severidad,
+            origen: origen.into(),
+            id: None,
+            campo: None,
+            anterior: None,
+            nuevo: None,
+            mensaje: mensaje.into(),
+        }
+    }
+
+    /// Indica el componente que emite el evento.
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Indica que el evento es el cambio de `campo` desde `anterior` a `nuevo`.
+    pub fn cambio<C: Into<String>, V: Into<f64>>(
+        mut self,
+        campo: C,
+        anterior: V,
+        nuevo: V,
+    ) -> Self {
+        self.campo = Some(campo.into());
+        self.anterior = Some(anterior.into());
+        self.nuevo = Some(nuevo.into());
+        self
+    }
+}
+
+impl fmt::Display for Evento {
+    /// Formato de una línea: `[origen][id][campo]: mensaje`, omitiendo lo que el evento no tiene.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.origen)?;
+        if let Some(id) = self.id {
+            write!(f, "[{}]", id)?;
+        }
+        if let Some(campo) = &self.campo {
+            write!(f, "[{}]", campo)?;
+        }
+        write!(f, ": {}", self.mensaje)
+    }
+}
+
+/// Destino de los eventos emitidos.
+///
+/// Se instala con `set_sumidero` y recibe los eventos de todos los componentes, por lo
+/// que debe poder usarse desde varios hilos.
+pub trait Sumidero: Send + Sync {
+    fn recibir(&self, evento: &Evento);
+}
+
+/// Escribe en la salida estándar los eventos de al menos la severidad `minima`.
+/// Un programa que quiera ver los eventos en la terminal la instala con `set_sumidero`.
+pub struct Consola {
+    minima: Severidad,
+}
+
+impl Consola {
+    pub fn new(minima: Severidad) -> Self {
+        Self { minima }
+    }
+}
+
+impl Default for Consola {
+    fn default() -> Self {
+        Self::new(Severidad::Depuracion)
+    }
+}
+
+impl Sumidero for Consola {
+    fn recibir(&self, evento: &Evento) {
+        if evento.severidad >= self.minima {
+            // Una salida cerrada, por ejemplo al filtrar con `head`, no debe detener el proceso
+            let _ = writeln!(io::stdout().lock(), "{}", evento);
+        }
+    }
+}
+
+/// Descarta todos los eventos. Es el sumidero por defecto, para que usar los componentes
+/// como biblioteca no escriba en la salida estándar.
+pub struct Silencio;
+
+impl Sumidero for Silencio {
+    fn recibir(&self, _evento: &Evento) {}
+}
+
+/// Guarda los eventos en memoria para consultarlos después, por ejemplo en pruebas o
+/// para reenviarlos a otro sistema.
+#[derive(Default)]
+pub struct Memoria {
+    eventos: Mutex<Vec<Evento>>,
+}
+
+impl Memoria {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Obtiene una copia de los eventos recibidos.
+    pub fn get_eventos(&self) -> Vec<Evento> {
+        self.eventos.lock().unwrap().clone()
+    }
+
+    /// Obtiene los eventos emitidos por el componente `id`.
+    pub fn get_eventos_de(&self, id: Uuid) -> Vec<Evento> {
+        self.eventos
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|evento| evento.id == Some(id))
+            .cloned()
+            .collect()
+    }
+
+    /// Descarta los eventos recibidos.
+    pub fn limpiar(&self) {
+        self.eventos.lock().unwrap().clear();
+    }
+}
+
+impl Sumidero for Memoria {
+    fn recibir(&self, evento: &Evento) {
+        self.eventos.lock().unwrap().push(evento.clone());
+    }
+}
+
+/// Función que entrega el instante actual en milisegundos desde la época Unix.
+pub type FuenteInstante = Arc<dyn Fn() -> u64 + Send + Sync>;
+
+static SUMIDERO: RwLock<Option<Arc<dyn Sumidero>>> = RwLock::new(None);
+static RELOJ: RwLock<Option<FuenteInstante>> = RwLock::new(None);
+
+/// Instala el sumidero que recibe los eventos de todo el proceso.
+///
+/// # Ejemplo:
+///! ```
+///! let memoria = Arc::new(Memoria::new());
+///! eventos::set_sumidero(memoria.clone());
+///! silo.set_alimento(1000)?;
+///! assert_eq!(memoria.get_eventos_de(silo.get_id()).len(), 1);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub fn set_sumidero(sumidero: Arc<dyn Sumidero>) {
+    *SUMIDERO.write().unwrap() = Some(sumidero);
+}
+
+/// Descarta los eventos de aquí en adelante. Equivale a `set_sumidero(Arc::new(Silencio))`.
+pub fn silenciar() {
+    set_sumidero(Arc::new(Silencio));
+}
+
+/// Instala la función con la que se marca el `instante` de los eventos, por ejemplo
+/// el `ahora` de un reloj simulado. Por defecto se usa el reloj del sistema.
+pub fn set_reloj(reloj: FuenteInstante) {
+    *RELOJ.write().unwrap() = Some(reloj);
+}
+
+/// Marca el instante del evento y lo entrega al sumidero instalado. Si no se instaló
+/// ninguno el evento se descarta, como con `Silencio`.
+pub fn emitir(mut evento: Evento) {
+    evento.instante = match RELOJ.read().unwrap().as_ref() {
+        Some(reloj) => reloj(),
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+    };
+
+    let sumidero = SUMIDERO.read().unwrap().clone();
+    if let Some(sumidero) = sumidero {
+        sumidero.recibir(&evento);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// Número decimal de punto fijo con `DECIMALES` cifras después de la coma.
+///
+/// Se guarda como un entero de unidades de `10^-DECIMALES`, por lo que no acumula el
+/// error de redondeo de un `f64` y se compara y ordena como un entero.
+///
+/// # Ejemplo:
+///! ```rust
+///! let kilos: Fijo<3> = Fijo::new(1250); // 1.250
+///! assert_eq!(kilos.to_string(), "1.250");
+///! assert_eq!(Fijo::<3>::entero(2).get_unidades(), 2000);
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Fijo<const DECIMALES: u32>(i64);
+
+impl<const DECIMALES: u32> Fijo<DECIMALES> {
+    /// Cantidad de unidades en un entero.
+    pub const ESCALA: i64 = 10i64.pow(DECIMALES);
+
+    /// Crea el número a partir de sus unidades de `10^-DECIMALES`.
+    pub const fn new(unidades: i64) -> Self {
+        Self(unidades)
+    }
+
+    /// Crea el número entero `n`.
+    pub const fn entero(n: i64) -> Self {
+        Self(n * Self::ESCALA)
+    }
+
+    pub fn get_unidades(&self) -> i64 {
+        self.0
+    }
+}
+
+impl<const DECIMALES: u32> fmt::Display for Fijo<DECIMALES> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signo = if self.0 < 0 { "-" } else { "" };
+        let unidades = self.0.unsigned_abs();
+        let escala = Self::ESCALA as u64;
+        if DECIMALES == 0 {
+            return write!(f, "{}{}", signo, unidades);
+        }
+        write!(
+            f,
+            "{}{}.{:0ancho$}",
+            signo,
+            unidades / escala,
+            unidades % escala,
+            ancho = DECIMALES as usize
+        )
+    }
+}
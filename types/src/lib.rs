@@ -1 +1,5 @@
+pub mod eventos;
+pub mod fijo;
+pub mod logs;
 pub mod rango;
+pub mod unidades;
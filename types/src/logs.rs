@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::eventos::{self, Evento, Severidad};
+
+/// Valor de un campo de un `Registro`.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Valor {
+    /// Un valor opcional que no está presente.
+    Nulo,
+    Booleano(bool),
+    Entero(u64),
+    Texto(String),
+    /// El registro de otro componente, por ejemplo un ciclo dentro de una ración.
+    Registro(Registro),
+    Lista(Vec<Valor>),
+}
+
+impl From<bool> for Valor {
+    fn from(valor: bool) -> Self {
+        Valor::Booleano(valor)
+    }
+}
+
+impl From<u32> for Valor {
+    fn from(valor: u32) -> Self {
+        Valor::Entero(u64::from(valor))
+    }
+}
+
+impl From<u64> for Valor {
+    fn from(valor: u64) -> Self {
+        Valor::Entero(valor)
+    }
+}
+
+impl From<usize> for Valor {
+    fn from(valor: usize) -> Self {
+        Valor::Entero(valor as u64)
+    }
+}
+
+impl From<&str> for Valor {
+    fn from(valor: &str) -> Self {
+        Valor::Texto(valor.to_string())
+    }
+}
+
+impl From<String> for Valor {
+    fn from(valor: String) -> Self {
+        Valor::Texto(valor)
+    }
+}
+
+impl From<Uuid> for Valor {
+    fn from(valor: Uuid) -> Self {
+        Valor::Texto(valor.to_string())
+    }
+}
+
+impl From<Registro> for Valor {
+    fn from(valor: Registro) -> Self {
+        Valor::Registro(valor)
+    }
+}
+
+impl<T: Into<Valor>> From<Option<T>> for Valor {
+    fn from(valor: Option<T>) -> Self {
+        valor.map_or(Valor::Nulo, Into::into)
+    }
+}
+
+impl<T: Into<Valor>> From<Vec<T>> for Valor {
+    fn from(valores: Vec<T>) -> Self {
+        Valor::Lista(valores.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Estado de un componente legible por máquina: su tipo, su `id` y sus campos por nombre.
+///
+/// Con la característica `serde` se serializa como
+/// `{"origen": "Silo", "id": "...", "campos": {"alimento_gramos": 1000, ...}}`.
+///
+/// # Ejemplo:
+///! ```
+///! let registro = Registro::new("Silo")
+///!     .id(silo.get_id())
+///!     .campo("alimento_gramos", silo.get_alimento_gramos());
+///! assert_eq!(registro.get("alimento_gramos"), Some(&Valor::Entero(1000)));
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Registro {
+    pub origen: String,
+    pub id: Option<Uuid>,
+    pub campos: BTreeMap<String, Valor>,
+}
+
+impl Registro {
+    pub fn new<O: Into<String>>(origen: O) -> Self {
+        Self {
+            origen: origen.into(),
+            id: None,
+            campos: BTreeMap::new(),
+        }
+    }
+
+    /// Indica el componente al que pertenece el registro.
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Agrega o reemplaza el campo `nombre`.
+    pub fn campo<N: Into<String>, V: Into<Valor>>(mut self, nombre: N, valor: V) -> Self {
+        self.campos.insert(nombre.into(), valor.into());
+        self
+    }
+
+    pub fn get(&self, nombre: &str) -> Option<&Valor> {
+        self.campos.get(nombre)
+    }
+}
+
+/// El trait `Print` permite que un componente se muestre de manera legible y se
+/// registre de manera legible por máquina.
+///
+/// # Métodos:
+/// - `texto`: La información relevante del componente en una línea, sin el `[origen][id]`.
+/// - `registro`: El estado del componente como `Registro`.
+/// - `print`: Emite `texto` como un `Evento` de `types::eventos` con el `origen` y el `id`
+///   del registro, y retorna la línea emitida.
+///
+/// # Ejemplo:
+///! ```
+///! silo.print(); // [Silo][id]: [Actual: 1kg][Historico: 0kg]
+///! let json = serde_json::to_string(&silo.registro())?;
+///! ```
+#[allow(clippy::suspicious_doc_comments)]
+pub trait Print {
+    fn texto(&self) -> String;
+
+    fn registro(&self) -> Registro;
+
+    fn print(&self) -> String {
+        let registro = self.registro();
+        let mut evento = Evento::new(Severidad::Info, registro.origen, self.texto());
+        if let Some(id) = registro.id {
+            evento = evento.id(id);
+        }
+        let linea = evento.to_string();
+        eventos::emitir(evento);
+        linea
+    }
+}
@@ -1,6 +1,112 @@
-pub struct RangoData {
-    pub min: u32,
-    pub max: u32,
+use std::fmt;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::eventos::{self, Evento, Severidad};
+use crate::fijo::Fijo;
+
+/// Tipos que puede guardar un `Rango`: enteros, `Fijo` y `Duration`.
+///
+/// # Métodos:
+/// - `texto`: El valor en los mensajes de los eventos, por ejemplo `"250"` o `"250ms"`.
+/// - `numero`: El valor en `Evento::anterior` y `Evento::nuevo`; un `Duration` en milisegundos.
+/// - `pasos_desde`: Cuántos `paso` enteros hay desde `origen` hasta el valor, redondeando
+///   hacia abajo. Lo usa `Rango::con_paso` para llevar el valor al paso más cercano.
+/// - `sumar_pasos`: El valor `origen + k * paso`, limitado a los valores del tipo.
+pub trait Acotable: PartialOrd + Copy + fmt::Debug {
+    fn texto(&self) -> String;
+    fn numero(&self) -> f64;
+    fn pasos_desde(&self, origen: Self, paso: Self) -> i128;
+    fn sumar_pasos(origen: Self, paso: Self, k: i128) -> Self;
+}
+
+/// `origen + k * paso` en `i128`, saturando en vez de desbordar.
+fn sumar_pasos_i128(origen: i128, paso: i128, k: i128) -> i128 {
+    origen.saturating_add(k.saturating_mul(paso))
+}
+
+macro_rules! acotable_entero {
+    ($($tipo:ty),*) => {
+        $(
+            impl Acotable for $tipo {
+                fn texto(&self) -> String {
+                    self.to_string()
+                }
+
+                fn numero(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn pasos_desde(&self, origen: Self, paso: Self) -> i128 {
+                    (*self as i128 - origen as i128).div_euclid(paso as i128)
+                }
+
+                fn sumar_pasos(origen: Self, paso: Self, k: i128) -> Self {
+                    let n = sumar_pasos_i128(origen as i128, paso as i128, k);
+                    let limite = if n < 0 { <$tipo>::MIN } else { <$tipo>::MAX };
+                    <$tipo>::try_from(n).unwrap_or(limite)
+                }
+            }
+        )*
+    };
+}
+
+acotable_entero!(u8, u16, u32, u64, usize, i32, i64);
+
+impl Acotable for Duration {
+    fn texto(&self) -> String {
+        format!("{}ms", self.as_millis())
+    }
+
+    fn numero(&self) -> f64 {
+        self.as_secs_f64() * 1000.0
+    }
+
+    fn pasos_desde(&self, origen: Self, paso: Self) -> i128 {
+        (self.as_nanos() as i128 - origen.as_nanos() as i128).div_euclid(paso.as_nanos() as i128)
+    }
+
+    fn sumar_pasos(origen: Self, paso: Self, k: i128) -> Self {
+        let n = sumar_pasos_i128(origen.as_nanos() as i128, paso.as_nanos() as i128, k);
+        if n <= 0 {
+            return Duration::ZERO;
+        }
+        match u64::try_from(n / 1_000_000_000) {
+            Ok(segundos) => Duration::new(segundos, (n % 1_000_000_000) as u32),
+            Err(_) => Duration::MAX,
+        }
+    }
+}
+
+impl<const DECIMALES: u32> Acotable for Fijo<DECIMALES> {
+    fn texto(&self) -> String {
+        self.to_string()
+    }
+
+    fn numero(&self) -> f64 {
+        self.get_unidades() as f64 / Self::ESCALA as f64
+    }
+
+    fn pasos_desde(&self, origen: Self, paso: Self) -> i128 {
+        self.get_unidades()
+            .pasos_desde(origen.get_unidades(), paso.get_unidades())
+    }
+
+    fn sumar_pasos(origen: Self, paso: Self, k: i128) -> Self {
+        Self::new(i64::sumar_pasos(
+            origen.get_unidades(),
+            paso.get_unidades(),
+            k,
+        ))
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangoData<T = u32> {
+    pub min: T,
+    pub max: T,
 }
 
 #[derive(Debug)]
@@ -8,27 +114,79 @@ pub enum RangoError {
     FueraDeRango,
 }
 
+/// Errores al configurar los límites de un `Rango` con `con_paso`, `excluir_min`,
+/// `excluir_max` o `restringir`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LimitesError {
+    /// El paso no es positivo, o ningún paso cae dentro de los límites.
+    PasoInvalido,
+    /// Los límites pedidos no están dentro de los límites nominales, o `min > max`.
+    LimitesInvalidos,
+    /// El valor actual queda fuera de los nuevos límites; el `Rango` no cambia.
+    ValorFuera,
+}
+
+/// Qué hace `Rango::set` con un valor fuera de los límites.
+///
+/// # Variantes:
+/// - `Rechazar`: Devuelve `RangoError::FueraDeRango` y el valor no cambia. Es la política
+///   por defecto.
+/// - `Acotar`: Lleva el valor al límite más cercano y emite un `Evento` de `Severidad::Aviso`.
+///   Un límite excluido sin paso no tiene un valor más cercano dentro del rango, así que
+///   en ese caso se rechaza igual que con `Rechazar`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Politica {
+    #[default]
+    Rechazar,
+    Acotar,
+}
+
 /// Estructura que representa un rango de valores, con un valor inicial
 /// que debe estar dentro de los límites definidos por el rango.
 ///
+/// Por defecto guarda un `u32`; cualquier tipo `Acotable` sirve, como `u64` para un
+/// contador, `Duration` para un tiempo o `Fijo` para un decimal.
+///
 /// # Campos:
 /// - `valor`: El valor actual dentro del rango.
 /// - `min`: El valor mínimo permitido.
 /// - `max`: El valor máximo permitido.
+/// - `restringido`: Límites más estrechos puestos en tiempo de ejecución con `restringir`.
+/// - `min_excluido` y `max_excluido`: Si el límite no es un valor válido.
+/// - `paso`: Si existe, el valor se lleva al `min + k * paso` más cercano.
+/// - `politica`: Qué hacer con un valor fuera de los límites.
 ///
 /// # Ejemplo:
 ///! ```rust
 ///! let rango = Rango::new(0, 100, 50).unwrap();
 ///! assert_eq!(rango.get(), 50);
+///!
+///! let espera = Rango::new(Duration::ZERO, Duration::from_secs(60), Duration::from_secs(5)).unwrap();
+///!
+///! let mut potencia = Rango::new(Porcentaje(0), Porcentaje(100), Porcentaje(0))
+///!     .unwrap()
+///!     .con_politica(Politica::Acotar)
+///!     .con_paso(Porcentaje(5))
+///!     .unwrap();
+///! potencia.set(Porcentaje(63), "[Soplador]").unwrap(); // 65%
+///! potencia.set(Porcentaje(120), "[Soplador]").unwrap(); // 100%, con un aviso
 ///! ```
-#[derive(PartialEq, Debug)]
-pub struct Rango {
-    valor: u32,
-    min: u32,
-    max: u32,
+#[allow(clippy::suspicious_doc_comments)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Rango<T = u32> {
+    valor: T,
+    min: T,
+    max: T,
+    restringido: Option<RangoData<T>>,
+    min_excluido: bool,
+    max_excluido: bool,
+    paso: Option<T>,
+    politica: Politica,
 }
 
-impl Rango {
+impl<T: Acotable> Rango<T> {
     /// Crea un nuevo `Rango` con un valor inicial dentro de los límites.
     ///
     /// # Parámetros:
@@ -45,7 +203,8 @@ impl Rango {
     ///! let rango = Rango::new(0, 100, 50).unwrap();  // Valor válido
     ///! let rango_invalido = Rango::new(0, 100, 150); // Error
     ///! ```
-    pub fn new(min: u32, max: u32, valor_inicial: u32) -> Result<Self, &'static str> {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn new(min: T, max: T, valor_inicial: T) -> Result<Self, &'static str> {
         if valor_inicial < min || valor_inicial > max {
             return Err("El valor inicial está fuera del rango permitido.");
         }
@@ -53,32 +212,170 @@ impl Rango {
             valor: valor_inicial,
             min,
             max,
+            restringido: None,
+            min_excluido: false,
+            max_excluido: false,
+            paso: None,
+            politica: Politica::Rechazar,
         })
     }
 
-    /// Establece un nuevo valor para el rango.
+    /// Cambia la política con que `set` trata los valores fuera de los límites.
+    pub fn con_politica(mut self, politica: Politica) -> Self {
+        self.politica = politica;
+        self
+    }
+
+    /// Hace que los valores se lleven al `min + k * paso` más cercano, por ejemplo la
+    /// potencia en pasos de 5% o una duración en pasos de 100 ms. El valor actual
+    /// también se lleva a su paso.
     ///
-    /// # Parámetros:
-    /// - `valor`: El nuevo valor que se desea establecer.
+    /// # Retorna:
+    /// `LimitesError::PasoInvalido` si el paso no es positivo o ningún paso cae dentro de
+    /// los límites.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let rango = Rango::new(0, 100, 52).unwrap().con_paso(5).unwrap();
+    ///! assert_eq!(rango.get(), 50);
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn con_paso(mut self, paso: T) -> Result<Self, LimitesError> {
+        if paso.numero() <= 0.0 {
+            return Err(LimitesError::PasoInvalido);
+        }
+        self.paso = Some(paso);
+        match self.ajustar(self.valor) {
+            Some((valor, _)) => {
+                self.valor = valor;
+                Ok(self)
+            }
+            None => Err(LimitesError::PasoInvalido),
+        }
+    }
+
+    /// Excluye `min` de los valores válidos, por ejemplo para un caudal que debe ser
+    /// mayor que cero. Falla si el valor actual es `min`.
+    pub fn excluir_min(mut self) -> Result<Self, LimitesError> {
+        self.min_excluido = true;
+        self.revalidar()
+    }
+
+    /// Excluye `max` de los valores válidos. Falla si el valor actual es `max`.
+    pub fn excluir_max(mut self) -> Result<Self, LimitesError> {
+        self.max_excluido = true;
+        self.revalidar()
+    }
+
+    fn revalidar(self) -> Result<Self, LimitesError> {
+        if self.contiene(self.valor) {
+            Ok(self)
+        } else {
+            Err(LimitesError::ValorFuera)
+        }
+    }
+
+    /// Estrecha los límites en tiempo de ejecución, por ejemplo la capacidad de un silo
+    /// reducida por mantención. Los nuevos límites deben estar dentro de los nominales,
+    /// con los que se creó el `Rango`, y se quitan con `restablecer`.
     ///
     /// # Retorna:
-    /// `Result<(), &'static str>`: Un `Ok(())` si el valor está dentro del rango,
-    /// o un `Err` si el valor está fuera de los límites.
+    /// - `LimitesError::LimitesInvalidos` si `min > max` o se salen de los límites nominales.
+    /// - `LimitesError::ValorFuera` si el valor actual queda fuera; los límites no cambian.
     ///
     /// # Ejemplo:
     ///! ```rust
     ///! let mut rango = Rango::new(0, 100, 50).unwrap();
-    ///! rango.set(80).unwrap();  // Establece un nuevo valor válido
-    ///! rango.set(150);  // Error: El valor está fuera del rango permitido
+    ///! rango.restringir(0, 80).unwrap();
+    ///! assert!(rango.set(90, "[Prueba]").is_err());
+    ///! rango.restablecer();
+    ///! rango.set(90, "[Prueba]").unwrap();
     ///! ```
-    fn set_rango_value(&mut self, valor: u32) -> Result<(), &'static str> {
-        if valor < self.min || valor > self.max {
-            return Err("El valor está fuera del rango permitido.");
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn restringir(&mut self, min: T, max: T) -> Result<(), LimitesError> {
+        if min > max || min < self.min || max > self.max {
+            return Err(LimitesError::LimitesInvalidos);
+        }
+        let anterior = self.restringido.replace(RangoData { min, max });
+        if !self.contiene(self.valor) {
+            self.restringido = anterior;
+            return Err(LimitesError::ValorFuera);
         }
-        self.valor = valor;
         Ok(())
     }
 
+    /// Vuelve a los límites nominales después de `restringir`.
+    pub fn restablecer(&mut self) {
+        self.restringido = None;
+    }
+
+    /// Indica si `valor` está dentro de los límites vigentes, respetando los excluidos.
+    pub fn contiene(&self, valor: T) -> bool {
+        let limites = self.get_rango();
+        let sobre_min = if self.min_excluido {
+            valor > limites.min
+        } else {
+            valor >= limites.min
+        };
+        let bajo_max = if self.max_excluido {
+            valor < limites.max
+        } else {
+            valor <= limites.max
+        };
+        sobre_min && bajo_max
+    }
+
+    /// Calcula el valor que `set` guardaría en lugar de `valor` según la política y el
+    /// paso, e indica si se tuvo que acotar a los límites. `None` si se rechaza.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! let rango = Rango::new(0, 100, 50).unwrap().con_politica(Politica::Acotar);
+    ///! assert_eq!(rango.ajustar(150), Some((100, true)));
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn ajustar(&self, valor: T) -> Option<(T, bool)> {
+        let mut nuevo = valor;
+        let mut acotado = false;
+
+        if !self.contiene(nuevo) {
+            if self.politica == Politica::Rechazar {
+                return None;
+            }
+            let limites = self.get_rango();
+            nuevo = if nuevo <= limites.min {
+                limites.min
+            } else {
+                limites.max
+            };
+            acotado = true;
+        }
+
+        match self.paso {
+            // Los pasos se cuentan desde el mínimo nominal, para que restringir no los mueva
+            Some(paso) => {
+                let k = nuevo.pasos_desde(self.min, paso);
+                // El de arriba primero, para que un valor a medio paso suba
+                [k + 1, k, k - 1, k + 2]
+                    .into_iter()
+                    .map(|k| T::sumar_pasos(self.min, paso, k))
+                    .filter(|candidato| self.contiene(*candidato))
+                    .fold(None, |mejor: Option<T>, candidato| match mejor {
+                        Some(mejor)
+                            if (mejor.numero() - nuevo.numero()).abs()
+                                <= (candidato.numero() - nuevo.numero()).abs() =>
+                        {
+                            Some(mejor)
+                        }
+                        _ => Some(candidato),
+                    })
+                    .map(|candidato| (candidato, acotado))
+            }
+            None if self.contiene(nuevo) => Some((nuevo, acotado)),
+            None => None,
+        }
+    }
+
     /// Obtiene el valor actual dentro del rango.
     ///
     /// # Retorna:
@@ -89,35 +386,226 @@ impl Rango {
     ///! let rango = Rango::new(0, 100, 50).unwrap();
     ///! assert_eq!(rango.get(), 50);
     ///! ```
-    pub fn get(&self) -> u32 {
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn get(&self) -> T {
         self.valor
     }
 
-    pub fn get_rango(&self) -> RangoData {
+    /// Obtiene los límites vigentes: los de `restringir` si existen, o los nominales.
+    pub fn get_rango(&self) -> RangoData<T> {
+        self.restringido.unwrap_or(self.get_nominal())
+    }
+
+    /// Obtiene los límites con que se creó el `Rango`, sin contar `restringir`.
+    pub fn get_nominal(&self) -> RangoData<T> {
         RangoData {
             min: self.min,
             max: self.max,
         }
     }
 
-    pub fn set(&mut self, valor: u32, tag: &str) -> Result<(), RangoError> {
+    pub fn get_politica(&self) -> Politica {
+        self.politica
+    }
+
+    pub fn get_paso(&self) -> Option<T> {
+        self.paso
+    }
+
+    /// Establece un nuevo valor y emite un `Evento` con el cambio, o un aviso si está
+    /// fuera de los límites. `tag` identifica al dueño del rango, por ejemplo `"[Ciclo]"`.
+    ///
+    /// Con un paso se guarda el paso más cercano, y con `Politica::Acotar` un valor fuera
+    /// de los límites se guarda acotado con un aviso en vez de rechazarse.
+    ///
+    /// Para indicar también el `id` del dueño y el nombre del campo se usa `set_campo`.
+    pub fn set(&mut self, valor: T, tag: &str) -> Result<(), RangoError> {
+        let origen = tag.trim_start_matches('[').trim_end_matches(']');
+        self.cambiar(
+            valor,
+            Evento::new(Severidad::Depuracion, origen, ""),
+            "valor",
+        )
+    }
+
+    /// Igual que `set`, indicando en el evento el componente `id` de tipo `origen` y el `campo`.
+    ///
+    /// # Ejemplo:
+    ///! ```rust
+    ///! self.pulsos.set_campo(n, "Ciclo", self.id, "pulsos")?;
+    ///! ```
+    #[allow(clippy::suspicious_doc_comments)]
+    pub fn set_campo(
+        &mut self,
+        valor: T,
+        origen: &str,
+        id: Uuid,
+        campo: &str,
+    ) -> Result<(), RangoError> {
+        self.cambiar(
+            valor,
+            Evento::new(Severidad::Depuracion, origen, "").id(id),
+            campo,
+        )
+    }
+
+    fn cambiar(&mut self, valor: T, evento: Evento, campo: &str) -> Result<(), RangoError> {
         let valor_actual = self.get();
         let rango = self.get_rango();
 
-        print!("{}", tag);
-        match self.set_rango_value(valor) {
-            Ok(()) => {
-                println!("El valor ha cambiado de {} a {}", valor_actual, valor);
+        match self.ajustar(valor) {
+            Some((nuevo, acotado)) => {
+                self.valor = nuevo;
+                let mut evento = evento.cambio(campo, valor_actual.numero(), nuevo.numero());
+                evento.mensaje = if acotado {
+                    evento.severidad = Severidad::Aviso;
+                    format!(
+                        "Aviso: {} fuera del rango ({};{}), se acotó a {}",
+                        valor.texto(),
+                        rango.min.texto(),
+                        rango.max.texto(),
+                        nuevo.texto()
+                    )
+                } else if nuevo != valor {
+                    format!(
+                        "El valor ha cambiado de {} a {} (pedido {})",
+                        valor_actual.texto(),
+                        nuevo.texto(),
+                        valor.texto()
+                    )
+                } else {
+                    format!(
+                        "El valor ha cambiado de {} a {}",
+                        valor_actual.texto(),
+                        nuevo.texto()
+                    )
+                };
+                eventos::emitir(evento);
                 Ok(())
             }
-            Err(_) => {
-                println!(
-                    "Error: Fuera del rango ({};{}), el valor no ha cambiado. Valor actual: {}",
-                    rango.min, rango.max, valor_actual
+            None => {
+                let mut evento = evento;
+                evento.severidad = Severidad::Aviso;
+                evento.campo = Some(campo.to_string());
+                evento.anterior = Some(valor_actual.numero());
+                evento.mensaje = format!(
+                    "Error: {} fuera del rango ({};{}), el valor no ha cambiado. Valor actual: {}",
+                    valor.texto(),
+                    rango.min.texto(),
+                    rango.max.texto(),
+                    valor_actual.texto()
                 );
+                eventos::emitir(evento);
 
                 Err(RangoError::FueraDeRango)
             }
         }
     }
 }
+
+/// Un `Rango` se serializa como `{ valor, min, max }`, más `restringido`, `min_excluido`,
+/// `max_excluido`, `paso` y `politica` cuando no tienen su valor por defecto. Al
+/// deserializarlo se vuelve a comprobar que `valor` esté dentro de los límites, de modo
+/// que un archivo no puede cargar un valor que `Rango::set` rechazaría.
+///
+/// Los tipos que guardan un `Rango` con límites fijos (como `Ciclo` o `Silo`) no lo
+/// serializan así: guardan solo el valor y lo cargan con su setter, para que el archivo
+/// tampoco pueda cambiar los límites.
+#[cfg(feature = "serde")]
+mod serializacion {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Acotable, Politica, Rango, RangoData};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Rango", deny_unknown_fields)]
+    #[serde(bound(deserialize = "T: Deserialize<'de>"))]
+    struct Datos<T> {
+        valor: T,
+        min: T,
+        max: T,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        restringido: Option<RangoData<T>>,
+        #[serde(default, skip_serializing_if = "es_falso")]
+        min_excluido: bool,
+        #[serde(default, skip_serializing_if = "es_falso")]
+        max_excluido: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        paso: Option<T>,
+        #[serde(default, skip_serializing_if = "es_rechazar")]
+        politica: Politica,
+    }
+
+    fn es_falso(b: &bool) -> bool {
+        !*b
+    }
+
+    fn es_rechazar(politica: &Politica) -> bool {
+        *politica == Politica::Rechazar
+    }
+
+    impl<T: Acotable + Serialize> Serialize for Rango<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Datos {
+                valor: self.valor,
+                min: self.min,
+                max: self.max,
+                restringido: self.restringido,
+                min_excluido: self.min_excluido,
+                max_excluido: self.max_excluido,
+                paso: self.paso,
+                politica: self.politica,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Acotable + Deserialize<'de>> Deserialize<'de> for Rango<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let datos = Datos::<T>::deserialize(deserializer)?;
+            let fuera_de_rango = || {
+                D::Error::custom(format!(
+                    "valor {} fuera de rango ({}..={})",
+                    datos.valor.texto(),
+                    datos.min.texto(),
+                    datos.max.texto()
+                ))
+            };
+
+            let mut rango = Rango::new(datos.min, datos.max, datos.valor)
+                .map_err(|_| fuera_de_rango())?
+                .con_politica(datos.politica);
+            if datos.min_excluido {
+                rango = rango.excluir_min().map_err(|_| fuera_de_rango())?;
+            }
+            if datos.max_excluido {
+                rango = rango.excluir_max().map_err(|_| fuera_de_rango())?;
+            }
+            if let Some(paso) = datos.paso {
+                rango = rango
+                    .con_paso(paso)
+                    .map_err(|_| D::Error::custom(format!("paso {} inválido", paso.texto())))?;
+                // `con_paso` lleva el valor al paso más cercano; uno guardado fuera del paso se rechaza
+                if rango.get() != datos.valor {
+                    return Err(D::Error::custom(format!(
+                        "valor {} fuera del paso {}",
+                        datos.valor.texto(),
+                        paso.texto()
+                    )));
+                }
+            }
+            if let Some(limites) = datos.restringido {
+                rango.restringir(limites.min, limites.max).map_err(|_| {
+                    D::Error::custom(format!(
+                        "valor {} fuera de los límites restringidos ({}..={})",
+                        datos.valor.texto(),
+                        limites.min.texto(),
+                        limites.max.texto()
+                    ))
+                })?;
+            }
+            Ok(rango)
+        }
+    }
+}
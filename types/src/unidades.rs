@@ -0,0 +1,269 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+use std::time::Duration;
+
+use crate::logs::Valor;
+use crate::rango::Acotable;
+
+/// Define una unidad como un `u32`, o el entero indicado, con su sufijo al mostrarla.
+///
+/// Se serializa, y se registra en un `Registro`, como el número sin el sufijo, para que
+/// los archivos de configuración no cambien.
+macro_rules! unidad {
+    ($(#[$doc:meta])* $nombre:ident, $sufijo:literal) => {
+        unidad!($(#[$doc])* $nombre(u32), $sufijo);
+    };
+    ($(#[$doc:meta])* $nombre:ident($tipo:ty), $sufijo:literal) => {
+        $(#[$doc])*
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Default, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
+        pub struct $nombre(pub $tipo);
+
+        impl $nombre {
+            pub fn saturating_add(self, otro: Self) -> Self {
+                Self(self.0.saturating_add(otro.0))
+            }
+
+            pub fn saturating_sub(self, otro: Self) -> Self {
+                Self(self.0.saturating_sub(otro.0))
+            }
+        }
+
+        impl fmt::Display for $nombre {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}{}", self.0, $sufijo)
+            }
+        }
+
+        impl From<$nombre> for Valor {
+            fn from(n: $nombre) -> Self {
+                Valor::Entero(u64::from(n.0))
+            }
+        }
+
+        impl Acotable for $nombre {
+            fn texto(&self) -> String {
+                self.to_string()
+            }
+
+            fn numero(&self) -> f64 {
+                self.0 as f64
+            }
+
+            fn pasos_desde(&self, origen: Self, paso: Self) -> i128 {
+                self.0.pasos_desde(origen.0, paso.0)
+            }
+
+            fn sumar_pasos(origen: Self, paso: Self, k: i128) -> Self {
+                Self(<$tipo>::sumar_pasos(origen.0, paso.0, k))
+            }
+        }
+    };
+}
+
+/// Define la suma y la resta de una unidad consigo misma.
+///
+/// Desbordan igual que el entero que guardan: en pánico en compilaciones de depuración
+/// y dando la vuelta en las de producción. Donde el resultado puede salirse del tipo
+/// se usan `saturating_add` y `saturating_sub`.
+macro_rules! aditiva {
+    ($nombre:ident) => {
+        impl Add for $nombre {
+            type Output = Self;
+
+            fn add(self, otro: Self) -> Self {
+                Self(self.0 + otro.0)
+            }
+        }
+
+        impl Sub for $nombre {
+            type Output = Self;
+
+            fn sub(self, otro: Self) -> Self {
+                Self(self.0 - otro.0)
+            }
+        }
+
+        impl AddAssign for $nombre {
+            fn add_assign(&mut self, otro: Self) {
+                self.0 += otro.0;
+            }
+        }
+
+        impl SubAssign for $nombre {
+            fn sub_assign(&mut self, otro: Self) {
+                self.0 -= otro.0;
+            }
+        }
+
+        impl Sum for $nombre {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|n| n.0).sum())
+            }
+        }
+    };
+}
+
+unidad!(
+    /// Un lapso en milisegundos, como la duración de un pulso o una espera.
+    ///
+    /// Los instantes y los lapsos que pueden superar `u32::MAX` se llevan en `Tiempo`.
+    Milisegundos,
+    "ms"
+);
+aditiva!(Milisegundos);
+
+unidad!(
+    /// Milisegundos en un `u64`: un instante según un reloj (desde la época Unix con el
+    /// reloj del sistema) o un lapso acumulado, como los de un `Reporte`.
+    Tiempo(u64),
+    "ms"
+);
+aditiva!(Tiempo);
+
+unidad!(
+    /// Una masa de alimento en gramos. Es la unidad con que se lleva la cuenta interna.
+    Gramos,
+    "g"
+);
+aditiva!(Gramos);
+
+unidad!(
+    /// Una masa de alimento en kilogramos, como la capacidad de un silo.
+    Kilogramos,
+    "kg"
+);
+aditiva!(Kilogramos);
+
+unidad!(
+    /// Caudal de un dosificador en gramos por segundo.
+    GramosPorSegundo,
+    "g/s"
+);
+
+unidad!(
+    /// Caudal de un dosificador en kilogramos por segundo.
+    KgPorSegundo,
+    "kg/s"
+);
+
+unidad!(
+    /// Potencia en porcentaje, de 0 a 100.
+    Porcentaje,
+    "%"
+);
+
+unidad!(
+    /// Una longitud en metros, como el largo de una línea.
+    Metros,
+    "m"
+);
+
+unidad!(
+    /// Una velocidad en metros por segundo, como la del pellet en la línea.
+    MetrosPorSegundo,
+    "m/s"
+);
+
+impl Milisegundos {
+    pub const fn segundos(n: u32) -> Self {
+        Self(n * 1000)
+    }
+
+    pub const fn minutos(n: u32) -> Self {
+        Self(n * 60 * 1000)
+    }
+
+    /// Convierte un `Duration`, truncando lo menor a un milisegundo y limitando a `u32::MAX`.
+    pub fn desde_duration(duracion: Duration) -> Self {
+        Self(u32::try_from(duracion.as_millis()).unwrap_or(u32::MAX))
+    }
+}
+
+impl From<Milisegundos> for Tiempo {
+    fn from(ms: Milisegundos) -> Self {
+        Tiempo(u64::from(ms.0))
+    }
+}
+
+impl From<Milisegundos> for Duration {
+    fn from(ms: Milisegundos) -> Self {
+        Duration::from_millis(u64::from(ms.0))
+    }
+}
+
+/// Error de una conversión entre unidades cuyo resultado no cabe en la unidad de destino.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum UnidadError {
+    /// El valor convertido supera `u32::MAX`.
+    Desborde,
+}
+
+impl TryFrom<Kilogramos> for Gramos {
+    type Error = UnidadError;
+
+    /// Sin pérdida; falla con `UnidadError::Desborde` sobre `u32::MAX` gramos.
+    fn try_from(kg: Kilogramos) -> Result<Self, UnidadError> {
+        kg.0.checked_mul(1000)
+            .map(Gramos)
+            .ok_or(UnidadError::Desborde)
+    }
+}
+
+impl Gramos {
+    /// Los kilogramos enteros, truncando los gramos restantes.
+    pub fn a_kilogramos(self) -> Kilogramos {
+        Kilogramos(self.0 / 1000)
+    }
+}
+
+impl TryFrom<KgPorSegundo> for GramosPorSegundo {
+    type Error = UnidadError;
+
+    /// Sin pérdida; falla con `UnidadError::Desborde` sobre `u32::MAX` g/s.
+    fn try_from(kg: KgPorSegundo) -> Result<Self, UnidadError> {
+        kg.0.checked_mul(1000)
+            .map(GramosPorSegundo)
+            .ok_or(UnidadError::Desborde)
+    }
+}
+
+impl GramosPorSegundo {
+    /// Los kg/s enteros, truncando el resto.
+    pub fn a_kg_por_segundo(self) -> KgPorSegundo {
+        KgPorSegundo(self.0 / 1000)
+    }
+}
+
+impl Mul<Milisegundos> for GramosPorSegundo {
+    type Output = Gramos;
+
+    /// Los gramos entregados en `ms` a este caudal, truncados al gramo y limitados a `u32::MAX`.
+    fn mul(self, ms: Milisegundos) -> Gramos {
+        let gramos = u64::from(self.0) * u64::from(ms.0) / 1000;
+        Gramos(u32::try_from(gramos).unwrap_or(u32::MAX))
+    }
+}
+
+impl Mul<Milisegundos> for KgPorSegundo {
+    type Output = Gramos;
+
+    /// Los gramos entregados en `ms` a este caudal, limitados a `u32::MAX`.
+    fn mul(self, ms: Milisegundos) -> Gramos {
+        let gramos = u64::from(self.0) * u64::from(ms.0);
+        Gramos(u32::try_from(gramos).unwrap_or(u32::MAX))
+    }
+}
+
+impl Div<MetrosPorSegundo> for Metros {
+    type Output = Milisegundos;
+
+    /// El tiempo en recorrer este largo a `velocidad`, truncado al milisegundo y limitado
+    /// a `u32::MAX`. Como la división entera, entra en pánico con una velocidad de 0.
+    fn div(self, velocidad: MetrosPorSegundo) -> Milisegundos {
+        let ms = u64::from(self.0) * 1000 / u64::from(velocidad.0);
+        Milisegundos(u32::try_from(ms).unwrap_or(u32::MAX))
+    }
+}
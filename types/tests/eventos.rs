@@ -0,0 +1,143 @@
+use std::sync::{Arc, OnceLock};
+
+use types::eventos::{self, Evento, Memoria, Severidad};
+use types::rango::{Politica, Rango, RangoError};
+use uuid::Uuid;
+
+/// El sumidero es global: todas las pruebas comparten la misma `Memoria` y filtran sus eventos.
+fn memoria() -> Arc<Memoria> {
+    static MEMORIA: OnceLock<Arc<Memoria>> = OnceLock::new();
+    MEMORIA
+        .get_or_init(|| {
+            let memoria = Arc::new(Memoria::new());
+            eventos::set_sumidero(memoria.clone());
+            memoria
+        })
+        .clone()
+}
+
+fn eventos_de(origen: &str) -> Vec<Evento> {
+    memoria()
+        .get_eventos()
+        .into_iter()
+        .filter(|evento| evento.origen == origen)
+        .collect()
+}
+
+#[cfg(test)]
+mod eventos_test {
+    use super::*;
+
+    mod rango {
+        use super::*;
+
+        #[test]
+        fn test_cambio() {
+            memoria();
+            let mut rango = Rango::new(0, 100, 10).unwrap();
+            rango.set(50, "[PruebaCambio]").unwrap();
+
+            let eventos = eventos_de("PruebaCambio");
+            assert_eq!(eventos.len(), 1);
+            assert_eq!(eventos[0].severidad, Severidad::Depuracion);
+            assert_eq!(eventos[0].anterior, Some(10.0));
+            assert_eq!(eventos[0].nuevo, Some(50.0));
+            assert_eq!(eventos[0].id, None);
+        }
+
+        #[test]
+        fn test_fuera_de_rango() {
+            memoria();
+            let mut rango = Rango::new(0, 100, 10).unwrap();
+            assert!(matches!(
+                rango.set(150, "[PruebaFuera]"),
+                Err(RangoError::FueraDeRango)
+            ));
+
+            let eventos = eventos_de("PruebaFuera");
+            assert_eq!(eventos.len(), 1);
+            assert_eq!(eventos[0].severidad, Severidad::Aviso);
+            assert_eq!(eventos[0].anterior, Some(10.0));
+            assert_eq!(eventos[0].nuevo, None);
+            assert!(eventos[0].mensaje.contains("(0;100)"));
+        }
+
+        #[test]
+        fn test_set_campo() {
+            let id = Uuid::new_v4();
+            let mut rango = Rango::new(0, 100, 0).unwrap();
+            rango.set_campo(30, "PruebaCampo", id, "potencia").unwrap();
+
+            let eventos = memoria().get_eventos_de(id);
+            assert_eq!(eventos.len(), 1);
+            assert_eq!(eventos[0].origen, "PruebaCampo");
+            assert_eq!(eventos[0].campo.as_deref(), Some("potencia"));
+            assert_eq!(
+                eventos[0].to_string(),
+                format!(
+                    "[PruebaCampo][{}][potencia]: El valor ha cambiado de 0 a 30",
+                    id
+                )
+            );
+        }
+
+        #[test]
+        fn test_acotado() {
+            memoria();
+            let mut rango = Rango::new(0, 100, 10)
+                .unwrap()
+                .con_politica(Politica::Acotar);
+            rango.set(150, "[PruebaAcotado]").unwrap();
+
+            let eventos = eventos_de("PruebaAcotado");
+            assert_eq!(eventos.len(), 1);
+            assert_eq!(eventos[0].severidad, Severidad::Aviso);
+            assert_eq!(eventos[0].anterior, Some(10.0));
+            assert_eq!(eventos[0].nuevo, Some(100.0));
+            assert!(eventos[0]
+                .mensaje
+                .contains("150 fuera del rango (0;100), se acotó a 100"));
+        }
+
+        #[test]
+        fn test_ajustado_al_paso() {
+            memoria();
+            let mut rango = Rango::new(0, 100, 0).unwrap().con_paso(5).unwrap();
+            rango.set(63, "[PruebaPaso]").unwrap();
+
+            let eventos = eventos_de("PruebaPaso");
+            assert_eq!(eventos.len(), 1);
+            assert_eq!(eventos[0].severidad, Severidad::Depuracion);
+            assert_eq!(
+                eventos[0].mensaje,
+                "El valor ha cambiado de 0 a 65 (pedido 63)"
+            );
+        }
+    }
+
+    mod emitir {
+        use super::*;
+
+        #[test]
+        fn test_instante_del_reloj() {
+            let id = Uuid::new_v4();
+            eventos::set_reloj(Arc::new(|| 1_000));
+            memoria();
+            eventos::emitir(Evento::new(Severidad::Info, "PruebaReloj", "hola").id(id));
+
+            let eventos = memoria().get_eventos_de(id);
+            assert_eq!(eventos[0].instante, 1_000);
+            assert_eq!(
+                eventos[0].to_string(),
+                format!("[PruebaReloj][{}]: hola", id)
+            );
+        }
+
+        #[test]
+        fn test_severidad_ordenada() {
+            assert!(Severidad::Depuracion < Severidad::Info);
+            assert!(Severidad::Info < Severidad::Aviso);
+            assert!(Severidad::Aviso < Severidad::Error);
+        }
+    }
+}
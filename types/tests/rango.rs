@@ -0,0 +1,325 @@
+use std::time::Duration;
+
+use types::fijo::Fijo;
+use types::rango::{LimitesError, Politica, Rango, RangoError};
+use types::unidades::{Milisegundos, Porcentaje};
+
+#[cfg(test)]
+mod rango {
+    use super::*;
+
+    mod enteros {
+        use super::*;
+
+        #[test]
+        fn test_u32_por_defecto() {
+            let mut rango: Rango = Rango::new(0, 100, 50).unwrap();
+            assert_eq!(rango.get(), 50u32);
+            assert!(matches!(
+                rango.set(101, "[Prueba]"),
+                Err(RangoError::FueraDeRango)
+            ));
+            assert_eq!(rango.get(), 50);
+        }
+
+        #[test]
+        fn test_u64_supera_u32() {
+            let mut rango = Rango::new(0, u64::MAX, 0).unwrap();
+            let grande = u64::from(u32::MAX) + 1;
+            rango.set(grande, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), grande);
+        }
+
+        #[test]
+        fn test_con_signo() {
+            let mut rango = Rango::new(-40i32, 60, 20).unwrap();
+            rango.set(-10, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), -10);
+            assert!(rango.set(-41, "[Prueba]").is_err());
+        }
+
+        #[test]
+        fn test_valor_inicial_fuera_de_rango() {
+            assert!(Rango::new(10u8, 20, 21).is_err());
+        }
+    }
+
+    mod duracion {
+        use super::*;
+
+        fn segundos(n: u64) -> Duration {
+            Duration::from_secs(n)
+        }
+
+        #[test]
+        fn test_set_y_get() {
+            let mut rango = Rango::new(segundos(1), segundos(60), segundos(5)).unwrap();
+            rango.set(Duration::from_millis(1500), "[Prueba]").unwrap();
+            assert_eq!(rango.get(), Duration::from_millis(1500));
+        }
+
+        #[test]
+        fn test_fuera_de_rango() {
+            let mut rango = Rango::new(segundos(1), segundos(60), segundos(5)).unwrap();
+            assert!(matches!(
+                rango.set(Duration::from_millis(999), "[Prueba]"),
+                Err(RangoError::FueraDeRango)
+            ));
+            assert_eq!(rango.get(), segundos(5));
+        }
+
+        #[test]
+        fn test_get_rango() {
+            let rango = Rango::new(segundos(1), segundos(60), segundos(5)).unwrap();
+            let limites = rango.get_rango();
+            assert_eq!(limites.min, segundos(1));
+            assert_eq!(limites.max, segundos(60));
+        }
+    }
+
+    mod fijo {
+        use super::*;
+
+        #[test]
+        fn test_texto() {
+            assert_eq!(Fijo::<3>::new(1250).to_string(), "1.250");
+            assert_eq!(Fijo::<3>::new(-5).to_string(), "-0.005");
+            assert_eq!(Fijo::<2>::entero(7).to_string(), "7.00");
+            assert_eq!(Fijo::<0>::new(42).to_string(), "42");
+        }
+
+        #[test]
+        fn test_en_rango() {
+            let mut rango =
+                Rango::new(Fijo::<1>::entero(0), Fijo::entero(100), Fijo::new(5)).unwrap();
+            rango.set(Fijo::new(999), "[Prueba]").unwrap();
+            assert_eq!(rango.get().get_unidades(), 999);
+            assert!(rango.set(Fijo::new(1001), "[Prueba]").is_err());
+        }
+    }
+
+    mod politica {
+        use super::*;
+
+        #[test]
+        fn test_rechazar_por_defecto() {
+            let rango = Rango::new(0, 100, 50).unwrap();
+            assert_eq!(rango.get_politica(), Politica::Rechazar);
+            assert_eq!(rango.ajustar(150), None);
+        }
+
+        #[test]
+        fn test_acotar() {
+            let mut rango = Rango::new(10, 100, 50)
+                .unwrap()
+                .con_politica(Politica::Acotar);
+
+            rango.set(150, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), 100);
+            rango.set(0, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), 10);
+            assert_eq!(rango.ajustar(40), Some((40, false)));
+        }
+
+        #[test]
+        fn test_acotar_limite_excluido_sin_paso() {
+            let mut rango = Rango::new(0, 100, 50)
+                .unwrap()
+                .con_politica(Politica::Acotar)
+                .excluir_max()
+                .unwrap();
+            assert!(matches!(
+                rango.set(150, "[Prueba]"),
+                Err(RangoError::FueraDeRango)
+            ));
+            assert_eq!(rango.get(), 50);
+        }
+    }
+
+    mod paso {
+        use super::*;
+
+        #[test]
+        fn test_al_paso_mas_cercano() {
+            let mut rango = Rango::new(Porcentaje(0), Porcentaje(100), Porcentaje(0))
+                .unwrap()
+                .con_paso(Porcentaje(5))
+                .unwrap();
+
+            rango.set(Porcentaje(63), "[Prueba]").unwrap();
+            assert_eq!(rango.get(), Porcentaje(65));
+            rango.set(Porcentaje(62), "[Prueba]").unwrap();
+            assert_eq!(rango.get(), Porcentaje(60));
+            // A medio paso se sube
+            let decenas = Rango::new(0, 100, 0).unwrap().con_paso(10).unwrap();
+            assert_eq!(decenas.ajustar(45), Some((50, false)));
+        }
+
+        #[test]
+        fn test_cuenta_desde_el_minimo() {
+            let rango = Rango::new(Milisegundos(1050), Milisegundos(2000), Milisegundos(1050))
+                .unwrap()
+                .con_paso(Milisegundos(100))
+                .unwrap();
+            assert_eq!(
+                rango.ajustar(Milisegundos(1399)),
+                Some((Milisegundos(1350), false))
+            );
+            // 2050 se pasa del máximo, así que queda en el paso anterior
+            assert_eq!(
+                rango.ajustar(Milisegundos(2000)),
+                Some((Milisegundos(1950), false))
+            );
+        }
+
+        #[test]
+        fn test_duracion() {
+            let rango = Rango::new(Duration::ZERO, Duration::from_secs(10), Duration::ZERO)
+                .unwrap()
+                .con_paso(Duration::from_millis(100))
+                .unwrap();
+            assert_eq!(
+                rango.ajustar(Duration::from_micros(1_234_567)),
+                Some((Duration::from_millis(1200), false))
+            );
+        }
+
+        #[test]
+        fn test_fijo() {
+            let rango = Rango::new(Fijo::<2>::entero(0), Fijo::entero(10), Fijo::entero(0))
+                .unwrap()
+                .con_paso(Fijo::new(25))
+                .unwrap();
+            assert_eq!(rango.ajustar(Fijo::new(138)), Some((Fijo::new(150), false)));
+        }
+
+        #[test]
+        fn test_ajusta_el_valor_actual() {
+            let rango = Rango::new(0, 100, 52).unwrap().con_paso(5).unwrap();
+            assert_eq!(rango.get(), 50);
+            assert_eq!(rango.get_paso(), Some(5));
+        }
+
+        #[test]
+        fn test_paso_invalido() {
+            assert_eq!(
+                Rango::new(0, 100, 50).unwrap().con_paso(0).err(),
+                Some(LimitesError::PasoInvalido)
+            );
+            // Con el 0 excluido, ningún múltiplo de 5 cae dentro de 0..=4
+            let rango = Rango::new(0, 4, 2).unwrap().excluir_min().unwrap();
+            assert_eq!(rango.con_paso(5).err(), Some(LimitesError::PasoInvalido));
+        }
+
+        #[test]
+        fn test_acotar_con_paso() {
+            let mut rango = Rango::new(0, 100, 50)
+                .unwrap()
+                .con_politica(Politica::Acotar)
+                .con_paso(30)
+                .unwrap();
+            rango.set(150, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), 90);
+        }
+    }
+
+    mod excluidos {
+        use super::*;
+
+        #[test]
+        fn test_limites_excluidos() {
+            let mut rango = Rango::new(0, 100, 50)
+                .unwrap()
+                .excluir_min()
+                .unwrap()
+                .excluir_max()
+                .unwrap();
+            assert!(rango.set(0, "[Prueba]").is_err());
+            assert!(rango.set(100, "[Prueba]").is_err());
+            rango.set(1, "[Prueba]").unwrap();
+            rango.set(99, "[Prueba]").unwrap();
+            assert!(rango.contiene(99));
+            assert!(!rango.contiene(100));
+        }
+
+        #[test]
+        fn test_valor_actual_en_el_limite() {
+            assert_eq!(
+                Rango::new(0, 100, 0).unwrap().excluir_min().err(),
+                Some(LimitesError::ValorFuera)
+            );
+        }
+
+        #[test]
+        fn test_acotar_con_paso() {
+            let mut rango = Rango::new(
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_millis(500),
+            )
+            .unwrap()
+            .con_politica(Politica::Acotar)
+            .con_paso(Duration::from_millis(250))
+            .unwrap()
+            .excluir_min()
+            .unwrap()
+            .excluir_max()
+            .unwrap();
+            rango.set(Duration::ZERO, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), Duration::from_millis(250));
+            rango.set(Duration::from_secs(5), "[Prueba]").unwrap();
+            assert_eq!(rango.get(), Duration::from_millis(750));
+        }
+    }
+
+    mod restringir {
+        use super::*;
+
+        #[test]
+        fn test_estrecha_y_restablece() {
+            let mut rango = Rango::new(0, 100, 50).unwrap();
+            rango.restringir(0, 80).unwrap();
+
+            assert_eq!(rango.get_rango().max, 80);
+            assert_eq!(rango.get_nominal().max, 100);
+            assert!(rango.set(90, "[Prueba]").is_err());
+            rango.set(80, "[Prueba]").unwrap();
+
+            rango.restablecer();
+            rango.set(90, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), 90);
+        }
+
+        #[test]
+        fn test_limites_invalidos() {
+            let mut rango = Rango::new(10, 100, 50).unwrap();
+            assert_eq!(rango.restringir(0, 80), Err(LimitesError::LimitesInvalidos));
+            assert_eq!(
+                rango.restringir(10, 120),
+                Err(LimitesError::LimitesInvalidos)
+            );
+            assert_eq!(
+                rango.restringir(60, 40),
+                Err(LimitesError::LimitesInvalidos)
+            );
+            assert_eq!(rango.get_rango().max, 100);
+        }
+
+        #[test]
+        fn test_valor_actual_fuera() {
+            let mut rango = Rango::new(0, 100, 50).unwrap();
+            assert_eq!(rango.restringir(0, 40), Err(LimitesError::ValorFuera));
+            assert_eq!(rango.get_rango().max, 100, "Los límites no deben cambiar");
+        }
+
+        #[test]
+        fn test_acotar_a_lo_restringido() {
+            let mut rango = Rango::new(0, 100, 50)
+                .unwrap()
+                .con_politica(Politica::Acotar);
+            rango.restringir(0, 80).unwrap();
+            rango.set(95, "[Prueba]").unwrap();
+            assert_eq!(rango.get(), 80);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use types::logs::Valor;
+use types::rango::Rango;
+use types::unidades::{
+    Gramos, GramosPorSegundo, KgPorSegundo, Kilogramos, Milisegundos, Porcentaje, UnidadError,
+};
+
+#[cfg(test)]
+mod unidades {
+    use super::*;
+
+    mod conversiones {
+        use super::*;
+
+        #[test]
+        fn test_milisegundos() {
+            assert_eq!(Milisegundos::segundos(2), Milisegundos(2000));
+            assert_eq!(Milisegundos::minutos(1), Milisegundos(60_000));
+            assert_eq!(
+                Duration::from(Milisegundos(1500)),
+                Duration::from_millis(1500)
+            );
+            assert_eq!(
+                Milisegundos::desde_duration(Duration::from_micros(2999)),
+                Milisegundos(2)
+            );
+            assert_eq!(
+                Milisegundos::desde_duration(Duration::from_secs(u64::MAX)),
+                Milisegundos(u32::MAX)
+            );
+        }
+
+        #[test]
+        fn test_masa() {
+            assert_eq!(Gramos::try_from(Kilogramos(24)), Ok(Gramos(24_000)));
+            assert_eq!(Gramos(24_999).a_kilogramos(), Kilogramos(24));
+            assert_eq!(
+                Gramos::try_from(Kilogramos(u32::MAX / 1000)),
+                Ok(Gramos(u32::MAX / 1000 * 1000))
+            );
+            assert_eq!(
+                Gramos::try_from(Kilogramos(u32::MAX / 1000 + 1)),
+                Err(UnidadError::Desborde)
+            );
+        }
+
+        #[test]
+        fn test_caudal() {
+            assert_eq!(
+                GramosPorSegundo::try_from(KgPorSegundo(2)),
+                Ok(GramosPorSegundo(2000))
+            );
+            assert_eq!(
+                GramosPorSegundo::try_from(KgPorSegundo(u32::MAX)),
+                Err(UnidadError::Desborde)
+            );
+            assert_eq!(GramosPorSegundo(2500).a_kg_por_segundo(), KgPorSegundo(2));
+        }
+    }
+
+    mod operaciones {
+        use super::*;
+
+        #[test]
+        fn test_caudal_por_tiempo() {
+            assert_eq!(GramosPorSegundo(1500) * Milisegundos(5000), Gramos(7500));
+            assert_eq!(KgPorSegundo(2) * Milisegundos(1), Gramos(2));
+            assert_eq!(GramosPorSegundo(1) * Milisegundos(999), Gramos(0));
+            assert_eq!(
+                GramosPorSegundo(u32::MAX) * Milisegundos(u32::MAX),
+                Gramos(u32::MAX)
+            );
+        }
+
+        #[test]
+        fn test_suma_y_resta() {
+            let mut total = Gramos(100) + Gramos(50);
+            total -= Gramos(30);
+            total += Gramos(10);
+            assert_eq!(total, Gramos(130));
+            assert_eq!(Gramos(10).saturating_sub(Gramos(20)), Gramos(0));
+            assert_eq!(
+                Milisegundos(u32::MAX).saturating_add(Milisegundos(1)),
+                Milisegundos(u32::MAX)
+            );
+        }
+
+        #[test]
+        fn test_sum() {
+            let pulsos = [Gramos(2250), Gramos(2250), Gramos(500)];
+            assert_eq!(pulsos.iter().copied().sum::<Gramos>(), Gramos(5000));
+        }
+    }
+
+    mod texto {
+        use super::*;
+
+        #[test]
+        fn test_display() {
+            assert_eq!(Milisegundos(250).to_string(), "250ms");
+            assert_eq!(Gramos(8850).to_string(), "8850g");
+            assert_eq!(Kilogramos(24).to_string(), "24kg");
+            assert_eq!(GramosPorSegundo(1500).to_string(), "1500g/s");
+            assert_eq!(KgPorSegundo(2).to_string(), "2kg/s");
+            assert_eq!(Porcentaje(80).to_string(), "80%");
+        }
+
+        #[test]
+        fn test_valor_sin_sufijo() {
+            assert_eq!(Valor::from(Gramos(8850)), Valor::Entero(8850));
+        }
+
+        #[test]
+        fn test_rango_muestra_la_unidad() {
+            let rango = Rango::new(Porcentaje(0), Porcentaje(100), Porcentaje(50)).unwrap();
+            assert_eq!(rango.get(), Porcentaje(50));
+            assert!(Rango::new(Porcentaje(0), Porcentaje(100), Porcentaje(101)).is_err());
+        }
+    }
+}